@@ -0,0 +1,188 @@
+use crate::error::PawError;
+use std::future::Future;
+use std::time::Duration;
+
+/// Exponential backoff applied to the requests that failed with a retryable error.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Backoff {
+    pub max_attempts: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Backoff {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(10),
+        }
+    }
+}
+
+impl Backoff {
+    /// Delay before the attempt following `attempt`, doubling each time up to `max_delay`.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay)
+    }
+
+    /// Retries requests that are safe to send twice on any retryable error.
+    pub async fn retry<T, F, Fut>(&self, operation: F) -> Result<T, PawError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, PawError>>,
+    {
+        self.retry_if(operation, PawError::is_retryable).await
+    }
+
+    /// Retries requests that must not run twice, as starting an execution without a name, only
+    /// when Step Functions throttled them. A request that timed out may have been carried out,
+    /// and the SDK already retried it.
+    pub async fn retry_throttled<T, F, Fut>(&self, operation: F) -> Result<T, PawError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, PawError>>,
+    {
+        self.retry_if(operation, PawError::is_throttling).await
+    }
+
+    async fn retry_if<T, F, Fut>(
+        &self,
+        mut operation: F,
+        retryable: fn(&PawError) -> bool,
+    ) -> Result<T, PawError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, PawError>>,
+    {
+        let mut attempt = 1;
+
+        loop {
+            match operation().await {
+                Err(error) if retryable(&error) && attempt < self.max_attempts => {
+                    tokio::time::sleep(self.delay(attempt)).await;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_sfn::error::{ErrorMetadata, SdkError};
+    use aws_sdk_sfn::operation::start_execution::StartExecutionError;
+    use aws_sdk_sfn::types::error::ExecutionLimitExceeded;
+    use aws_smithy_runtime_api::http::{Response, StatusCode};
+    use aws_smithy_types::body::SdkBody;
+    use std::cell::Cell;
+
+    fn no_delay(max_attempts: u32) -> Backoff {
+        Backoff {
+            max_attempts,
+            base_delay: Duration::ZERO,
+            max_delay: Duration::ZERO,
+        }
+    }
+
+    fn transient_error() -> PawError {
+        PawError::Transient(Box::new(aws_sdk_sfn::Error::ExecutionLimitExceeded(
+            ExecutionLimitExceeded::builder()
+                .message("slow down")
+                .build(),
+        )))
+    }
+
+    #[test]
+    fn should_double_delay_up_to_max() {
+        let backoff = Backoff::default();
+
+        assert_eq!(backoff.delay(1), Duration::from_millis(500));
+        assert_eq!(backoff.delay(2), Duration::from_secs(1));
+        assert_eq!(backoff.delay(3), Duration::from_secs(2));
+        assert_eq!(backoff.delay(10), Duration::from_secs(10));
+        assert_eq!(backoff.delay(u32::MAX), Duration::from_secs(10));
+    }
+
+    #[tokio::test]
+    async fn should_retry_transient_errors_until_success() {
+        let attempts = Cell::new(0);
+
+        let result = no_delay(3)
+            .retry(|| async {
+                attempts.set(attempts.get() + 1);
+                if attempts.get() < 3 {
+                    Err(transient_error())
+                } else {
+                    Ok("started")
+                }
+            })
+            .await;
+
+        assert_eq!(result.unwrap(), "started");
+        assert_eq!(attempts.get(), 3);
+    }
+
+    #[tokio::test]
+    async fn should_give_up_after_max_attempts() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), PawError> = no_delay(2)
+            .retry(|| async {
+                attempts.set(attempts.get() + 1);
+                Err(transient_error())
+            })
+            .await;
+
+        assert!(matches!(result, Err(PawError::Transient(_))));
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_only_retry_throttling_of_requests_that_must_not_run_twice() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), PawError> = no_delay(3)
+            .retry_throttled(|| async {
+                attempts.set(attempts.get() + 1);
+                match attempts.get() {
+                    1 => Err(SdkError::service_error(
+                        StartExecutionError::generic(
+                            ErrorMetadata::builder().code("ThrottlingException").build(),
+                        ),
+                        Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty()),
+                    )
+                    .into()),
+                    _ => Err(
+                        SdkError::<StartExecutionError, Response>::timeout_error("timeout").into(),
+                    ),
+                }
+            })
+            .await;
+
+        assert!(matches!(result, Err(PawError::Transient(_))));
+        assert_eq!(attempts.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn should_not_retry_other_errors() {
+        let attempts = Cell::new(0);
+
+        let result: Result<(), PawError> = no_delay(3)
+            .retry(|| async {
+                attempts.set(attempts.get() + 1);
+                Err(PawError::MissingField("input"))
+            })
+            .await;
+
+        assert!(matches!(result, Err(PawError::MissingField("input"))));
+        assert_eq!(attempts.get(), 1);
+    }
+}
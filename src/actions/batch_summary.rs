@@ -0,0 +1,62 @@
+use crate::error::PawError;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{stdout, BufWriter, Result as IoResult, Write};
+use std::path::Path;
+
+/// Outcome of every execution of a batch that changes executions, printed as a header with
+/// the counters of the batch followed by one line per execution.
+pub trait BatchSummary: Serialize {
+    /// What a dry run would have done to the executions, ex. started.
+    const DRY_RUN_VERB: &'static str;
+
+    /// Counters of the header in the order they are printed, ex. (2, "succeeded").
+    fn counters(&self) -> Vec<(usize, &'static str)>;
+
+    fn planned(&self) -> usize;
+
+    fn failed(&self) -> usize;
+
+    /// Writes what happened to every execution, in the order they were selected.
+    fn write_results<W: Write>(&self, writer: &mut W) -> IoResult<()>;
+
+    fn print<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        if self.planned() > 0 {
+            write!(writer, "Dry run, nothing was {}: ", Self::DRY_RUN_VERB)?;
+        }
+        let counters: Vec<String> = self
+            .counters()
+            .into_iter()
+            .map(|(count, counter)| format!("{} {}", count, counter))
+            .collect();
+        writeln!(writer, "{}", counters.join(", "))?;
+
+        self.write_results(writer)
+    }
+
+    fn write_to_file(&self, path: &Path) -> IoResult<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer_pretty(&mut writer, self)?;
+        writeln!(writer)?;
+        writer.flush()
+    }
+}
+
+/// Prints the summary, writes it to `summary_file` when given and fails when any execution
+/// of the batch failed.
+pub fn report<S: BatchSummary>(summary: &S, summary_file: Option<&Path>) -> Result<(), PawError> {
+    summary.print(&mut stdout().lock())?;
+
+    if let Some(summary_file) = summary_file {
+        summary.write_to_file(summary_file)?;
+    }
+
+    match summary.failed() {
+        0 => Ok(()),
+        failed => Err(PawError::PartialFailure(failed)),
+    }
+}
+
+pub(super) fn is_zero(count: &usize) -> bool {
+    *count == 0
+}
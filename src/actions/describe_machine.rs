@@ -0,0 +1,212 @@
+use crate::actions::{find_machine, print_output, select_machine, StepFunctionsAction};
+use crate::aws::model::{ClientOptions, StateMachine};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::error::PawError;
+use crate::output::OutputFormat;
+use crate::transform::pretty_input;
+use async_trait::async_trait;
+use chrono::SecondsFormat;
+use console::Term;
+use dialoguer::theme::ColorfulTheme;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct DescribeMachine {
+    client_options: ClientOptions,
+    options: Option<DescribeMachineOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DescribeMachineOptions {
+    pub machine: String,
+    pub output: OutputFormat,
+}
+
+impl DescribeMachine {
+    pub fn new(client_options: ClientOptions) -> Self {
+        DescribeMachine {
+            client_options,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: DescribeMachineOptions) -> Self {
+        DescribeMachine {
+            client_options,
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(&self, options: &DescribeMachineOptions) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let state_machine = find_machine(&machine, &options.machine).await?;
+        // A machine given by ARN was described already to find it.
+        let described_machine = match state_machine.details {
+            Some(_) => state_machine,
+            None => machine.describe_state_machine(state_machine.arn).await?,
+        };
+
+        match options.output {
+            OutputFormat::Table => {
+                print!("{}", DescribeMachine::describe(&described_machine));
+                Ok(())
+            }
+            output => print_output(&[described_machine], output),
+        }
+    }
+
+    /// Every detail of a described machine, one per line, followed by its pretty printed
+    /// definition.
+    fn describe(state_machine: &StateMachine) -> String {
+        let mut description = format!(
+            "Name:       {}\nARN:        {}\n",
+            state_machine.name, state_machine.arn
+        );
+
+        let Some(details) = &state_machine.details else {
+            return description;
+        };
+
+        let logging = match &details.logging {
+            Some(logging) if logging.level != "OFF" => {
+                let mut logging_line = format!(
+                    "{}, execution data {}",
+                    logging.level,
+                    match logging.include_execution_data {
+                        true => "included",
+                        false => "excluded",
+                    }
+                );
+                if !logging.log_groups.is_empty() {
+                    logging_line.push_str(&format!(", to {}", logging.log_groups.join(", ")));
+                }
+                logging_line
+            }
+            _ => "OFF".to_string(),
+        };
+
+        description.push_str(&format!(
+            "Type:       {}\nStatus:     {}\nCreated:    {}\nRole:       {}\nLogging:    {}\nTracing:    {}\nDefinition:\n",
+            details.machine_type.as_str(),
+            details.status.map(|status| status.as_str()).unwrap_or("UNKNOWN"),
+            details.creation_date.to_rfc3339_opts(SecondsFormat::Secs, true),
+            details.role_arn,
+            logging,
+            match details.tracing_enabled {
+                true => "enabled",
+                false => "disabled",
+            }
+        ));
+        for line in pretty_input(&details.definition).lines() {
+            description.push_str(&format!("  {}\n", line));
+        }
+
+        description
+    }
+}
+
+impl Display for DescribeMachine {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for DescribeMachine {
+    fn name(&self) -> String {
+        "Describe Machine".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+        let described_machine = machine.describe_state_machine(selected_machine.arn).await?;
+
+        print!("{}", DescribeMachine::describe(&described_machine));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::model::{
+        LoggingConfiguration, MachineStatus, MachineType, StateMachineDetails,
+    };
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn should_return_name() {
+        let describe_machine = DescribeMachine::new(ClientOptions::default());
+
+        assert_eq!(describe_machine.name(), "Describe Machine".to_string());
+    }
+
+    #[test]
+    fn should_describe_machine() {
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: Some(StateMachineDetails {
+                machine_type: MachineType::Express,
+                status: Some(MachineStatus::Active),
+                creation_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                    .unwrap()
+                    .with_timezone(&Utc),
+                role_arn: "dinosaur::role".to_string(),
+                logging: Some(LoggingConfiguration {
+                    level: "ERROR".to_string(),
+                    include_execution_data: false,
+                    log_groups: vec!["dinosaur::logs".to_string()],
+                }),
+                tracing_enabled: true,
+                definition: "{\"StartAt\":\"Roar\",\"States\":{\"Roar\":{\"Type\":\"Succeed\"}}}"
+                    .to_string(),
+            }),
+        };
+
+        assert_eq!(
+            DescribeMachine::describe(&state_machine),
+            r#"Name:       dinosaur
+ARN:        dinosaur::arn
+Type:       EXPRESS
+Status:     ACTIVE
+Created:    1989-09-30T22:10:32Z
+Role:       dinosaur::role
+Logging:    ERROR, execution data excluded, to dinosaur::logs
+Tracing:    enabled
+Definition:
+  {
+    "StartAt": "Roar",
+    "States": {
+      "Roar": {
+        "Type": "Succeed"
+      }
+    }
+  }
+"#
+        );
+    }
+
+    #[test]
+    fn should_describe_machine_without_details() {
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+
+        assert_eq!(
+            DescribeMachine::describe(&state_machine),
+            "Name:       dinosaur\nARN:        dinosaur::arn\n"
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use crate::actions::{
+    load_definition, machine_definition, select_diagram_format, select_machine, DefinitionSource,
+    StepFunctionsAction,
+};
+use crate::asl::diagram::{render, DiagramFormat};
+use crate::aws::model::ClientOptions;
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::error::PawError;
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::theme::ColorfulTheme;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct DrawDiagram {
+    client_options: ClientOptions,
+    options: Option<DiagramOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct DiagramOptions {
+    pub definition: DefinitionSource,
+    pub format: DiagramFormat,
+}
+
+impl DrawDiagram {
+    pub fn new(client_options: ClientOptions) -> Self {
+        DrawDiagram {
+            client_options,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: DiagramOptions) -> Self {
+        DrawDiagram {
+            client_options,
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(&self, options: &DiagramOptions) -> Result<(), PawError> {
+        let definition = load_definition(&self.client_options, &options.definition, false).await?;
+
+        print!(
+            "{}",
+            render(&definition.parse()?, &definition.label, options.format)
+        );
+
+        Ok(())
+    }
+}
+
+impl Display for DrawDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for DrawDiagram {
+    fn name(&self) -> String {
+        "Draw Machine Diagram".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+        let definition =
+            machine_definition(machine.describe_state_machine(selected_machine.arn).await?)?;
+
+        let format = select_diagram_format(&theme, &term)?;
+
+        print!(
+            "{}",
+            render(&definition.parse()?, &definition.label, format)
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_return_name() {
+        let draw_diagram = DrawDiagram::new(ClientOptions::default());
+
+        assert_eq!(draw_diagram.name(), "Draw Machine Diagram".to_string());
+    }
+}
@@ -0,0 +1,349 @@
+use crate::actions::{
+    select_diagram_format, select_execution, select_machine, LoadedDefinition, StepFunctionsAction,
+};
+use crate::asl::diagram::{render_execution, DiagramFormat, StateActivity, StateStatus};
+use crate::aws::model::{ClientOptions, HistoryEvent, MachineType};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::theme::ColorfulTheme;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct DrawExecutionDiagram {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<ExecutionDiagramOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ExecutionDiagramOptions {
+    pub execution: String,
+    pub format: DiagramFormat,
+}
+
+/// One run of a state, from its StateEntered event to its StateExited event.
+struct Visit {
+    state_name: String,
+    failures: u32,
+    /// The last event of the visit is a failure, which the state did not retry yet.
+    failing: bool,
+    status: Option<StateStatus>,
+}
+
+impl DrawExecutionDiagram {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        DrawExecutionDiagram {
+            client_options,
+            settings,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: ExecutionDiagramOptions) -> Self {
+        DrawExecutionDiagram {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(
+        &self,
+        options: &ExecutionDiagramOptions,
+    ) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        print!(
+            "{}",
+            DrawExecutionDiagram::draw(&machine, options.execution.clone(), options.format).await?
+        );
+
+        Ok(())
+    }
+
+    /// Draws the definition the execution ran, not the current one of its machine.
+    async fn draw(
+        machine: &StepFunctionsMachine,
+        execution_arn: String,
+        format: DiagramFormat,
+    ) -> Result<String, PawError> {
+        // Express execution ARNs read `arn:aws:states:<region>:<account>:express:...`.
+        if execution_arn.split(':').nth(5) == Some("express") {
+            return Err(DrawExecutionDiagram::express_error(&execution_arn));
+        }
+
+        let execution = machine.describe_execution(execution_arn.clone()).await?;
+        let definition = LoadedDefinition {
+            label: execution.name,
+            source: machine.execution_definition(execution_arn.clone()).await?,
+            // Express executions were rejected above.
+            express: false,
+        };
+        let events = machine.get_execution_history(execution_arn).await?;
+
+        Ok(render_execution(
+            &definition.parse()?,
+            &definition.label,
+            format,
+            &DrawExecutionDiagram::state_activity(&events),
+        ))
+    }
+
+    /// Step Functions keeps no history of Express executions, only their CloudWatch logs do.
+    fn express_error(name: &str) -> PawError {
+        PawError::InvalidInput(format!(
+            "{} is an Express workflow, Step Functions keeps no execution history for it to draw",
+            name
+        ))
+    }
+
+    /// What the execution did in every state it entered. Retries are the failures the state
+    /// ran again after, a state that failed for good or moved on through a Catch does not
+    /// count its last failure.
+    fn state_activity(events: &[HistoryEvent]) -> HashMap<String, StateActivity> {
+        let mut visits: Vec<Visit> = vec![];
+        // Concurrent Map iterations interleave their events, so events are matched to their
+        // visit through the event they follow.
+        let mut visit_of_event: HashMap<i64, usize> = HashMap::new();
+
+        for event in events {
+            let Some(state_name) = &event.state_name else {
+                continue;
+            };
+
+            let index = if event.event_type.ends_with("StateEntered") {
+                visits.push(Visit {
+                    state_name: state_name.clone(),
+                    failures: 0,
+                    failing: false,
+                    status: None,
+                });
+                visits.len() - 1
+            } else {
+                match visit_of_event.get(&event.previous_event_id) {
+                    Some(index) => *index,
+                    None => continue,
+                }
+            };
+            visit_of_event.insert(event.id, index);
+
+            let visit = &mut visits[index];
+            if event.event_type.ends_with("StateExited") {
+                visit.status = Some(match visit.failing {
+                    true => StateStatus::Caught,
+                    false => StateStatus::Succeeded,
+                });
+            } else {
+                visit.failing = event.error.is_some();
+                visit.failures += event.error.is_some() as u32;
+            }
+        }
+
+        let execution_ended = events.last().is_some_and(|event| {
+            matches!(
+                event.event_type.as_str(),
+                "ExecutionFailed" | "ExecutionTimedOut" | "ExecutionAborted"
+            )
+        });
+
+        let mut activity: HashMap<String, StateActivity> = HashMap::new();
+        for visit in visits {
+            let status = visit
+                .status
+                .unwrap_or(match visit.failing || execution_ended {
+                    true => StateStatus::Failed,
+                    false => StateStatus::Running,
+                });
+            let retries = visit.failures - visit.failing as u32;
+
+            activity
+                .entry(visit.state_name)
+                .and_modify(|state| {
+                    state.status = state.status.max(status);
+                    state.runs += 1;
+                    state.retries += retries;
+                })
+                .or_insert(StateActivity {
+                    status,
+                    runs: 1,
+                    retries,
+                });
+        }
+
+        activity
+    }
+}
+
+impl Display for DrawExecutionDiagram {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for DrawExecutionDiagram {
+    fn name(&self) -> String {
+        "Draw Execution Diagram".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+        let machine_type = machine
+            .describe_state_machine(selected_machine.arn.clone())
+            .await?
+            .details
+            .map(|details| details.machine_type);
+        if machine_type == Some(MachineType::Express) {
+            return Err(DrawExecutionDiagram::express_error(&selected_machine.name));
+        }
+
+        let Some(execution) = select_execution(
+            &machine,
+            &selected_machine,
+            &self.settings.range,
+            "Select the execution to draw:",
+            &theme,
+            &term,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+        let format = select_diagram_format(&theme, &term)?;
+
+        print!(
+            "{}",
+            DrawExecutionDiagram::draw(&machine, execution.arn, format).await?
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::step_functions::MockStepFunctionsClient;
+    use chrono::{TimeZone, Utc};
+
+    fn event(
+        id: i64,
+        previous_event_id: i64,
+        event_type: &str,
+        state_name: Option<&str>,
+        error: Option<&str>,
+    ) -> HistoryEvent {
+        HistoryEvent {
+            id,
+            previous_event_id,
+            event_type: event_type.to_string(),
+            timestamp: Utc.timestamp_opt(0, 0).unwrap(),
+            state_name: state_name.map(String::from),
+            input: None,
+            output: None,
+            error: error.map(String::from),
+            cause: None,
+        }
+    }
+
+    fn activity(status: StateStatus, runs: u32, retries: u32) -> StateActivity {
+        StateActivity {
+            status,
+            runs,
+            retries,
+        }
+    }
+
+    #[test]
+    fn should_return_name() {
+        let draw_execution =
+            DrawExecutionDiagram::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(draw_execution.name(), "Draw Execution Diagram".to_string());
+    }
+
+    #[tokio::test]
+    async fn should_reject_express_executions() {
+        let machine = StepFunctionsMachine::with_client(MockStepFunctionsClient::default());
+
+        let error = DrawExecutionDiagram::draw(
+            &machine,
+            "arn:aws:states:us-east-1:123456789012:express:PawMachine:dinosaur:42".to_string(),
+            DiagramFormat::Mermaid,
+        )
+        .await
+        .unwrap_err();
+
+        assert!(matches!(
+            error,
+            PawError::InvalidInput(message) if message.contains("no execution history")
+        ));
+    }
+
+    #[test]
+    fn should_find_what_the_execution_did_in_each_state() {
+        let events = vec![
+            event(1, 0, "ExecutionStarted", None, None),
+            event(2, 1, "TaskStateEntered", Some("Hunt"), None),
+            event(3, 2, "TaskScheduled", Some("Hunt"), None),
+            event(4, 3, "TaskFailed", Some("Hunt"), Some("Escaped")),
+            event(5, 4, "TaskScheduled", Some("Hunt"), None),
+            event(6, 5, "TaskSucceeded", Some("Hunt"), None),
+            event(7, 6, "TaskStateExited", Some("Hunt"), None),
+            event(8, 7, "TaskStateEntered", Some("Eat"), None),
+            event(9, 8, "TaskScheduled", Some("Eat"), None),
+            event(10, 9, "TaskFailed", Some("Eat"), Some("Choked")),
+            event(11, 10, "TaskStateExited", Some("Eat"), None),
+            event(12, 11, "TaskStateEntered", Some("Hunt"), None),
+            event(13, 12, "TaskScheduled", Some("Hunt"), None),
+            event(14, 13, "TaskFailed", Some("Hunt"), Some("Escaped")),
+            event(15, 14, "TaskScheduled", Some("Hunt"), None),
+            event(16, 15, "TaskFailed", Some("Hunt"), Some("Escaped")),
+            event(17, 16, "ExecutionFailed", None, Some("Escaped")),
+        ];
+
+        assert_eq!(
+            DrawExecutionDiagram::state_activity(&events),
+            HashMap::from([
+                ("Hunt".to_string(), activity(StateStatus::Failed, 2, 2)),
+                ("Eat".to_string(), activity(StateStatus::Caught, 1, 0)),
+            ])
+        );
+    }
+
+    #[test]
+    fn should_follow_interleaved_map_iterations() {
+        let events = vec![
+            event(1, 0, "ExecutionStarted", None, None),
+            event(2, 1, "MapStateEntered", Some("Eggs"), None),
+            event(3, 2, "MapStateStarted", Some("Eggs"), None),
+            event(4, 3, "TaskStateEntered", Some("Hatch"), None),
+            event(5, 3, "TaskStateEntered", Some("Hatch"), None),
+            event(6, 4, "TaskScheduled", Some("Hatch"), None),
+            event(7, 5, "TaskScheduled", Some("Hatch"), None),
+            event(8, 6, "TaskSucceeded", Some("Hatch"), None),
+            event(9, 7, "TaskFailed", Some("Hatch"), Some("Cracked")),
+            event(10, 8, "TaskStateExited", Some("Hatch"), None),
+            event(11, 9, "TaskScheduled", Some("Hatch"), None),
+        ];
+
+        assert_eq!(
+            DrawExecutionDiagram::state_activity(&events),
+            HashMap::from([
+                ("Eggs".to_string(), activity(StateStatus::Running, 1, 0)),
+                ("Hatch".to_string(), activity(StateStatus::Running, 2, 1)),
+            ])
+        );
+    }
+}
@@ -1,76 +1,686 @@
-use crate::actions::StepFunctionsAction;
-use crate::aws::model::{ExecutionInput, StateMachineExecution};
+use crate::actions::backoff::Backoff;
+use crate::actions::batch_summary::report;
+use crate::actions::retry_journal::RetryJournal;
+use crate::actions::retry_summary::{RetryOutcome, RetryResult, RetrySummary};
+use crate::actions::{
+    find_machine, get_user_date_range, run_concurrently, select_machine, select_statuses,
+    StepFunctionsAction,
+};
+use crate::aws::model::{
+    ClientOptions, DateRange, ExecutionInput, ExecutionStatus, FailureSignature, RedriveOutcome,
+    RedriveStatus, RootCause, StartOutcome, StateMachineExecution,
+};
 use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use crate::transform::{input_diff, pretty_input, validate_input, InputTransform};
 use async_trait::async_trait;
-use aws_sdk_sfn::Error;
-use chrono::{DateTime, ParseError, Utc};
 use console::Term;
-use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use dialoguer::{theme::ColorfulTheme, Confirm, Editor, Input, MultiSelect, Select};
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
-pub struct RetryFailedExecution {}
+/// Number of executions retried at the same time when neither the options nor the settings set it.
+pub const DEFAULT_CONCURRENCY: usize = 1;
+/// Executions described or read at the same time when nothing is changed yet.
+pub const DEFAULT_READ_CONCURRENCY: usize = 10;
+
+pub struct RetryFailedExecution {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<RetryFailedOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct RetryFailedOptions {
+    pub machine: String,
+    pub statuses: Vec<ExecutionStatus>,
+    pub range: DateRange,
+    pub concurrency: usize,
+    pub summary_file: Option<PathBuf>,
+    pub journal: Option<PathBuf>,
+    pub idempotent_names: bool,
+    pub transform: Option<InputTransform>,
+    pub redrive: bool,
+    pub dry_run: bool,
+    pub yes: bool,
+}
+
+/// How a batch of executions is retried.
+pub struct RetryBatch {
+    pub concurrency: usize,
+    /// Names each retry after the original execution, so a repeated retry is not started twice.
+    pub idempotent_names: bool,
+    /// Applied to every input before the retry is started.
+    pub transform: Option<InputTransform>,
+    /// Redrives the executions that allow it from the state that failed, restarting the others.
+    pub redrive: bool,
+    pub dry_run: bool,
+    pub journal: Option<RetryJournal>,
+    /// Plans recorded in the journal of a resumed batch, by execution ARN. The executions
+    /// without one are retried the way the batch says.
+    pub plans: HashMap<String, RetryPlan>,
+    /// Executions of a resumed batch that were being started without a name when it was
+    /// interrupted. They may be running already, so they are skipped instead of started again.
+    pub interrupted: HashSet<String>,
+}
+
+/// How one execution of a batch is retried, recorded in the journal so a resumed batch
+/// retries it the way it was planned whatever the options of the resume.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RetryPlan {
+    /// Name of the retry when the batch uses idempotent names.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub execution_name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transform: Option<InputTransform>,
+    /// Input edited by hand, started as it is instead of the transformed input.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edited_input: Option<String>,
+    /// Redrives the execution from the state that failed when Step Functions allows it.
+    #[serde(default)]
+    pub redrive: bool,
+}
+
+impl RetryBatch {
+    fn plan(&self, execution: &StateMachineExecution) -> RetryPlan {
+        match self.plans.get(&execution.arn) {
+            Some(plan) => plan.clone(),
+            None => RetryPlan {
+                execution_name: self.idempotent_names.then(|| execution.retry_name()),
+                transform: self.transform.clone(),
+                edited_input: None,
+                redrive: self.redrive,
+            },
+        }
+    }
+}
 
 impl RetryFailedExecution {
-    pub fn new() -> Self {
-        RetryFailedExecution {}
-    }
-
-    fn parse_utc_date_time(raw_date_time: String) -> Result<Option<DateTime<Utc>>, ParseError> {
-        if raw_date_time.is_empty() {
-            Ok(None)
-        } else {
-            let parsed_date = DateTime::parse_from_str(&raw_date_time, "%Y-%m-%d %H:%M:%S %z");
-            match parsed_date {
-                Ok(date) => Ok(Some(date.with_timezone(&Utc))),
-                Err(error) => Err(error),
-            }
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        RetryFailedExecution {
+            client_options,
+            settings,
+            options: None,
         }
     }
 
-    fn get_user_date_input(prompt_message: &str) -> Option<DateTime<Utc>> {
-        loop {
-            let date_str: String = Input::with_theme(&ColorfulTheme::default())
-                .with_prompt(prompt_message)
-                .allow_empty(true)
-                .interact_on(&Term::buffered_stderr())
-                .unwrap();
-            let parsed_date = RetryFailedExecution::parse_utc_date_time(date_str);
+    pub fn with_options(client_options: ClientOptions, options: RetryFailedOptions) -> Self {
+        RetryFailedExecution {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    /// Reads the last events of every execution to tell why it failed, at most `concurrency` at
+    /// a time. Executions whose history cannot be read are kept without a root cause.
+    async fn find_root_causes(
+        machine: &StepFunctionsMachine,
+        executions: Vec<StateMachineExecution>,
+        concurrency: usize,
+    ) -> Vec<StateMachineExecution> {
+        let progress_bar = ProgressBar::new(executions.len() as u64);
+        progress_bar.set_style(ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}]({pos} of {len}) Reading history: {msg}")
+            .progress_chars("#>-"));
+        let backoff = Backoff::default();
+
+        let root_causes: Vec<Option<RootCause>> =
+            run_concurrently(executions.len(), concurrency, |index| {
+                let execution = &executions[index];
+                let (progress_bar, backoff) = (&progress_bar, &backoff);
+
+                async move {
+                    let root_cause = backoff
+                        .retry(|| machine.find_root_cause(execution.arn.clone()))
+                        .await
+                        .ok()
+                        .flatten();
+
+                    progress_bar.set_message(execution.name.clone());
+                    progress_bar.inc(1);
+
+                    root_cause
+                }
+            })
+            .await;
+
+        progress_bar.finish_and_clear();
+
+        executions
+            .into_iter()
+            .zip(root_causes)
+            .map(|(execution, root_cause)| StateMachineExecution {
+                root_cause,
+                ..execution
+            })
+            .collect()
+    }
 
-            match parsed_date {
-                Ok(date) => return date,
-                Err(error) => println!("Invalid date ({}). Please try again!", error),
+    /// Groups the executions by failure signature, the largest groups first.
+    fn group_by_signature(
+        executions: Vec<StateMachineExecution>,
+    ) -> Vec<(FailureSignature, Vec<StateMachineExecution>)> {
+        let mut groups: Vec<(FailureSignature, Vec<StateMachineExecution>)> = vec![];
+        // Index of the group of every signature, the groups keep the order they were first seen.
+        let mut group_indexes: HashMap<FailureSignature, usize> = HashMap::new();
+        for execution in executions {
+            let signature = execution
+                .root_cause
+                .as_ref()
+                .map(RootCause::signature)
+                .unwrap_or_default();
+
+            match group_indexes.get(&signature) {
+                Some(&index) => groups[index].1.push(execution),
+                None => {
+                    group_indexes.insert(signature.clone(), groups.len());
+                    groups.push((signature, vec![execution]));
+                }
             }
         }
+        // Stable, so groups of the same size keep the order of their newest execution.
+        groups.sort_by_key(|(_, group_executions)| Reverse(group_executions.len()));
+
+        groups
     }
 
-    async fn retry_selected_failed_executions(
-        selected_executions_to_retry: Vec<usize>,
-        failed_executions: Vec<StateMachineExecution>,
-    ) -> Result<(), Error> {
-        let machine = StepFunctionsMachine::new().await;
-        let progress_bar = ProgressBar::new(selected_executions_to_retry.len() as u64);
+    /// Lets the user retry or skip whole groups of executions that failed the same way, then
+    /// optionally go through the executions of the selected groups one by one.
+    fn select_executions(
+        executions: Vec<StateMachineExecution>,
+        theme: &ColorfulTheme,
+        term: &Term,
+    ) -> Result<Vec<StateMachineExecution>, PawError> {
+        let groups = RetryFailedExecution::group_by_signature(executions);
+        let checked_groups: Vec<(String, bool)> = groups
+            .iter()
+            .map(|(signature, group_executions)| {
+                (format!("{} x {}", group_executions.len(), signature), true)
+            })
+            .collect();
+        let selected_groups = MultiSelect::with_theme(theme)
+            .with_prompt("Select the failures to retry:")
+            .items_checked(&checked_groups)
+            .interact_on_opt(term)?
+            .ok_or(PawError::Cancelled)?;
+        let selected_executions: Vec<StateMachineExecution> = selected_groups
+            .into_iter()
+            .flat_map(|index| groups[index].1.clone())
+            .collect();
+
+        if selected_executions.is_empty() {
+            return Ok(selected_executions);
+        }
+
+        let review = Confirm::with_theme(theme)
+            .with_prompt(format!(
+                "Review the {} executions one by one?",
+                selected_executions.len()
+            ))
+            .default(false)
+            .interact_on_opt(term)?
+            .ok_or(PawError::Cancelled)?;
+        if !review {
+            return Ok(selected_executions);
+        }
+
+        let checked_executions: Vec<(String, bool)> = selected_executions
+            .iter()
+            .map(|execution| (RetryFailedExecution::picker_label(execution), true))
+            .collect();
+        let selected_executions_to_retry = MultiSelect::with_theme(theme)
+            .with_prompt("Select the executions to retry:")
+            .items_checked(&checked_executions)
+            .interact_on_opt(term)?
+            .ok_or(PawError::Cancelled)?;
+
+        Ok(selected_executions_to_retry
+            .into_iter()
+            .map(|index| selected_executions[index].clone())
+            .collect())
+    }
+
+    /// Picker label of an execution, with why it failed when its history told.
+    fn picker_label(execution: &StateMachineExecution) -> String {
+        match &execution.root_cause {
+            Some(root_cause) => format!("{} {}", execution, root_cause),
+            None => execution.to_string(),
+        }
+    }
+
+    pub(crate) async fn retry_selected_failed_executions(
+        machine: &StepFunctionsMachine,
+        executions: Vec<StateMachineExecution>,
+        batch: &RetryBatch,
+    ) -> Result<RetrySummary, PawError> {
+        if let Some(journal) = &batch.journal {
+            for execution in &executions {
+                journal.record_planned(execution, &batch.plan(execution))?;
+            }
+        }
+
+        let progress_bar = ProgressBar::new(executions.len() as u64);
         progress_bar.set_style(ProgressStyle::default_bar()
             .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}]({pos} of {len}) ID: {msg}")
             .progress_chars("#>-"));
+        let backoff = Backoff::default();
+        let journal_failed = AtomicBool::new(false);
 
-        for index in selected_executions_to_retry.into_iter() {
-            let execution = &failed_executions[index];
-            let full_execution = machine.describe_execution(execution.arn.clone()).await?;
+        // The report keeps the selection order, whatever order the retries finish in.
+        let results: Vec<Result<Option<RetryResult>, io::Error>> =
+            run_concurrently(executions.len(), batch.concurrency, |index| {
+                let execution = &executions[index];
+                let (progress_bar, backoff, journal_failed) =
+                    (&progress_bar, &backoff, &journal_failed);
 
-            progress_bar.set_message(format!("{}", full_execution.name));
-            progress_bar.inc(1);
+                async move {
+                    // Without the journal a resumed batch could start executions twice.
+                    if journal_failed.load(Ordering::Relaxed) {
+                        return Ok(None);
+                    }
 
-            machine
-                .start_execution(ExecutionInput {
-                    machine_arn: full_execution.machine_arn,
-                    input: full_execution.input.unwrap(),
-                })
-                .await?;
+                    if let Some(journal) = &batch.journal {
+                        if batch.plan(execution).execution_name.is_none()
+                            && !batch.interrupted.contains(&execution.arn)
+                        {
+                            journal.record_started(execution).inspect_err(|_| {
+                                journal_failed.store(true, Ordering::Relaxed);
+                            })?;
+                        }
+                    }
+
+                    let result =
+                        RetryFailedExecution::retry_execution(machine, execution, backoff, batch)
+                            .await;
+
+                    if let Some(journal) = &batch.journal {
+                        journal.record_finished(&result).inspect_err(|_| {
+                            journal_failed.store(true, Ordering::Relaxed);
+                        })?;
+                    }
+
+                    progress_bar.set_message(execution.name.clone());
+                    progress_bar.inc(1);
+
+                    Ok(Some(result))
+                }
+            })
+            .await;
+
+        progress_bar.finish_and_clear();
+
+        let results = results
+            .into_iter()
+            .filter_map(Result::transpose)
+            .collect::<Result<Vec<RetryResult>, io::Error>>()?;
+
+        Ok(RetrySummary::new(results))
+    }
+
+    async fn retry_execution(
+        machine: &StepFunctionsMachine,
+        execution: &StateMachineExecution,
+        backoff: &Backoff,
+        batch: &RetryBatch,
+    ) -> RetryResult {
+        RetryResult {
+            name: execution.name.clone(),
+            execution_arn: execution.arn.clone(),
+            outcome: RetryFailedExecution::retry_outcome(machine, execution, backoff, batch).await,
+        }
+    }
+
+    async fn retry_outcome(
+        machine: &StepFunctionsMachine,
+        execution: &StateMachineExecution,
+        backoff: &Backoff,
+        batch: &RetryBatch,
+    ) -> RetryOutcome {
+        if batch.interrupted.contains(&execution.arn) {
+            return RetryOutcome::Skipped {
+                reason: "may have been started before the batch was interrupted".to_string(),
+            };
+        }
+
+        let full_execution = match backoff
+            .retry(|| machine.describe_execution(execution.arn.clone()))
+            .await
+        {
+            Ok(full_execution) => full_execution,
+            Err(error) => {
+                return RetryOutcome::Failed {
+                    reason: error.to_string(),
+                }
+            }
+        };
+
+        // Redriven or restarted since it was listed, a retry would run it a second time.
+        if !ExecutionStatus::RETRYABLE.contains(&full_execution.status) {
+            return RetryOutcome::Skipped {
+                reason: format!("execution is now {}", full_execution.status),
+            };
+        }
+        if full_execution.redrive_status == Some(RedriveStatus::RedrivableByMapRun) {
+            return RetryOutcome::Skipped {
+                reason: "execution is redriven through the map run of its parent".to_string(),
+            };
+        }
+
+        let plan = batch.plan(execution);
+        if plan.redrive && full_execution.redrive_status == Some(RedriveStatus::Redrivable) {
+            if batch.dry_run {
+                return RetryOutcome::PlannedRedrive;
+            }
+
+            match backoff
+                .retry_throttled(|| machine.redrive_execution(execution.arn.clone()))
+                .await
+            {
+                Ok(RedriveOutcome::Redriven) => return RetryOutcome::Redriven,
+                // Eligibility can change between the describe and the redrive, restart instead.
+                Ok(RedriveOutcome::NotRedrivable) => {}
+                Err(error) => {
+                    return RetryOutcome::Failed {
+                        reason: error.to_string(),
+                    }
+                }
+            }
+        }
+
+        let Some(original_input) = full_execution.input else {
+            return RetryOutcome::Skipped {
+                reason: "execution has no input to start the retry with".to_string(),
+            };
+        };
+
+        let input = match (&plan.edited_input, &plan.transform) {
+            (Some(edited_input), _) => edited_input.clone(),
+            (None, Some(transform)) => match transform
+                .apply(&original_input)
+                .and_then(|input| validate_input(&input))
+            {
+                Ok(input) => input,
+                Err(error) => {
+                    return RetryOutcome::Failed {
+                        reason: format!("could not transform the input: {}", error),
+                    }
+                }
+            },
+            (None, None) => original_input.clone(),
+        };
+
+        if batch.dry_run {
+            return RetryOutcome::Planned {
+                machine_arn: full_execution.machine_arn,
+                execution_name: plan.execution_name,
+                original_input: (plan.edited_input.is_some() || plan.transform.is_some())
+                    .then_some(original_input),
+                input,
+            };
         }
 
+        let execution_input = ExecutionInput {
+            machine_arn: full_execution.machine_arn,
+            input,
+            name: plan.execution_name,
+        };
+
+        // Step Functions rejects a second execution with the same name, so only an execution
+        // without a name could be started twice by retrying a request that timed out.
+        let start = || machine.start_execution(execution_input.clone());
+        let started = match execution_input.name {
+            Some(_) => backoff.retry(start).await,
+            None => backoff.retry_throttled(start).await,
+        };
+
+        match started {
+            Ok(StartOutcome::Started { execution_arn }) => RetryOutcome::Succeeded {
+                new_execution_arn: execution_arn,
+            },
+            Ok(StartOutcome::AlreadyExists) => RetryOutcome::Skipped {
+                reason: format!(
+                    "already retried as {}",
+                    execution_input.name.unwrap_or_default()
+                ),
+            },
+            Err(error) => RetryOutcome::Failed {
+                reason: error.to_string(),
+            },
+        }
+    }
+
+    /// Describes the selected executions and shows how the transformation changes their
+    /// inputs, returning whether the user wants to go on with the retry.
+    async fn preview_transform(
+        machine: &StepFunctionsMachine,
+        executions: &[StateMachineExecution],
+        transform: &InputTransform,
+        read_concurrency: usize,
+        theme: &ColorfulTheme,
+        term: &Term,
+    ) -> Result<bool, PawError> {
+        RetryFailedExecution::print_transform_diffs(
+            machine,
+            executions,
+            transform,
+            read_concurrency,
+            term,
+        )
+        .await?;
+
+        Ok(Confirm::with_theme(theme)
+            .with_prompt(format!(
+                "Retry {} executions with the transformed inputs?",
+                executions.len()
+            ))
+            .interact_on_opt(term)?
+            .unwrap_or(false))
+    }
+
+    /// Writes how the transformation changes the input of every execution.
+    async fn print_transform_diffs(
+        machine: &StepFunctionsMachine,
+        executions: &[StateMachineExecution],
+        transform: &InputTransform,
+        concurrency: usize,
+        term: &Term,
+    ) -> Result<(), PawError> {
+        for diff in
+            RetryFailedExecution::transform_diffs(machine, executions, transform, concurrency).await
+        {
+            term.write_str(&diff)?;
+        }
+        term.flush()?;
+
         Ok(())
     }
+
+    /// Diff of the original and transformed input of every execution, describing at most
+    /// `concurrency` executions at a time. Executions that would not be started get the
+    /// reason instead of a diff.
+    async fn transform_diffs(
+        machine: &StepFunctionsMachine,
+        executions: &[StateMachineExecution],
+        transform: &InputTransform,
+        concurrency: usize,
+    ) -> Vec<String> {
+        let backoff = Backoff::default();
+
+        run_concurrently(executions.len(), concurrency, |index| {
+            let execution = &executions[index];
+            let backoff = &backoff;
+
+            async move {
+                let diff = match backoff
+                    .retry(|| machine.describe_execution(execution.arn.clone()))
+                    .await
+                {
+                    Ok(StateMachineExecution {
+                        input: Some(input), ..
+                    }) => match transform
+                        .apply(&input)
+                        .and_then(|transformed_input| validate_input(&transformed_input))
+                    {
+                        Ok(transformed_input) => input_diff(&input, &transformed_input),
+                        Err(error) => format!("could not transform the input: {}\n", error),
+                    },
+                    Ok(_) => "no input, will be skipped\n".to_string(),
+                    Err(error) => format!("could not read the input: {}\n", error),
+                };
+
+                format!("{}\n{}", execution, diff)
+            }
+        })
+        .await
+    }
+
+    /// Lets the user fix the input of one of the selected executions in `$EDITOR`, returning
+    /// the ARN of the execution and its edited input.
+    async fn edit_input(
+        machine: &StepFunctionsMachine,
+        executions: &[StateMachineExecution],
+        transform: Option<&InputTransform>,
+        theme: &ColorfulTheme,
+        term: &Term,
+    ) -> Result<Option<(StateMachineExecution, String)>, PawError> {
+        let wants_edit = Confirm::with_theme(theme)
+            .with_prompt("Edit the input of an execution before the retry?")
+            .default(false)
+            .interact_on_opt(term)?
+            .ok_or(PawError::Cancelled)?;
+        if !wants_edit {
+            return Ok(None);
+        }
+
+        let index = Select::with_theme(theme)
+            .with_prompt("Select the execution to edit:")
+            .items(executions)
+            .default(0)
+            .interact_on_opt(term)?
+            .ok_or(PawError::Cancelled)?;
+        let execution = &executions[index];
+
+        let mut input = machine
+            .describe_execution(execution.arn.clone())
+            .await?
+            .input
+            .ok_or(PawError::MissingField("input"))?;
+        if let Some(transform) = transform {
+            input = transform
+                .apply(&input)
+                .map_err(|error| PawError::InvalidInput(error.to_string()))?;
+        }
+
+        let mut text = pretty_input(&input);
+        loop {
+            term.flush()?;
+            let Some(edited_text) = Editor::new().extension(".json").edit(&text)? else {
+                term.write_line("Input not saved, keeping it as it was")?;
+                return Ok(None);
+            };
+
+            // Saved as it was opened, the input keeps the layout it had.
+            let edited_input = if edited_text == pretty_input(&input) {
+                &input
+            } else {
+                &edited_text
+            };
+
+            match validate_input(edited_input) {
+                Ok(edited_input) => return Ok(Some((execution.clone(), edited_input))),
+                Err(error) => {
+                    term.write_line(&format!("{}, opening the editor again", error))?;
+                    text = edited_text;
+                }
+            }
+        }
+    }
+
+    async fn execute_with_options(&self, options: &RetryFailedOptions) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let state_machine = find_machine(&machine, &options.machine).await?;
+
+        let failed_executions = machine
+            .list_executions(&state_machine, &options.statuses, &options.range)
+            .await?;
+
+        if failed_executions.is_empty() {
+            eprintln!("No executions to retry found for {}", state_machine);
+            return Ok(());
+        }
+
+        if !options.yes && !options.dry_run {
+            for execution in failed_executions.iter() {
+                eprintln!("{} : {}", execution, execution.status);
+            }
+            eprintln!(
+                "{} executions found, run again with --yes to retry them or --dry-run to preview the retries",
+                failed_executions.len()
+            );
+            return Ok(());
+        }
+
+        // A dry run already shows the diffs in its summary.
+        if let (Some(transform), false) = (&options.transform, options.dry_run) {
+            RetryFailedExecution::print_transform_diffs(
+                &machine,
+                &failed_executions,
+                transform,
+                DEFAULT_READ_CONCURRENCY,
+                &Term::stderr(),
+            )
+            .await?;
+        }
+
+        let journal = match options.dry_run {
+            true => None,
+            false => {
+                Some(open_journal(options.journal.clone().unwrap_or_else(
+                    || RetryJournal::default_path(&state_machine.name),
+                ))?)
+            }
+        };
+        let batch = RetryBatch {
+            concurrency: options.concurrency,
+            idempotent_names: options.idempotent_names,
+            transform: options.transform.clone(),
+            redrive: options.redrive,
+            dry_run: options.dry_run,
+            journal,
+            plans: HashMap::new(),
+            interrupted: HashSet::new(),
+        };
+
+        let summary = RetryFailedExecution::retry_selected_failed_executions(
+            &machine,
+            failed_executions,
+            &batch,
+        )
+        .await?;
+
+        report(&summary, options.summary_file.as_deref())
+    }
+}
+
+/// Opens the journal of a new batch and tells the user how to resume it.
+pub(crate) fn open_journal(path: PathBuf) -> Result<RetryJournal, PawError> {
+    let journal = RetryJournal::open(&path)?;
+
+    eprintln!(
+        "Recording the retries in {0}, resume an interrupted batch with: sfn-paw retry --resume {0}",
+        path.display()
+    );
+
+    Ok(journal)
 }
 
 impl Display for RetryFailedExecution {
@@ -85,47 +695,119 @@ impl StepFunctionsAction for RetryFailedExecution {
         "Retry Failed Executions".to_string()
     }
 
-    async fn execute(&self) -> Result<(), Error> {
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
         let term = Term::buffered_stderr();
         let theme = ColorfulTheme::default();
 
-        let machine = StepFunctionsMachine::new().await;
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
 
-        let machines = machine.list_machines().await?;
-        let selected_machine = Select::with_theme(&theme)
-            .with_prompt("Select the Machine:")
-            .items(&machines)
-            .interact_on(&term)
-            .unwrap();
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
 
-        let start_date = RetryFailedExecution::get_user_date_input(
-            "Start Date (ex. 1989-09-30 22:10:32 -03:00): ",
-        );
-        let end_date = RetryFailedExecution::get_user_date_input(
-            "End Date (ex. 1989-09-30 23:15:00 -03:00): ",
-        );
+        let statuses = select_statuses(
+            "Select the statuses to retry:",
+            &ExecutionStatus::RETRYABLE,
+            &[ExecutionStatus::Failed],
+            &theme,
+            &term,
+        )?;
+        if statuses.is_empty() {
+            return Err(PawError::InvalidInput(
+                "select at least one status to retry".to_string(),
+            ));
+        }
+
+        let range = get_user_date_range(&self.settings.range)?;
+
+        let concurrency = self
+            .settings
+            .retry
+            .concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY);
 
         let failed_executions = machine
-            .list_failed_executions(&machines[selected_machine], start_date, end_date)
+            .list_executions(&selected_machine, &statuses, &range)
             .await?;
-        let checked_executions: Vec<(StateMachineExecution, bool)> = failed_executions
-            .iter()
-            .map(|execution| (execution.clone(), true))
-            .collect();
+        if failed_executions.is_empty() {
+            eprintln!("No executions to retry found for {}", selected_machine);
+            return Ok(());
+        }
 
-        // TODO: check empty before going to multiselect
-
-        if !checked_executions.is_empty() {
-            let selected_executions_to_retry = MultiSelect::with_theme(&theme)
-                .with_prompt("Select the executions to retry:")
-                .items_checked(&checked_executions)
-                .interact_on(&term)
-                .unwrap();
-            return RetryFailedExecution::retry_selected_failed_executions(
-                selected_executions_to_retry,
-                failed_executions,
+        let read_concurrency = self
+            .settings
+            .retry
+            .read_concurrency
+            .unwrap_or(DEFAULT_READ_CONCURRENCY);
+        let failed_executions =
+            RetryFailedExecution::find_root_causes(&machine, failed_executions, read_concurrency)
+                .await;
+        let selected_executions =
+            RetryFailedExecution::select_executions(failed_executions, &theme, &term)?;
+
+        if !selected_executions.is_empty() {
+            let transform_file: String = Input::with_theme(&theme)
+                .with_prompt("Transformation file for the inputs (leave blank to keep them):")
+                .allow_empty(true)
+                .interact_text_on(&term)?;
+            let transform = match transform_file.trim() {
+                "" => None,
+                transform_file => Some(InputTransform::load(Path::new(transform_file))?),
+            };
+            if let Some(transform) = &transform {
+                if !RetryFailedExecution::preview_transform(
+                    &machine,
+                    &selected_executions,
+                    transform,
+                    read_concurrency,
+                    &theme,
+                    &term,
+                )
+                .await?
+                {
+                    return Err(PawError::Cancelled);
+                }
+            }
+
+            let edited = RetryFailedExecution::edit_input(
+                &machine,
+                &selected_executions,
+                transform.as_ref(),
+                &theme,
+                &term,
             )
-            .await;
+            .await?;
+
+            let mut batch = RetryBatch {
+                concurrency,
+                idempotent_names: self.settings.retry.idempotent_names,
+                transform,
+                redrive: false,
+                dry_run: false,
+                journal: Some(open_journal(RetryJournal::default_path(
+                    &selected_machine.name,
+                ))?),
+                plans: HashMap::new(),
+                interrupted: HashSet::new(),
+            };
+            if let Some((execution, edited_input)) = edited {
+                let plan = RetryPlan {
+                    edited_input: Some(edited_input),
+                    ..batch.plan(&execution)
+                };
+                batch.plans.insert(execution.arn, plan);
+            }
+
+            let summary = RetryFailedExecution::retry_selected_failed_executions(
+                &machine,
+                selected_executions,
+                &batch,
+            )
+            .await?;
+
+            return report(&summary, None);
         }
 
         Ok(())
@@ -133,16 +815,309 @@ impl StepFunctionsAction for RetryFailedExecution {
 }
 
 #[cfg(test)]
+// The mocked `StepFunctionsClient` returns the errors of the SDK as they are.
+#[allow(clippy::result_large_err)]
 mod tests {
     use super::*;
+    use crate::aws::step_functions::MockStepFunctionsClient;
+    use crate::transform::MAX_INPUT_BYTES;
+    use aws_sdk_sfn::operation::describe_execution::builders::DescribeExecutionOutputBuilder;
+    use aws_sdk_sfn::operation::describe_execution::DescribeExecutionOutput;
+    use aws_sdk_sfn::types::{
+        ExecutionRedriveStatus as SdkRedriveStatus, ExecutionStatus as SdkExecutionStatus,
+    };
+    use aws_smithy_types::DateTime as SdkDateTime;
+    use chrono::{DateTime, Utc};
+    use serde_json::json;
 
     #[test]
     fn should_return_name() {
-        let retry_failed_executions = RetryFailedExecution::new();
+        let retry_failed_executions =
+            RetryFailedExecution::new(ClientOptions::default(), Settings::default());
 
         assert_eq!(
             retry_failed_executions.name(),
             "Retry Failed Executions".to_string()
         );
     }
+
+    fn failed_execution(name: &str, root_cause: Option<RootCause>) -> StateMachineExecution {
+        StateMachineExecution {
+            arn: format!("{}::arn", name),
+            machine_arn: "dinosaur_machine::arn".to_string(),
+            name: name.to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause,
+        }
+    }
+
+    fn batch(idempotent_names: bool, plans: HashMap<String, RetryPlan>) -> RetryBatch {
+        RetryBatch {
+            concurrency: 1,
+            idempotent_names,
+            transform: None,
+            redrive: false,
+            dry_run: false,
+            journal: None,
+            plans,
+            interrupted: HashSet::new(),
+        }
+    }
+
+    #[test]
+    fn should_plan_idempotent_names_from_the_batch() {
+        let execution = failed_execution("dinosaur", None);
+
+        assert_eq!(
+            batch(false, HashMap::new()).plan(&execution),
+            RetryPlan::default()
+        );
+        assert_eq!(
+            batch(true, HashMap::new()).plan(&execution).execution_name,
+            Some(execution.retry_name())
+        );
+    }
+
+    #[test]
+    fn should_prefer_the_recorded_plan_over_the_batch() {
+        let execution = failed_execution("dinosaur", None);
+        let plans = HashMap::from([(execution.arn.clone(), RetryPlan::default())]);
+
+        assert_eq!(batch(true, plans).plan(&execution), RetryPlan::default());
+    }
+
+    fn describe_output(input: &str) -> DescribeExecutionOutput {
+        describe_output_builder(SdkExecutionStatus::Failed)
+            .input(input)
+            .build()
+            .unwrap()
+    }
+
+    fn describe_output_builder(status: SdkExecutionStatus) -> DescribeExecutionOutputBuilder {
+        DescribeExecutionOutputBuilder::default()
+            .execution_arn("dinosaur::arn")
+            .state_machine_arn("dinosaur_machine::arn")
+            .name("dinosaur")
+            .status(status)
+            .start_date(SdkDateTime::from_secs(0))
+    }
+
+    #[tokio::test]
+    async fn should_skip_execution_running_again() {
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(describe_output_builder(SdkExecutionStatus::Running)
+                    .input("{}")
+                    .build()
+                    .unwrap())
+            });
+        mock_client.expect_redrive_execution().never();
+        mock_client.expect_start_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let mut batch = batch(false, HashMap::new());
+        batch.redrive = true;
+
+        let outcome = RetryFailedExecution::retry_outcome(
+            &machine,
+            &failed_execution("dinosaur", None),
+            &Backoff::default(),
+            &batch,
+        )
+        .await;
+
+        assert_eq!(
+            outcome,
+            RetryOutcome::Skipped {
+                reason: "execution is now RUNNING".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_skip_execution_redriven_by_map_run() {
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(describe_output_builder(SdkExecutionStatus::Failed)
+                    .input("{}")
+                    .redrive_status(SdkRedriveStatus::RedrivableByMapRun)
+                    .build()
+                    .unwrap())
+            });
+        mock_client.expect_redrive_execution().never();
+        mock_client.expect_start_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let mut batch = batch(false, HashMap::new());
+        batch.redrive = true;
+
+        let outcome = RetryFailedExecution::retry_outcome(
+            &machine,
+            &failed_execution("dinosaur", None),
+            &Backoff::default(),
+            &batch,
+        )
+        .await;
+
+        assert!(matches!(outcome, RetryOutcome::Skipped { reason } if reason.contains("map run")));
+    }
+
+    #[tokio::test]
+    async fn should_fail_retry_when_transformed_input_is_too_large() {
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(1)
+            .returning(|_execution_arn| Ok(describe_output("{}")));
+        mock_client.expect_start_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let mut batch = batch(false, HashMap::new());
+        batch.transform = Some(InputTransform::MergePatch(
+            json!({ "egg": "x".repeat(MAX_INPUT_BYTES) }),
+        ));
+
+        let outcome = RetryFailedExecution::retry_outcome(
+            &machine,
+            &failed_execution("dinosaur", None),
+            &Backoff::default(),
+            &batch,
+        )
+        .await;
+
+        assert!(
+            matches!(outcome, RetryOutcome::Failed { reason } if reason.contains("payload limit"))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_show_why_transformed_inputs_would_not_be_started() {
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(3)
+            .returning(|execution_arn| {
+                Ok(match execution_arn.as_str() {
+                    "dinosaur::arn" => describe_output("{\"egg\":1}"),
+                    "egg::arn" => describe_output_builder(SdkExecutionStatus::Failed)
+                        .build()
+                        .unwrap(),
+                    _ => {
+                        describe_output(&format!("{{\"egg\":\"{}\"}}", "x".repeat(MAX_INPUT_BYTES)))
+                    }
+                })
+            });
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let diffs = RetryFailedExecution::transform_diffs(
+            &machine,
+            &[
+                failed_execution("dinosaur", None),
+                failed_execution("egg", None),
+                failed_execution("t-rex", None),
+            ],
+            &InputTransform::MergePatch(json!({ "retry": true })),
+            2,
+        )
+        .await;
+
+        assert!(diffs[0].contains("+  \"retry\": true"));
+        assert!(diffs[1].ends_with("no input, will be skipped\n"));
+        assert!(diffs[2].contains("payload limit"));
+    }
+
+    #[test]
+    fn should_plan_transform_from_the_batch() {
+        let transform = InputTransform::MergePatch(json!({ "retry": true }));
+        let mut batch = batch(false, HashMap::new());
+        batch.transform = Some(transform.clone());
+
+        let plan = batch.plan(&failed_execution("dinosaur", None));
+        let recorded: RetryPlan =
+            serde_json::from_str(&serde_json::to_string(&plan).unwrap()).unwrap();
+
+        assert_eq!(recorded.transform, Some(transform));
+    }
+
+    fn timeout(request_id: &str) -> Option<RootCause> {
+        Some(RootCause {
+            failed_state: Some("Charge".to_string()),
+            error: Some("States.Timeout".to_string()),
+            cause: Some(format!("Request {} timed out", request_id)),
+            retries_used: 2,
+        })
+    }
+
+    #[test]
+    fn should_group_executions_by_failure_signature() {
+        let declined = Some(RootCause {
+            failed_state: Some("Charge".to_string()),
+            error: Some("Card.Declined".to_string()),
+            cause: None,
+            retries_used: 0,
+        });
+
+        let groups = RetryFailedExecution::group_by_signature(vec![
+            failed_execution("dinosaur-1", declined.clone()),
+            failed_execution("dinosaur-2", timeout("7c9e6679")),
+            failed_execution("dinosaur-3", None),
+            failed_execution("dinosaur-4", timeout("a4b11f09")),
+            failed_execution("dinosaur-5", timeout("5e8d9c2a")),
+            failed_execution("dinosaur-6", declined),
+        ]);
+
+        assert_eq!(
+            groups
+                .iter()
+                .map(|(signature, executions)| (
+                    signature.to_string(),
+                    executions
+                        .iter()
+                        .map(|execution| execution.name.as_str())
+                        .collect::<Vec<&str>>()
+                ))
+                .collect::<Vec<(String, Vec<&str>)>>(),
+            vec![
+                (
+                    "Charge: States.Timeout (Request <id> timed out)".to_string(),
+                    vec!["dinosaur-2", "dinosaur-4", "dinosaur-5"]
+                ),
+                (
+                    "Charge: Card.Declined".to_string(),
+                    vec!["dinosaur-1", "dinosaur-6"]
+                ),
+                ("(no state): (no error)".to_string(), vec!["dinosaur-3"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_label_executions_with_root_cause() {
+        let mut execution = failed_execution("dinosaur", None);
+
+        assert_eq!(
+            RetryFailedExecution::picker_label(&execution),
+            "dinosaur : 1989-09-30T22:10:32Z"
+        );
+
+        execution.root_cause = timeout("7c9e6679");
+
+        assert_eq!(
+            RetryFailedExecution::picker_label(&execution),
+            "dinosaur : 1989-09-30T22:10:32Z Charge: States.Timeout (Request 7c9e6679 timed out), 2 retries used"
+        );
+    }
 }
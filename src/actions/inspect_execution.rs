@@ -0,0 +1,341 @@
+use crate::actions::{print_output, select_execution, select_machine, StepFunctionsAction};
+use crate::aws::model::{ClientOptions, DetailedTimelineEvent, HistoryEvent, TimelineEvent};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use crate::output::{format_elapsed, OutputFormat};
+use crate::transform::pretty_input;
+use async_trait::async_trait;
+use chrono::{DateTime, SecondsFormat, Utc};
+use console::Term;
+use dialoguer::{theme::ColorfulTheme, Select};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Number of events shown at a time in the timeline.
+const PAGE_SIZE: usize = 20;
+
+pub struct InspectExecution {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<InspectExecutionOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct InspectExecutionOptions {
+    pub execution: String,
+    pub include_data: bool,
+    pub output: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum TimelineChoice {
+    Toggle(i64),
+    Next,
+    Previous,
+    Quit,
+}
+
+impl InspectExecution {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        InspectExecution {
+            client_options,
+            settings,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: InspectExecutionOptions) -> Self {
+        InspectExecution {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(
+        &self,
+        options: &InspectExecutionOptions,
+    ) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let events = TimelineEvent::from_history(
+            machine
+                .get_execution_history(options.execution.clone())
+                .await?,
+        );
+
+        match options.include_data {
+            true => print_output(
+                &events
+                    .into_iter()
+                    .map(DetailedTimelineEvent)
+                    .collect::<Vec<_>>(),
+                options.output,
+            ),
+            false => print_output(&events, options.output),
+        }
+    }
+
+    /// Shows the events a page at a time, with the input and output of the events the user
+    /// expands printed below them.
+    fn browse_timeline(
+        events: &[HistoryEvent],
+        theme: &ColorfulTheme,
+        term: &Term,
+    ) -> Result<(), PawError> {
+        let Some(first_event) = events.first() else {
+            println!("The execution has no events");
+            return Ok(());
+        };
+        let start = first_event.timestamp;
+        let pages = events.len().div_ceil(PAGE_SIZE);
+        let mut page = 0;
+        let mut expanded: HashSet<i64> = HashSet::new();
+
+        loop {
+            let page_events = &events[page * PAGE_SIZE..((page + 1) * PAGE_SIZE).min(events.len())];
+
+            println!(
+                "Events {}-{} of {} (page {} of {})",
+                page * PAGE_SIZE + 1,
+                page * PAGE_SIZE + page_events.len(),
+                events.len(),
+                page + 1,
+                pages
+            );
+            for event in page_events {
+                println!("{}", InspectExecution::timeline_line(event, start));
+                if expanded.contains(&event.id) {
+                    print!("{}", InspectExecution::event_details(event));
+                }
+            }
+
+            let choices = InspectExecution::timeline_choices(
+                page_events,
+                &expanded,
+                page + 1 < pages,
+                page > 0,
+            );
+            let labels: Vec<&String> = choices.iter().map(|(label, _)| label).collect();
+            let choice = Select::with_theme(theme)
+                .with_prompt("Expand an event or move through the timeline:")
+                .items(&labels)
+                .default(0)
+                .interact_on_opt(term)?
+                .map(|index| choices[index].1)
+                .unwrap_or(TimelineChoice::Quit);
+
+            match choice {
+                TimelineChoice::Toggle(id) => {
+                    if !expanded.remove(&id) {
+                        expanded.insert(id);
+                    }
+                }
+                TimelineChoice::Next => page += 1,
+                TimelineChoice::Previous => page -= 1,
+                TimelineChoice::Quit => return Ok(()),
+            }
+        }
+    }
+
+    fn timeline_choices(
+        page_events: &[HistoryEvent],
+        expanded: &HashSet<i64>,
+        has_next: bool,
+        has_previous: bool,
+    ) -> Vec<(String, TimelineChoice)> {
+        let mut choices: Vec<(String, TimelineChoice)> = vec![];
+        if has_next {
+            choices.push(("Next page".to_string(), TimelineChoice::Next));
+        }
+        if has_previous {
+            choices.push(("Previous page".to_string(), TimelineChoice::Previous));
+        }
+
+        for event in page_events.iter().filter(|event| event.has_data()) {
+            let action = match expanded.contains(&event.id) {
+                true => "Collapse",
+                false => "Expand",
+            };
+            choices.push((
+                format!(
+                    "{} #{} {} {}",
+                    action,
+                    event.id,
+                    event.event_type,
+                    event.state_name.as_deref().unwrap_or_default()
+                )
+                .trim_end()
+                .to_string(),
+                TimelineChoice::Toggle(event.id),
+            ));
+        }
+
+        choices.push(("Quit".to_string(), TimelineChoice::Quit));
+        choices
+    }
+
+    fn timeline_line(event: &HistoryEvent, start: DateTime<Utc>) -> String {
+        format!(
+            "{:>5}  {:>12}  {}  {:<28}  {}",
+            event.id,
+            format_elapsed(event.timestamp - start),
+            event.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            event.event_type,
+            event.state_name.as_deref().unwrap_or_default()
+        )
+        .trim_end()
+        .to_string()
+    }
+
+    fn event_details(event: &HistoryEvent) -> String {
+        let mut details = String::new();
+        for (label, value) in [
+            ("Input", &event.input),
+            ("Output", &event.output),
+            ("Error", &event.error),
+            ("Cause", &event.cause),
+        ] {
+            if let Some(value) = value {
+                details.push_str(&format!("        {}:\n", label));
+                for line in pretty_input(value).lines() {
+                    details.push_str(&format!("          {}\n", line));
+                }
+            }
+        }
+
+        details
+    }
+}
+
+impl Display for InspectExecution {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for InspectExecution {
+    fn name(&self) -> String {
+        "Inspect Execution".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+
+        let Some(execution) = select_execution(
+            &machine,
+            &selected_machine,
+            &self.settings.range,
+            "Select the execution to inspect:",
+            &theme,
+            &term,
+        )
+        .await?
+        else {
+            return Ok(());
+        };
+
+        let events = machine.get_execution_history(execution.arn).await?;
+
+        InspectExecution::browse_timeline(&events, &theme, &term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(id: i64, event_type: &str, timestamp: &str, input: Option<&str>) -> HistoryEvent {
+        HistoryEvent {
+            id,
+            previous_event_id: id - 1,
+            event_type: event_type.to_string(),
+            timestamp: DateTime::parse_from_rfc3339(timestamp)
+                .unwrap()
+                .with_timezone(&Utc),
+            state_name: Some("Charge")
+                .filter(|_| event_type != "ExecutionStarted")
+                .map(String::from),
+            input: input.map(String::from),
+            output: None,
+            error: None,
+            cause: None,
+        }
+    }
+
+    #[test]
+    fn should_return_name() {
+        let inspect = InspectExecution::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(inspect.name(), "Inspect Execution".to_string());
+    }
+
+    #[test]
+    fn should_print_timeline_line() {
+        let start = DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            InspectExecution::timeline_line(
+                &event(3, "TaskStateEntered", "1989-09-30T22:10:33.500Z", None),
+                start
+            ),
+            "    3       +1.500s  1989-09-30T22:10:33.500Z  TaskStateEntered              Charge"
+        );
+    }
+
+    #[test]
+    fn should_offer_to_expand_events_with_data() {
+        let events = vec![
+            event(1, "ExecutionStarted", "1989-09-30T22:10:32Z", Some("{}")),
+            event(2, "TaskStateEntered", "1989-09-30T22:10:33Z", Some("{}")),
+            event(3, "TaskStarted", "1989-09-30T22:10:34Z", None),
+        ];
+        let expanded = HashSet::from([2]);
+
+        let choices = InspectExecution::timeline_choices(&events, &expanded, true, false);
+
+        assert_eq!(
+            choices,
+            vec![
+                ("Next page".to_string(), TimelineChoice::Next),
+                (
+                    "Expand #1 ExecutionStarted".to_string(),
+                    TimelineChoice::Toggle(1)
+                ),
+                (
+                    "Collapse #2 TaskStateEntered Charge".to_string(),
+                    TimelineChoice::Toggle(2)
+                ),
+                ("Quit".to_string(), TimelineChoice::Quit),
+            ]
+        );
+    }
+
+    #[test]
+    fn should_print_pretty_event_details() {
+        let mut failed_event = event(
+            4,
+            "TaskFailed",
+            "1989-09-30T22:10:35Z",
+            Some("{\"card\":\"visa\"}"),
+        );
+        failed_event.error = Some("States.Timeout".to_string());
+
+        assert_eq!(
+            InspectExecution::event_details(&failed_event),
+            "        Input:\n          {\n            \"card\": \"visa\"\n          }\n        Error:\n          States.Timeout\n"
+        );
+    }
+}
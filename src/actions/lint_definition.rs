@@ -0,0 +1,204 @@
+use crate::actions::{
+    load_definition, machine_definition, print_output, select_machine, DefinitionSource,
+    StepFunctionsAction,
+};
+use crate::asl::lint::{lint_source, Finding, Severity};
+use crate::aws::model::ClientOptions;
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::error::PawError;
+use crate::output::{count, OutputFormat};
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::theme::ColorfulTheme;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct LintDefinition {
+    client_options: ClientOptions,
+    options: Option<LintOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct LintOptions {
+    pub definition: DefinitionSource,
+    /// Checks a definition file against the limits of Express workflows, deployed machines
+    /// are checked against the limits of their own type.
+    pub express: bool,
+    pub deny_warnings: bool,
+    pub output: OutputFormat,
+}
+
+impl LintDefinition {
+    pub fn new(client_options: ClientOptions) -> Self {
+        LintDefinition {
+            client_options,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: LintOptions) -> Self {
+        LintDefinition {
+            client_options,
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(&self, options: &LintOptions) -> Result<(), PawError> {
+        let definition =
+            load_definition(&self.client_options, &options.definition, options.express).await?;
+        let findings = lint_source(&definition.source, definition.express);
+
+        match options.output {
+            OutputFormat::Table => {
+                print!("{}", LintDefinition::report(&definition.label, &findings))
+            }
+            output => print_output(&findings, output)?,
+        }
+
+        LintDefinition::outcome(&findings, options.deny_warnings)
+    }
+
+    /// One line per finding in the `file:line:column: severity: message [rule]` format of
+    /// compilers, which editors and CI annotations pick up, followed by a summary.
+    fn report(label: &str, findings: &[Finding]) -> String {
+        let mut report: String = findings
+            .iter()
+            .map(|finding| format!("{}:{}\n", label, finding))
+            .collect();
+
+        let errors = findings
+            .iter()
+            .filter(|finding| finding.severity == Severity::Error)
+            .count();
+        let warnings = findings.len() - errors;
+        report.push_str(&match (errors, warnings) {
+            (0, 0) => format!("{}: no problems found\n", label),
+            _ => format!(
+                "{}: {}, {}\n",
+                label,
+                count(errors, "error"),
+                count(warnings, "warning")
+            ),
+        });
+
+        report
+    }
+
+    /// Fails when errors were found, or any finding with `deny_warnings`, so CI jobs stop.
+    fn outcome(findings: &[Finding], deny_warnings: bool) -> Result<(), PawError> {
+        let problems = findings
+            .iter()
+            .filter(|finding| deny_warnings || finding.severity == Severity::Error)
+            .count();
+
+        match problems {
+            0 => Ok(()),
+            problems => Err(PawError::LintFailed(problems)),
+        }
+    }
+}
+
+impl Display for LintDefinition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for LintDefinition {
+    fn name(&self) -> String {
+        "Lint Machine Definition".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+        let definition =
+            machine_definition(machine.describe_state_machine(selected_machine.arn).await?)?;
+        let findings = lint_source(&definition.source, definition.express);
+
+        print!("{}", LintDefinition::report(&definition.label, &findings));
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asl::lint::Rule;
+    use crate::asl::Position;
+
+    fn findings() -> Vec<Finding> {
+        vec![
+            Finding {
+                severity: Severity::Warning,
+                rule: Rule::TaskWithoutCatch,
+                position: Position { line: 4, column: 5 },
+                state: Some("Feed".to_string()),
+                message: "Task state \"Feed\" has no Catch".to_string(),
+            },
+            Finding {
+                severity: Severity::Error,
+                rule: Rule::UnknownState,
+                position: Position {
+                    line: 7,
+                    column: 15,
+                },
+                state: Some("Feed".to_string()),
+                message: "Next names unknown state \"Meteor\"".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn should_return_name() {
+        let lint_definition = LintDefinition::new(ClientOptions::default());
+
+        assert_eq!(
+            lint_definition.name(),
+            "Lint Machine Definition".to_string()
+        );
+    }
+
+    #[test]
+    fn should_report_findings_like_a_compiler() {
+        assert_eq!(
+            LintDefinition::report("dinosaur.json", &findings()),
+            r#"dinosaur.json:4:5: warning: Task state "Feed" has no Catch [task-without-catch]
+dinosaur.json:7:15: error: Next names unknown state "Meteor" [unknown-state]
+dinosaur.json: 1 error, 1 warning
+"#
+        );
+        assert!(
+            LintDefinition::report("dinosaur.json", &vec![findings()[1].clone(); 2])
+                .ends_with("dinosaur.json: 2 errors, 0 warnings\n")
+        );
+        assert_eq!(
+            LintDefinition::report("dinosaur.json", &[]),
+            "dinosaur.json: no problems found\n"
+        );
+    }
+
+    #[test]
+    fn should_fail_on_errors_or_denied_warnings() {
+        let warnings = &findings()[..1];
+
+        assert!(LintDefinition::outcome(&[], true).is_ok());
+        assert!(LintDefinition::outcome(warnings, false).is_ok());
+        assert!(matches!(
+            LintDefinition::outcome(warnings, true),
+            Err(PawError::LintFailed(1))
+        ));
+        assert!(matches!(
+            LintDefinition::outcome(&findings(), false),
+            Err(PawError::LintFailed(1))
+        ));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::actions::{
+    find_machine, get_user_date_range, print_output, select_machine, select_statuses,
+    StepFunctionsAction,
+};
+use crate::aws::model::{ClientOptions, DateRange, ExecutionStatus};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use crate::output::OutputFormat;
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::theme::ColorfulTheme;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct ListExecutions {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<ListExecutionsOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ListExecutionsOptions {
+    pub machine: String,
+    pub statuses: Vec<ExecutionStatus>,
+    pub range: DateRange,
+    pub output: OutputFormat,
+}
+
+impl ListExecutions {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        ListExecutions {
+            client_options,
+            settings,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: ListExecutionsOptions) -> Self {
+        ListExecutions {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    async fn execute_with_options(&self, options: &ListExecutionsOptions) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let state_machine = find_machine(&machine, &options.machine).await?;
+
+        let executions = machine
+            .list_executions(&state_machine, &options.statuses, &options.range)
+            .await?;
+
+        print_output(&executions, options.output)
+    }
+}
+
+impl Display for ListExecutions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for ListExecutions {
+    fn name(&self) -> String {
+        "List Executions".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+
+        let statuses = select_statuses(
+            "Select the statuses to list (none for every status):",
+            &ExecutionStatus::ALL,
+            &[],
+            &theme,
+            &term,
+        )?;
+        let range = get_user_date_range(&self.settings.range)?;
+
+        let executions = machine
+            .list_executions(&selected_machine, &statuses, &range)
+            .await?;
+
+        print_output(&executions, self.settings.output.unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_return_name() {
+        let list_executions = ListExecutions::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(list_executions.name(), "List Executions".to_string());
+    }
+}
@@ -0,0 +1,59 @@
+use crate::actions::{print_output, StepFunctionsAction};
+use crate::aws::model::{ClientOptions, NameFilter};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::error::PawError;
+use crate::output::OutputFormat;
+use async_trait::async_trait;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub struct ListMachines {
+    client_options: ClientOptions,
+    output: OutputFormat,
+    filter: Option<NameFilter>,
+}
+
+impl ListMachines {
+    pub fn new(
+        client_options: ClientOptions,
+        output: OutputFormat,
+        filter: Option<NameFilter>,
+    ) -> Self {
+        ListMachines {
+            client_options,
+            output,
+            filter,
+        }
+    }
+}
+
+impl Display for ListMachines {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for ListMachines {
+    fn name(&self) -> String {
+        "List Machines".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        let machines = machine.list_machines(self.filter.as_ref()).await?;
+
+        print_output(&machines, self.output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_return_name() {
+        let list_machines = ListMachines::new(ClientOptions::default(), OutputFormat::Table, None);
+
+        assert_eq!(list_machines.name(), "List Machines".to_string());
+    }
+}
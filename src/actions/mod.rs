@@ -1,33 +1,417 @@
+use crate::actions::describe_machine::DescribeMachine;
+use crate::actions::draw_diagram::DrawDiagram;
+use crate::actions::draw_execution::DrawExecutionDiagram;
 use crate::actions::failed_executions::RetryFailedExecution;
+use crate::actions::inspect_execution::InspectExecution;
+use crate::actions::lint_definition::LintDefinition;
+use crate::actions::list_executions::ListExecutions;
+use crate::actions::list_machines::ListMachines;
+use crate::actions::redrive_executions::RedriveFailedExecutions;
+use crate::actions::resume_retry::ResumeRetry;
+use crate::actions::stop_executions::StopRunningExecutions;
+use crate::asl::diagram::DiagramFormat;
+use crate::asl::model::Definition;
+use crate::asl::parse_definition;
+use crate::aws::model::{
+    ClientOptions, DateRange, ExecutionStatus, MachineType, NameFilter, StateMachine,
+    StateMachineExecution,
+};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::{RangeSettings, Settings};
+use crate::error::PawError;
+use crate::output::{render, OutputFormat, Tabular};
 use async_trait::async_trait;
-use aws_sdk_sfn::Error;
+use chrono::{DateTime, Duration, Utc};
+use console::Term;
+use dialoguer::{theme::ColorfulTheme, Input, MultiSelect, Select};
+use futures::{stream, Future, FutureExt, StreamExt};
+use serde::Serialize;
 use std::fmt::Display;
+use std::fs;
+use std::io::stdout;
+use std::path::PathBuf;
 
+mod backoff;
+mod batch_summary;
+pub mod describe_machine;
+pub mod draw_diagram;
+pub mod draw_execution;
 pub mod failed_executions;
+pub mod inspect_execution;
+pub mod lint_definition;
+pub mod list_executions;
+pub mod list_machines;
+pub mod redrive_executions;
+pub mod resume_retry;
+mod retry_journal;
+mod retry_summary;
+pub mod stop_executions;
+mod stop_summary;
+
+/// Above this number of machines the picker asks for a name filter before listing them.
+const MACHINE_FILTER_THRESHOLD: usize = 20;
 
 #[async_trait]
 pub trait StepFunctionsAction: Display {
-    async fn execute(&self) -> Result<(), Error>;
+    async fn execute(&self) -> Result<(), PawError>;
 
     fn name(&self) -> String {
         "Invalid Action".to_string()
     }
 }
 
-pub fn get_actions() -> Vec<Box<dyn StepFunctionsAction>> {
-    vec![Box::new(RetryFailedExecution::new())]
+pub fn get_actions(
+    client_options: ClientOptions,
+    settings: Settings,
+) -> Vec<Box<dyn StepFunctionsAction>> {
+    let output = settings.output.unwrap_or_default();
+
+    vec![
+        Box::new(RetryFailedExecution::new(
+            client_options.clone(),
+            settings.clone(),
+        )),
+        Box::new(RedriveFailedExecutions::new(
+            client_options.clone(),
+            settings.clone(),
+        )),
+        Box::new(ListMachines::new(client_options.clone(), output, None)),
+        Box::new(DescribeMachine::new(client_options.clone())),
+        Box::new(LintDefinition::new(client_options.clone())),
+        Box::new(DrawDiagram::new(client_options.clone())),
+        Box::new(ListExecutions::new(
+            client_options.clone(),
+            settings.clone(),
+        )),
+        Box::new(InspectExecution::new(
+            client_options.clone(),
+            settings.clone(),
+        )),
+        Box::new(DrawExecutionDiagram::new(
+            client_options.clone(),
+            settings.clone(),
+        )),
+        Box::new(ResumeRetry::new(client_options.clone(), settings.clone())),
+        Box::new(StopRunningExecutions::new(client_options, settings)),
+    ]
+}
+
+/// Runs `task` for every index up to `count` with at most `concurrency` tasks in flight. A
+/// slow task does not hold back the ones after it, the results are put back in index order
+/// once they all finished.
+async fn run_concurrently<T, F, Fut>(count: usize, concurrency: usize, task: F) -> Vec<T>
+where
+    F: Fn(usize) -> Fut,
+    Fut: Future<Output = T>,
+{
+    let mut results: Vec<(usize, T)> = stream::iter(0..count)
+        .map(|index| task(index).map(move |result| (index, result)))
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+    results.sort_by_key(|(index, _)| *index);
+
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+/// Parses either a date (ex. 1989-09-30 22:10:32 -03:00 or 1989-09-30T22:10:32-03:00)
+/// or a duration relative to now (ex. 30m, 24h, 7d, 2w).
+pub fn parse_utc_date_time(raw_date_time: String) -> Result<Option<DateTime<Utc>>, PawError> {
+    if raw_date_time.is_empty() {
+        Ok(None)
+    } else if let Some(duration) = parse_relative_duration(&raw_date_time) {
+        Utc::now()
+            .checked_sub_signed(duration)
+            .map(Some)
+            .ok_or_else(|| PawError::InvalidInput(format!("{} is too far back", raw_date_time)))
+    } else {
+        let parsed_date = DateTime::parse_from_str(&raw_date_time, "%Y-%m-%d %H:%M:%S %z")
+            .or_else(|_| DateTime::parse_from_rfc3339(&raw_date_time));
+        match parsed_date {
+            Ok(date) => Ok(Some(date.with_timezone(&Utc))),
+            Err(error) => Err(PawError::InvalidInput(error.to_string())),
+        }
+    }
+}
+
+/// Parses a duration made of a positive amount and a unit, m, h, d or w (ex. 30m or 24h).
+pub(crate) fn parse_relative_duration(raw_duration: &str) -> Option<Duration> {
+    let unit = raw_duration.chars().last()?;
+    let amount = raw_duration[..raw_duration.len() - unit.len_utf8()]
+        .parse::<i64>()
+        .ok()
+        .filter(|amount| *amount > 0)?;
+
+    match unit {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
+fn get_user_date_input(
+    prompt_message: &str,
+    initial_text: Option<&String>,
+) -> Result<Option<DateTime<Utc>>, PawError> {
+    loop {
+        let date_str: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt_message)
+            .with_initial_text(initial_text.cloned().unwrap_or_default())
+            .allow_empty(true)
+            .interact_text_on(&Term::buffered_stderr())?;
+        let parsed_date = parse_utc_date_time(date_str);
+
+        match parsed_date {
+            Ok(date) => return Ok(date),
+            Err(PawError::InvalidInput(error)) => {
+                eprintln!("Invalid date ({}). Please try again!", error)
+            }
+            Err(error) => return Err(error),
+        }
+    }
+}
+
+fn get_user_date_range(defaults: &RangeSettings) -> Result<DateRange, PawError> {
+    let start_date = get_user_date_input(
+        "Start Date (ex. 1989-09-30 22:10:32 -03:00 or 24h): ",
+        defaults.since.as_ref(),
+    )?;
+    let end_date = get_user_date_input(
+        "End Date (ex. 1989-09-30 23:15:00 -03:00 or 1h): ",
+        defaults.until.as_ref(),
+    )?;
+
+    Ok(DateRange::new(start_date, end_date))
+}
+
+fn select_statuses(
+    prompt: &str,
+    statuses: &[ExecutionStatus],
+    checked: &[ExecutionStatus],
+    theme: &ColorfulTheme,
+    term: &Term,
+) -> Result<Vec<ExecutionStatus>, PawError> {
+    let items: Vec<(ExecutionStatus, bool)> = statuses
+        .iter()
+        .map(|status| (*status, checked.contains(status)))
+        .collect();
+
+    let selected_statuses = MultiSelect::with_theme(theme)
+        .with_prompt(prompt)
+        .items_checked(&items)
+        .interact_on_opt(term)?
+        .ok_or(PawError::Cancelled)?;
+
+    Ok(selected_statuses
+        .into_iter()
+        .map(|index| statuses[index])
+        .collect())
+}
+
+async fn select_machine(
+    machine: &StepFunctionsMachine,
+    theme: &ColorfulTheme,
+    term: &Term,
+) -> Result<StateMachine, PawError> {
+    let mut machines = machine.list_machines(None).await?;
+    if machines.is_empty() {
+        return Err(PawError::InvalidInput(
+            "no state machines found in the account".to_string(),
+        ));
+    }
+
+    if machines.len() > MACHINE_FILTER_THRESHOLD {
+        machines = filter_machines(machines, theme, term)?;
+    }
+
+    let selected_machine = Select::with_theme(theme)
+        .with_prompt("Select the Machine:")
+        .items(&machines)
+        .interact_on_opt(term)?
+        .ok_or(PawError::Cancelled)?;
+
+    Ok(machines.swap_remove(selected_machine))
+}
+
+/// Lets the user pick an execution of the machine among the ones in the statuses and dates
+/// they choose. Returns nothing when no execution matches.
+async fn select_execution(
+    machine: &StepFunctionsMachine,
+    selected_machine: &StateMachine,
+    range_settings: &RangeSettings,
+    prompt: &str,
+    theme: &ColorfulTheme,
+    term: &Term,
+) -> Result<Option<StateMachineExecution>, PawError> {
+    let statuses = select_statuses(
+        "Select the statuses to list:",
+        &ExecutionStatus::ALL,
+        &[ExecutionStatus::Failed],
+        theme,
+        term,
+    )?;
+    let range = get_user_date_range(range_settings)?;
+
+    let mut executions = machine
+        .list_executions(selected_machine, &statuses, &range)
+        .await?;
+    if executions.is_empty() {
+        eprintln!("No executions found for {}", selected_machine);
+        return Ok(None);
+    }
+
+    let labels: Vec<String> = executions
+        .iter()
+        .map(|execution| format!("{} [{}]", execution, execution.status))
+        .collect();
+    let selected_execution = Select::with_theme(theme)
+        .with_prompt(prompt)
+        .items(&labels)
+        .default(0)
+        .interact_on_opt(term)?
+        .ok_or(PawError::Cancelled)?;
+
+    Ok(Some(executions.swap_remove(selected_execution)))
+}
+
+fn select_diagram_format(theme: &ColorfulTheme, term: &Term) -> Result<DiagramFormat, PawError> {
+    let formats = [DiagramFormat::Mermaid, DiagramFormat::Dot];
+    let index = Select::with_theme(theme)
+        .with_prompt("Select the diagram format:")
+        .items(&["Mermaid", "Graphviz DOT"])
+        .default(0)
+        .interact_on_opt(term)?
+        .ok_or(PawError::Cancelled)?;
+
+    Ok(formats[index])
+}
+
+fn filter_machines(
+    machines: Vec<StateMachine>,
+    theme: &ColorfulTheme,
+    term: &Term,
+) -> Result<Vec<StateMachine>, PawError> {
+    loop {
+        let raw_filter: String = Input::with_theme(theme)
+            .with_prompt(format!(
+                "{} machines found, filter by name prefix or /regex/ (leave blank for all):",
+                machines.len()
+            ))
+            .allow_empty(true)
+            .interact_text_on(term)?;
+
+        let filter = match NameFilter::parse(&raw_filter) {
+            Ok(Some(filter)) => filter,
+            Ok(None) => return Ok(machines),
+            Err(error) => {
+                eprintln!("Invalid filter ({}). Please try again!", error);
+                continue;
+            }
+        };
+
+        let filtered_machines: Vec<StateMachine> = machines
+            .iter()
+            .filter(|machine| filter.matches(&machine.name))
+            .cloned()
+            .collect();
+
+        if filtered_machines.is_empty() {
+            eprintln!(
+                "No machine matches {}. Please try again!",
+                raw_filter.trim()
+            );
+        } else {
+            return Ok(filtered_machines);
+        }
+    }
+}
+
+async fn find_machine(
+    machine: &StepFunctionsMachine,
+    name_or_arn: &str,
+) -> Result<StateMachine, PawError> {
+    machine
+        .find_machine(name_or_arn)
+        .await?
+        .ok_or_else(|| PawError::InvalidInput(format!("machine not found: {}", name_or_arn)))
+}
+
+/// Where a command reads a state machine definition from.
+#[derive(Clone, PartialEq, Debug)]
+pub enum DefinitionSource {
+    File(PathBuf),
+    /// Name, ARN or alias of a deployed machine, described to get its definition.
+    Machine(String),
+}
+
+/// Definition of a state machine, with the label its findings and diagrams refer to it by.
+struct LoadedDefinition {
+    label: String,
+    source: String,
+    express: bool,
+}
+
+/// Reads the definition of a file or of a deployed machine. Files do not say the type of the
+/// machine, so `express` is taken from the caller for them.
+async fn load_definition(
+    client_options: &ClientOptions,
+    definition: &DefinitionSource,
+    express: bool,
+) -> Result<LoadedDefinition, PawError> {
+    match definition {
+        DefinitionSource::File(path) => Ok(LoadedDefinition {
+            label: path.display().to_string(),
+            source: fs::read_to_string(path)?,
+            express,
+        }),
+        DefinitionSource::Machine(name_or_arn) => {
+            let machine = StepFunctionsMachine::new(client_options.clone()).await;
+            let state_machine = find_machine(&machine, name_or_arn).await?;
+
+            // A machine given by ARN was described already to find it.
+            machine_definition(match state_machine.details {
+                Some(_) => state_machine,
+                None => machine.describe_state_machine(state_machine.arn).await?,
+            })
+        }
+    }
+}
+
+impl LoadedDefinition {
+    fn parse(&self) -> Result<Definition, PawError> {
+        parse_definition(&self.source)
+            .map_err(|error| PawError::InvalidInput(format!("{}: {}", self.label, error)))
+    }
+}
+
+fn machine_definition(state_machine: StateMachine) -> Result<LoadedDefinition, PawError> {
+    let details = state_machine
+        .details
+        .ok_or(PawError::MissingField("definition"))?;
+
+    Ok(LoadedDefinition {
+        label: state_machine.name,
+        source: details.definition,
+        express: details.machine_type == MachineType::Express,
+    })
+}
+
+fn print_output<T: Serialize + Tabular>(items: &[T], format: OutputFormat) -> Result<(), PawError> {
+    Ok(render(items, format, &mut stdout().lock())?)
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{fmt, fmt::Formatter};
     use super::*;
+    use std::{fmt, fmt::Formatter};
 
     struct TestAction {}
 
     #[async_trait]
     impl StepFunctionsAction for TestAction {
-        async fn execute(&self) -> Result<(), Error> {
+        async fn execute(&self) -> Result<(), PawError> {
             Ok(())
         }
     }
@@ -54,4 +438,71 @@ mod tests {
             "Test: Invalid Action".to_string()
         );
     }
+
+    #[tokio::test]
+    async fn should_keep_index_order_of_tasks_finishing_out_of_order() {
+        let results = run_concurrently(4, 4, |index| async move {
+            tokio::time::sleep(std::time::Duration::from_millis(40 - 10 * index as u64)).await;
+            index
+        })
+        .await;
+
+        assert_eq!(results, vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn should_parse_empty_date_as_none() {
+        assert_eq!(parse_utc_date_time("".to_string()).unwrap(), None);
+    }
+
+    #[test]
+    fn should_parse_prompt_and_rfc3339_dates() {
+        let expected = DateTime::parse_from_rfc3339("1989-10-01T01:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_utc_date_time("1989-09-30 22:10:32 -03:00".to_string()).unwrap(),
+            Some(expected)
+        );
+        assert_eq!(
+            parse_utc_date_time("1989-09-30T22:10:32-03:00".to_string()).unwrap(),
+            Some(expected)
+        );
+    }
+
+    #[test]
+    fn should_parse_relative_dates() {
+        let before = Utc::now();
+        let parsed = parse_utc_date_time("24h".to_string()).unwrap().unwrap();
+        let after = Utc::now();
+
+        assert!(parsed >= before - Duration::hours(24));
+        assert!(parsed <= after - Duration::hours(24));
+    }
+
+    #[test]
+    fn should_parse_relative_durations() {
+        assert_eq!(parse_relative_duration("30m"), Some(Duration::minutes(30)));
+        assert_eq!(parse_relative_duration("7d"), Some(Duration::days(7)));
+        assert_eq!(parse_relative_duration("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_relative_duration("2y"), None);
+        assert_eq!(parse_relative_duration("h"), None);
+        assert_eq!(parse_relative_duration("2é"), None);
+        assert_eq!(parse_relative_duration("-5h"), None);
+        assert_eq!(parse_relative_duration("0m"), None);
+    }
+
+    #[test]
+    fn should_reject_relative_dates_out_of_range() {
+        assert!(matches!(
+            parse_utc_date_time("99999999w".to_string()),
+            Err(PawError::InvalidInput(message)) if message == "99999999w is too far back"
+        ));
+    }
+
+    #[test]
+    fn should_fail_to_parse_invalid_date() {
+        assert!(parse_utc_date_time("yesterday".to_string()).is_err());
+    }
 }
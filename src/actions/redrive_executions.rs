@@ -0,0 +1,253 @@
+use crate::actions::batch_summary::report;
+use crate::actions::failed_executions::{
+    open_journal, RetryBatch, RetryFailedExecution, DEFAULT_CONCURRENCY, DEFAULT_READ_CONCURRENCY,
+};
+use crate::actions::retry_journal::RetryJournal;
+use crate::actions::{
+    get_user_date_range, run_concurrently, select_machine, select_statuses, StepFunctionsAction,
+};
+use crate::aws::model::{ClientOptions, ExecutionStatus, RedriveStatus, StateMachineExecution};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::{theme::ColorfulTheme, MultiSelect};
+use std::collections::{HashMap, HashSet};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Resumes failed executions from the state that failed, restarting the ones Step Functions
+/// cannot redrive.
+pub struct RedriveFailedExecutions {
+    client_options: ClientOptions,
+    settings: Settings,
+}
+
+impl RedriveFailedExecutions {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        RedriveFailedExecutions {
+            client_options,
+            settings,
+        }
+    }
+
+    /// Picker label of an execution, with its redrive eligibility and how often it was
+    /// redriven already.
+    fn redrive_label(execution: &StateMachineExecution) -> String {
+        let redrive_count = execution.redrive_count.unwrap_or_default();
+
+        match &execution.redrive_status {
+            Some(RedriveStatus::Redrivable) => format!(
+                "{} [{}] redrive, redriven {} times",
+                execution, execution.status, redrive_count
+            ),
+            Some(RedriveStatus::RedrivableByMapRun) => format!(
+                "{} [{}] skip, redriven through its map run",
+                execution, execution.status
+            ),
+            Some(RedriveStatus::NotRedrivable {
+                reason: Some(reason),
+            }) => format!(
+                "{} [{}] restart, not redrivable: {}",
+                execution, execution.status, reason
+            ),
+            _ => format!(
+                "{} [{}] restart, not redrivable",
+                execution, execution.status
+            ),
+        }
+    }
+
+    /// Picker label of an execution that could not be described, which is still redriven
+    /// when Step Functions allows it.
+    fn unknown_eligibility_label(execution: &StateMachineExecution, error: &PawError) -> String {
+        format!(
+            "{} [{}] eligibility unknown: {}",
+            execution, execution.status, error
+        )
+    }
+}
+
+impl Display for RedriveFailedExecutions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for RedriveFailedExecutions {
+    fn name(&self) -> String {
+        "Redrive Failed Executions".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+        let concurrency = self
+            .settings
+            .retry
+            .concurrency
+            .unwrap_or(DEFAULT_CONCURRENCY);
+        let read_concurrency = self
+            .settings
+            .retry
+            .read_concurrency
+            .unwrap_or(DEFAULT_READ_CONCURRENCY);
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+
+        let statuses = select_statuses(
+            "Select the statuses to redrive:",
+            &ExecutionStatus::RETRYABLE,
+            &[ExecutionStatus::Failed],
+            &theme,
+            &term,
+        )?;
+        if statuses.is_empty() {
+            return Err(PawError::InvalidInput(
+                "select at least one status to redrive".to_string(),
+            ));
+        }
+
+        let range = get_user_date_range(&self.settings.range)?;
+
+        let failed_executions = machine
+            .list_executions(&selected_machine, &statuses, &range)
+            .await?;
+        if failed_executions.is_empty() {
+            eprintln!("No executions to redrive found for {}", selected_machine);
+            return Ok(());
+        }
+
+        // Only the description of an execution tells whether it can be redriven. One that
+        // cannot be described is still listed, the redrive describes it again.
+        let described_executions: Vec<Result<StateMachineExecution, PawError>> =
+            run_concurrently(failed_executions.len(), read_concurrency, |index| {
+                machine.describe_execution(failed_executions[index].arn.clone())
+            })
+            .await;
+
+        let checked_executions: Vec<(String, bool)> = failed_executions
+            .iter()
+            .zip(&described_executions)
+            .map(|(execution, described)| {
+                let label = match described {
+                    Ok(described) => RedriveFailedExecutions::redrive_label(described),
+                    Err(error) => {
+                        RedriveFailedExecutions::unknown_eligibility_label(execution, error)
+                    }
+                };
+                (label, true)
+            })
+            .collect();
+        let selected_executions_to_redrive = MultiSelect::with_theme(&theme)
+            .with_prompt("Select the executions to redrive:")
+            .items_checked(&checked_executions)
+            .interact_on_opt(&term)?
+            .ok_or(PawError::Cancelled)?;
+        let selected_executions: Vec<StateMachineExecution> = selected_executions_to_redrive
+            .into_iter()
+            .map(|index| failed_executions[index].clone())
+            .collect();
+
+        if selected_executions.is_empty() {
+            return Ok(());
+        }
+
+        let batch = RetryBatch {
+            concurrency,
+            idempotent_names: self.settings.retry.idempotent_names,
+            transform: None,
+            redrive: true,
+            dry_run: false,
+            journal: Some(open_journal(RetryJournal::default_path(
+                &selected_machine.name,
+            ))?),
+            plans: HashMap::new(),
+            interrupted: HashSet::new(),
+        };
+
+        let summary = RetryFailedExecution::retry_selected_failed_executions(
+            &machine,
+            selected_executions,
+            &batch,
+        )
+        .await?;
+
+        report(&summary, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{DateTime, Utc};
+
+    fn failed_execution(redrive_status: Option<RedriveStatus>) -> StateMachineExecution {
+        StateMachineExecution {
+            arn: "dinosaur::arn".to_string(),
+            machine_arn: "dinosaur_machine::arn".to_string(),
+            name: "dinosaur".to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: Some(2),
+            redrive_status,
+            root_cause: None,
+        }
+    }
+
+    #[test]
+    fn should_return_name() {
+        let redrive = RedriveFailedExecutions::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(redrive.name(), "Redrive Failed Executions".to_string());
+    }
+
+    #[test]
+    fn should_label_executions_with_redrive_eligibility() {
+        assert_eq!(
+            RedriveFailedExecutions::redrive_label(&failed_execution(Some(
+                RedriveStatus::Redrivable
+            ))),
+            "dinosaur : 1989-09-30T22:10:32Z [FAILED] redrive, redriven 2 times"
+        );
+        assert_eq!(
+            RedriveFailedExecutions::redrive_label(&failed_execution(Some(
+                RedriveStatus::NotRedrivable {
+                    reason: Some("Execution is older than 14 days".to_string())
+                }
+            ))),
+            "dinosaur : 1989-09-30T22:10:32Z [FAILED] restart, not redrivable: Execution is older than 14 days"
+        );
+        assert_eq!(
+            RedriveFailedExecutions::redrive_label(&failed_execution(Some(
+                RedriveStatus::RedrivableByMapRun
+            ))),
+            "dinosaur : 1989-09-30T22:10:32Z [FAILED] skip, redriven through its map run"
+        );
+        assert_eq!(
+            RedriveFailedExecutions::redrive_label(&failed_execution(None)),
+            "dinosaur : 1989-09-30T22:10:32Z [FAILED] restart, not redrivable"
+        );
+    }
+
+    #[test]
+    fn should_label_executions_that_could_not_be_described() {
+        assert_eq!(
+            RedriveFailedExecutions::unknown_eligibility_label(
+                &failed_execution(None),
+                &PawError::MissingField("status")
+            ),
+            format!(
+                "dinosaur : 1989-09-30T22:10:32Z [FAILED] eligibility unknown: {}",
+                PawError::MissingField("status")
+            )
+        );
+    }
+}
@@ -0,0 +1,379 @@
+use crate::actions::batch_summary::report;
+use crate::actions::failed_executions::{
+    RetryBatch, RetryFailedExecution, RetryPlan, DEFAULT_CONCURRENCY,
+};
+use crate::actions::retry_journal::RetryJournal;
+use crate::actions::StepFunctionsAction;
+use crate::aws::model::{ClientOptions, StateMachineExecution};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use async_trait::async_trait;
+use console::Term;
+use dialoguer::{theme::ColorfulTheme, Input};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::{Path, PathBuf};
+
+pub struct ResumeRetry {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<ResumeRetryOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ResumeRetryOptions {
+    pub journal: PathBuf,
+    pub concurrency: usize,
+    pub summary_file: Option<PathBuf>,
+}
+
+impl ResumeRetry {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        ResumeRetry {
+            client_options,
+            settings,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: ResumeRetryOptions) -> Self {
+        ResumeRetry {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    async fn resume(
+        &self,
+        journal_path: &Path,
+        concurrency: usize,
+        summary_file: Option<&Path>,
+    ) -> Result<(), PawError> {
+        let pending = RetryJournal::pending_executions(journal_path)?;
+        let (pending_executions, plans) = ResumeRetry::split_plans(pending.executions);
+
+        if pending_executions.is_empty() {
+            eprintln!(
+                "Every execution of {} was already retried",
+                journal_path.display()
+            );
+            return Ok(());
+        }
+
+        eprintln!(
+            "Resuming {} executions from {}",
+            pending_executions.len(),
+            journal_path.display()
+        );
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+        // The journal says how every execution was planned to be retried.
+        let batch = RetryBatch {
+            concurrency,
+            idempotent_names: false,
+            transform: None,
+            redrive: false,
+            dry_run: false,
+            journal: Some(RetryJournal::open(journal_path)?),
+            plans,
+            interrupted: pending.interrupted,
+        };
+
+        let summary = RetryFailedExecution::retry_selected_failed_executions(
+            &machine,
+            pending_executions,
+            &batch,
+        )
+        .await?;
+
+        report(&summary, summary_file)
+    }
+
+    /// Separates the pending executions from the plans the journal recorded for them.
+    fn split_plans(
+        pending: Vec<(StateMachineExecution, RetryPlan)>,
+    ) -> (Vec<StateMachineExecution>, HashMap<String, RetryPlan>) {
+        pending
+            .into_iter()
+            .map(|(execution, plan)| {
+                let arn = execution.arn.clone();
+                (execution, (arn, plan))
+            })
+            .unzip()
+    }
+}
+
+impl Display for ResumeRetry {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for ResumeRetry {
+    fn name(&self) -> String {
+        "Resume Interrupted Retry".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self
+                .resume(
+                    &options.journal,
+                    options.concurrency,
+                    options.summary_file.as_deref(),
+                )
+                .await;
+        }
+
+        let journal: String = Input::with_theme(&ColorfulTheme::default())
+            .with_prompt("Journal of the interrupted retry:")
+            .interact_text_on(&Term::buffered_stderr())?;
+
+        self.resume(
+            Path::new(journal.trim()),
+            self.settings
+                .retry
+                .concurrency
+                .unwrap_or(DEFAULT_CONCURRENCY),
+            None,
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+// The mocked `StepFunctionsClient` returns the errors of the SDK as they are.
+#[allow(clippy::result_large_err)]
+mod tests {
+    use super::*;
+    use crate::actions::retry_journal::PendingRetries;
+    use crate::aws::model::ExecutionStatus;
+    use crate::aws::step_functions::MockStepFunctionsClient;
+    use aws_sdk_sfn::operation::describe_execution::builders::DescribeExecutionOutputBuilder;
+    use aws_sdk_sfn::operation::redrive_execution::builders::RedriveExecutionOutputBuilder;
+    use aws_sdk_sfn::operation::start_execution::builders::StartExecutionOutputBuilder;
+    use aws_sdk_sfn::types::{
+        ExecutionRedriveStatus as SdkRedriveStatus, ExecutionStatus as SdkExecutionStatus,
+    };
+    use aws_smithy_types::DateTime as SdkDateTime;
+    use chrono::{DateTime, Utc};
+    use mockall::predicate::eq;
+    use std::fs;
+
+    fn execution() -> StateMachineExecution {
+        StateMachineExecution {
+            arn: "dinosaur::arn".to_string(),
+            machine_arn: "dinosaur_machine::arn".to_string(),
+            name: "dinosaur".to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
+        }
+    }
+
+    #[test]
+    fn should_return_name() {
+        let resume_retry = ResumeRetry::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(resume_retry.name(), "Resume Interrupted Retry".to_string());
+    }
+
+    #[tokio::test]
+    async fn should_start_resumed_execution_with_edited_input() {
+        let path =
+            std::env::temp_dir().join(format!("paw-resume-edited-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        RetryJournal::open(&path)
+            .unwrap()
+            .record_planned(
+                &execution(),
+                &RetryPlan {
+                    edited_input: Some("{\"egg\":\"fixed\"}".to_string()),
+                    ..RetryPlan::default()
+                },
+            )
+            .unwrap();
+
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(DescribeExecutionOutputBuilder::default()
+                    .execution_arn("dinosaur::arn")
+                    .state_machine_arn("dinosaur_machine::arn")
+                    .name("dinosaur")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(SdkDateTime::from_secs(0))
+                    .input("{\"egg\":\"broken\"}")
+                    .build()
+                    .unwrap())
+            });
+        mock_client
+            .expect_start_execution()
+            .with(
+                eq("dinosaur_machine::arn".to_string()),
+                eq("{\"egg\":\"fixed\"}".to_string()),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_machine_arn, _input, _name| {
+                Ok(StartExecutionOutputBuilder::default()
+                    .execution_arn("dinosaur::arn::retry")
+                    .start_date(SdkDateTime::from_secs(0))
+                    .build()
+                    .unwrap())
+            });
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let pending = RetryJournal::pending_executions(&path).unwrap();
+        let (executions, plans) = ResumeRetry::split_plans(pending.executions);
+        let batch = RetryBatch {
+            concurrency: 1,
+            idempotent_names: false,
+            transform: None,
+            redrive: false,
+            dry_run: false,
+            journal: Some(RetryJournal::open(&path).unwrap()),
+            plans,
+            interrupted: pending.interrupted,
+        };
+
+        RetryFailedExecution::retry_selected_failed_executions(&machine, executions, &batch)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap(),
+            PendingRetries::default()
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_redrive_resumed_execution_planned_for_redrive() {
+        let path =
+            std::env::temp_dir().join(format!("paw-resume-redrive-{}.jsonl", std::process::id()));
+        let _ = fs::remove_file(&path);
+        RetryJournal::open(&path)
+            .unwrap()
+            .record_planned(
+                &execution(),
+                &RetryPlan {
+                    redrive: true,
+                    ..RetryPlan::default()
+                },
+            )
+            .unwrap();
+
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(DescribeExecutionOutputBuilder::default()
+                    .execution_arn("dinosaur::arn")
+                    .state_machine_arn("dinosaur_machine::arn")
+                    .name("dinosaur")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(SdkDateTime::from_secs(0))
+                    .input("{\"egg\":\"broken\"}")
+                    .redrive_status(SdkRedriveStatus::Redrivable)
+                    .build()
+                    .unwrap())
+            });
+        mock_client
+            .expect_redrive_execution()
+            .with(eq("dinosaur::arn".to_string()))
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(RedriveExecutionOutputBuilder::default()
+                    .redrive_date(SdkDateTime::from_secs(0))
+                    .build()
+                    .unwrap())
+            });
+        mock_client.expect_start_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let pending = RetryJournal::pending_executions(&path).unwrap();
+        let (executions, plans) = ResumeRetry::split_plans(pending.executions);
+        let batch = RetryBatch {
+            concurrency: 1,
+            idempotent_names: false,
+            transform: None,
+            redrive: false,
+            dry_run: false,
+            journal: Some(RetryJournal::open(&path).unwrap()),
+            plans,
+            interrupted: pending.interrupted,
+        };
+
+        let summary =
+            RetryFailedExecution::retry_selected_failed_executions(&machine, executions, &batch)
+                .await
+                .unwrap();
+
+        assert_eq!(summary.redriven, 1);
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap(),
+            PendingRetries::default()
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn should_skip_resumed_execution_interrupted_while_starting() {
+        let path = std::env::temp_dir().join(format!(
+            "paw-resume-interrupted-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let journal = RetryJournal::open(&path).unwrap();
+        journal
+            .record_planned(&execution(), &RetryPlan::default())
+            .unwrap();
+        journal.record_started(&execution()).unwrap();
+
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client.expect_describe_execution().never();
+        mock_client.expect_start_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let pending = RetryJournal::pending_executions(&path).unwrap();
+        let (executions, plans) = ResumeRetry::split_plans(pending.executions);
+        let batch = RetryBatch {
+            concurrency: 1,
+            idempotent_names: false,
+            transform: None,
+            redrive: false,
+            dry_run: false,
+            journal: Some(RetryJournal::open(&path).unwrap()),
+            plans,
+            interrupted: pending.interrupted,
+        };
+
+        let summary =
+            RetryFailedExecution::retry_selected_failed_executions(&machine, executions, &batch)
+                .await
+                .unwrap();
+
+        assert_eq!(summary.skipped, 1);
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap(),
+            PendingRetries::default()
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+}
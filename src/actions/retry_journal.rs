@@ -0,0 +1,370 @@
+use crate::actions::failed_executions::RetryPlan;
+use crate::actions::retry_summary::{RetryOutcome, RetryResult};
+use crate::aws::model::StateMachineExecution;
+use crate::error::PawError;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "entry", rename_all = "snake_case")]
+enum JournalEntry {
+    Planned {
+        execution: StateMachineExecution,
+        plan: Box<RetryPlan>,
+    },
+    /// Written before an execution is started without a name, which Step Functions would start
+    /// a second time on resume.
+    Started { execution_arn: String },
+    Finished {
+        execution_arn: String,
+        #[serde(flatten)]
+        outcome: RetryOutcome,
+    },
+}
+
+/// Append only record of a retry batch, one JSON entry per line, so an interrupted batch can
+/// be resumed without restarting the executions that were already retried.
+pub struct RetryJournal {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl RetryJournal {
+    /// Journal path for a new batch of `machine_name`, under the local data directory.
+    pub fn default_path(machine_name: &str) -> PathBuf {
+        dirs::data_local_dir()
+            .unwrap_or_else(std::env::temp_dir)
+            .join("paw")
+            .join("journals")
+            .join(format!(
+                "{}-{}.jsonl",
+                machine_name,
+                Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+            ))
+    }
+
+    /// Opens the journal at `path` for appending, creating it and its directory if needed.
+    pub fn open(path: &Path) -> io::Result<RetryJournal> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            fs::create_dir_all(parent)?;
+        }
+
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+
+        Ok(RetryJournal {
+            path: path.to_path_buf(),
+            file: Mutex::new(file),
+        })
+    }
+
+    pub fn record_planned(
+        &self,
+        execution: &StateMachineExecution,
+        plan: &RetryPlan,
+    ) -> io::Result<()> {
+        self.append(&JournalEntry::Planned {
+            execution: execution.clone(),
+            plan: Box::new(plan.clone()),
+        })
+    }
+
+    pub fn record_started(&self, execution: &StateMachineExecution) -> io::Result<()> {
+        self.append(&JournalEntry::Started {
+            execution_arn: execution.arn.clone(),
+        })
+    }
+
+    pub fn record_finished(&self, result: &RetryResult) -> io::Result<()> {
+        self.append(&JournalEntry::Finished {
+            execution_arn: result.execution_arn.clone(),
+            outcome: result.outcome.clone(),
+        })
+    }
+
+    fn append(&self, entry: &JournalEntry) -> io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        // A poisoned lock only means another retry panicked while writing its line, the file
+        // itself is still usable.
+        let mut file = self
+            .file
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        file.write_all(&line)
+            .and_then(|_| file.flush())
+            .map_err(|error| {
+                io::Error::new(
+                    error.kind(),
+                    format!(
+                        "could not write the journal {}: {}",
+                        self.path.display(),
+                        error
+                    ),
+                )
+            })
+    }
+
+    /// Executions planned in the journal that were not retried yet. Executions whose retry
+    /// failed are pending again.
+    pub fn pending_executions(path: &Path) -> Result<PendingRetries, PawError> {
+        let reader = BufReader::new(File::open(path)?);
+        let mut planned: Vec<(StateMachineExecution, RetryPlan)> = vec![];
+        let mut planned_arns: HashSet<String> = HashSet::new();
+        let mut started: HashSet<String> = HashSet::new();
+        let mut done: HashSet<String> = HashSet::new();
+
+        for (index, line) in reader.lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let entry: JournalEntry = serde_json::from_str(&line).map_err(|error| {
+                PawError::InvalidInput(format!(
+                    "invalid journal {} at line {}: {}",
+                    path.display(),
+                    index + 1,
+                    error
+                ))
+            })?;
+
+            match entry {
+                JournalEntry::Planned { execution, plan } => {
+                    if planned_arns.insert(execution.arn.clone()) {
+                        planned.push((execution, *plan));
+                    }
+                }
+                JournalEntry::Started { execution_arn } => {
+                    started.insert(execution_arn);
+                }
+                JournalEntry::Finished {
+                    execution_arn,
+                    outcome,
+                } => {
+                    started.remove(&execution_arn);
+                    match outcome {
+                        RetryOutcome::Succeeded { .. }
+                        | RetryOutcome::Redriven
+                        | RetryOutcome::Skipped { .. } => {
+                            done.insert(execution_arn);
+                        }
+                        RetryOutcome::Planned { .. }
+                        | RetryOutcome::PlannedRedrive
+                        | RetryOutcome::Failed { .. } => {
+                            done.remove(&execution_arn);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(PendingRetries {
+            executions: planned
+                .into_iter()
+                .filter(|(execution, _)| !done.contains(&execution.arn))
+                .collect(),
+            interrupted: started,
+        })
+    }
+}
+
+/// Executions of a journal that were not retried yet.
+#[derive(Default, PartialEq, Debug)]
+pub struct PendingRetries {
+    /// In the planned order, with how they were planned to be retried.
+    pub executions: Vec<(StateMachineExecution, RetryPlan)>,
+    /// ARNs of the executions whose retry was being started without a name when the batch was
+    /// interrupted, they may be running already.
+    pub interrupted: HashSet<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::model::ExecutionStatus;
+    use chrono::DateTime;
+
+    fn journal_path(name: &str) -> PathBuf {
+        let path =
+            std::env::temp_dir().join(format!("paw-journal-{}-{}.jsonl", name, std::process::id()));
+        let _ = fs::remove_file(&path);
+        path
+    }
+
+    fn execution(name: &str) -> StateMachineExecution {
+        StateMachineExecution {
+            arn: format!("{}::arn", name),
+            machine_arn: "dinosaur_machine::arn".to_string(),
+            name: name.to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
+        }
+    }
+
+    fn result(name: &str, outcome: RetryOutcome) -> RetryResult {
+        RetryResult {
+            name: name.to_string(),
+            execution_arn: format!("{}::arn", name),
+            outcome,
+        }
+    }
+
+    #[test]
+    fn should_return_executions_not_retried_yet() {
+        let path = journal_path("pending");
+        let journal = RetryJournal::open(&path).unwrap();
+
+        for name in ["dinosaur", "egg", "t-rex", "raptor"] {
+            journal
+                .record_planned(&execution(name), &RetryPlan::default())
+                .unwrap();
+        }
+        journal
+            .record_finished(&result(
+                "dinosaur",
+                RetryOutcome::Succeeded {
+                    new_execution_arn: "dinosaur::arn::retry".to_string(),
+                },
+            ))
+            .unwrap();
+        journal
+            .record_finished(&result(
+                "egg",
+                RetryOutcome::Skipped {
+                    reason: "execution has no input".to_string(),
+                },
+            ))
+            .unwrap();
+        journal
+            .record_finished(&result(
+                "t-rex",
+                RetryOutcome::Failed {
+                    reason: "boom".to_string(),
+                },
+            ))
+            .unwrap();
+
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap().executions,
+            vec![
+                (execution("t-rex"), RetryPlan::default()),
+                (execution("raptor"), RetryPlan::default())
+            ]
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_append_to_existing_journal_on_resume() {
+        let path = journal_path("resume");
+        RetryJournal::open(&path)
+            .unwrap()
+            .record_planned(&execution("dinosaur"), &RetryPlan::default())
+            .unwrap();
+
+        RetryJournal::open(&path)
+            .unwrap()
+            .record_finished(&result(
+                "dinosaur",
+                RetryOutcome::Succeeded {
+                    new_execution_arn: "dinosaur::arn::retry".to_string(),
+                },
+            ))
+            .unwrap();
+
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap(),
+            PendingRetries::default()
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_return_executions_interrupted_while_starting() {
+        let path = journal_path("interrupted");
+        let journal = RetryJournal::open(&path).unwrap();
+
+        for name in ["dinosaur", "egg"] {
+            journal
+                .record_planned(&execution(name), &RetryPlan::default())
+                .unwrap();
+            journal.record_started(&execution(name)).unwrap();
+        }
+        journal
+            .record_finished(&result(
+                "egg",
+                RetryOutcome::Succeeded {
+                    new_execution_arn: "egg::arn::retry".to_string(),
+                },
+            ))
+            .unwrap();
+
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap(),
+            PendingRetries {
+                executions: vec![(execution("dinosaur"), RetryPlan::default())],
+                interrupted: HashSet::from(["dinosaur::arn".to_string()]),
+            }
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_return_recorded_plans() {
+        let path = journal_path("plans");
+        let plan = RetryPlan {
+            execution_name: Some("dinosaur-retry-1".to_string()),
+            ..RetryPlan::default()
+        };
+        RetryJournal::open(&path)
+            .unwrap()
+            .record_planned(&execution("dinosaur"), &plan)
+            .unwrap();
+
+        assert_eq!(
+            RetryJournal::pending_executions(&path).unwrap().executions,
+            vec![(execution("dinosaur"), plan)]
+        );
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_fail_on_invalid_journal_line() {
+        let path = journal_path("invalid");
+        fs::write(&path, "{\"entry\":\"planned\"}\n").unwrap();
+
+        let error = RetryJournal::pending_executions(&path).unwrap_err();
+
+        assert!(matches!(error, PawError::InvalidInput(message) if message.contains("line 1")));
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn should_fail_on_missing_journal() {
+        assert!(matches!(
+            RetryJournal::pending_executions(&journal_path("missing")),
+            Err(PawError::Io(_))
+        ));
+    }
+}
@@ -0,0 +1,308 @@
+use crate::actions::batch_summary::{is_zero, BatchSummary};
+use crate::transform::input_diff;
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum RetryOutcome {
+    Planned {
+        machine_arn: String,
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        execution_name: Option<String>,
+        /// Input before the transformation, when the batch transforms the inputs.
+        #[serde(skip_serializing_if = "Option::is_none", default)]
+        original_input: Option<String>,
+        input: String,
+    },
+    /// Dry run of a redrive, the execution would resume from the state that failed.
+    PlannedRedrive,
+    Succeeded {
+        new_execution_arn: String,
+    },
+    /// The execution resumed from the state that failed, keeping its ARN.
+    Redriven,
+    Skipped {
+        reason: String,
+    },
+    Failed {
+        reason: String,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct RetryResult {
+    pub name: String,
+    pub execution_arn: String,
+    #[serde(flatten)]
+    pub outcome: RetryOutcome,
+}
+
+/// Outcome of every execution of a retry batch, in the order they were selected.
+#[derive(Clone, Default, PartialEq, Debug, Serialize)]
+pub struct RetrySummary {
+    #[serde(skip_serializing_if = "is_zero")]
+    pub planned: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    pub redriven: usize,
+    pub succeeded: usize,
+    pub skipped: usize,
+    pub failed: usize,
+    pub results: Vec<RetryResult>,
+}
+
+impl RetrySummary {
+    pub fn new(results: Vec<RetryResult>) -> Self {
+        let count = |matches: fn(&RetryOutcome) -> bool| {
+            results
+                .iter()
+                .filter(|result| matches(&result.outcome))
+                .count()
+        };
+
+        RetrySummary {
+            planned: count(|outcome| {
+                matches!(
+                    outcome,
+                    RetryOutcome::Planned { .. } | RetryOutcome::PlannedRedrive
+                )
+            }),
+            redriven: count(|outcome| matches!(outcome, RetryOutcome::Redriven)),
+            succeeded: count(|outcome| matches!(outcome, RetryOutcome::Succeeded { .. })),
+            skipped: count(|outcome| matches!(outcome, RetryOutcome::Skipped { .. })),
+            failed: count(|outcome| matches!(outcome, RetryOutcome::Failed { .. })),
+            results,
+        }
+    }
+}
+
+impl BatchSummary for RetrySummary {
+    const DRY_RUN_VERB: &'static str = "started";
+
+    fn counters(&self) -> Vec<(usize, &'static str)> {
+        if self.planned > 0 {
+            return vec![
+                (self.planned, "planned"),
+                (self.skipped, "skipped"),
+                (self.failed, "failed"),
+            ];
+        }
+
+        let redriven = Some((self.redriven, "redriven")).filter(|_| self.redriven > 0);
+        redriven
+            .into_iter()
+            .chain([
+                (self.succeeded, "succeeded"),
+                (self.skipped, "skipped"),
+                (self.failed, "failed"),
+            ])
+            .collect()
+    }
+
+    fn planned(&self) -> usize {
+        self.planned
+    }
+
+    fn failed(&self) -> usize {
+        self.failed
+    }
+
+    fn write_results<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        for result in self.results.iter() {
+            match &result.outcome {
+                RetryOutcome::Planned {
+                    machine_arn,
+                    execution_name,
+                    original_input,
+                    input,
+                } => {
+                    writeln!(
+                        writer,
+                        "PLANNED    {} -> start {}{} with input {}",
+                        result.execution_arn,
+                        machine_arn,
+                        execution_name
+                            .as_ref()
+                            .map(|name| format!(" as {}", name))
+                            .unwrap_or_default(),
+                        input
+                    )?;
+                    if let Some(original_input) = original_input {
+                        write!(writer, "{}", input_diff(original_input, input))?;
+                    }
+                }
+                RetryOutcome::PlannedRedrive => writeln!(
+                    writer,
+                    "PLANNED    {} -> redrive from the failed state",
+                    result.execution_arn
+                )?,
+                RetryOutcome::Redriven => writeln!(writer, "REDRIVEN   {}", result.execution_arn)?,
+                RetryOutcome::Succeeded { new_execution_arn } => writeln!(
+                    writer,
+                    "SUCCEEDED  {} -> {}",
+                    result.execution_arn, new_execution_arn
+                )?,
+                RetryOutcome::Skipped { reason } => {
+                    writeln!(writer, "SKIPPED    {}: {}", result.execution_arn, reason)?
+                }
+                RetryOutcome::Failed { reason } => {
+                    writeln!(writer, "FAILED     {}: {}", result.execution_arn, reason)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary() -> RetrySummary {
+        RetrySummary::new(vec![
+            RetryResult {
+                name: "dinosaur".to_string(),
+                execution_arn: "dinosaur::arn".to_string(),
+                outcome: RetryOutcome::Succeeded {
+                    new_execution_arn: "dinosaur::arn::retry".to_string(),
+                },
+            },
+            RetryResult {
+                name: "egg".to_string(),
+                execution_arn: "egg::arn".to_string(),
+                outcome: RetryOutcome::Skipped {
+                    reason: "execution has no input".to_string(),
+                },
+            },
+            RetryResult {
+                name: "t-rex".to_string(),
+                execution_arn: "t-rex::arn".to_string(),
+                outcome: RetryOutcome::Failed {
+                    reason: "Step Functions request failed: boom".to_string(),
+                },
+            },
+        ])
+    }
+
+    #[test]
+    fn should_count_outcomes() {
+        let summary = summary();
+
+        assert_eq!(
+            (summary.succeeded, summary.skipped, summary.failed),
+            (1, 1, 1)
+        );
+    }
+
+    #[test]
+    fn should_print_summary_in_selection_order() {
+        let mut output = vec![];
+        summary().print(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1 succeeded, 1 skipped, 1 failed\n\
+             SUCCEEDED  dinosaur::arn -> dinosaur::arn::retry\n\
+             SKIPPED    egg::arn: execution has no input\n\
+             FAILED     t-rex::arn: Step Functions request failed: boom\n"
+        );
+    }
+
+    #[test]
+    fn should_print_dry_run_summary() {
+        let summary = RetrySummary::new(vec![RetryResult {
+            name: "dinosaur".to_string(),
+            execution_arn: "dinosaur::arn".to_string(),
+            outcome: RetryOutcome::Planned {
+                machine_arn: "dinosaur_machine::arn".to_string(),
+                execution_name: Some("dinosaur-retry-1".to_string()),
+                original_input: Some("{\"egg\":0}".to_string()),
+                input: "{\"egg\":1}".to_string(),
+            },
+        }]);
+        let mut output = vec![];
+        summary.print(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Dry run, nothing was started: 1 planned, 0 skipped, 0 failed\n\
+             PLANNED    dinosaur::arn -> start dinosaur_machine::arn as dinosaur-retry-1 with input {\"egg\":1}\n\
+             --- original\n\
+             +++ transformed\n\
+             @@ -1,3 +1,3 @@\n \
+             {\n\
+             -  \"egg\": 0\n\
+             +  \"egg\": 1\n \
+             }\n"
+        );
+        assert_eq!(serde_json::to_value(&summary).unwrap()["planned"], 1);
+    }
+
+    #[test]
+    fn should_print_redriven_executions() {
+        let summary = RetrySummary::new(vec![
+            RetryResult {
+                name: "dinosaur".to_string(),
+                execution_arn: "dinosaur::arn".to_string(),
+                outcome: RetryOutcome::Redriven,
+            },
+            RetryResult {
+                name: "egg".to_string(),
+                execution_arn: "egg::arn".to_string(),
+                outcome: RetryOutcome::Succeeded {
+                    new_execution_arn: "egg::arn::retry".to_string(),
+                },
+            },
+        ]);
+        let mut output = vec![];
+        summary.print(&mut output).unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1 redriven, 1 succeeded, 0 skipped, 0 failed\n\
+             REDRIVEN   dinosaur::arn\n\
+             SUCCEEDED  egg::arn -> egg::arn::retry\n"
+        );
+        assert_eq!(
+            serde_json::to_value(&summary).unwrap()["results"][0],
+            serde_json::json!({
+                "name": "dinosaur",
+                "execution_arn": "dinosaur::arn",
+                "outcome": "redriven"
+            })
+        );
+    }
+
+    #[test]
+    fn should_serialize_summary() {
+        assert_eq!(
+            serde_json::to_value(summary()).unwrap(),
+            serde_json::json!({
+                "succeeded": 1,
+                "skipped": 1,
+                "failed": 1,
+                "results": [
+                    {
+                        "name": "dinosaur",
+                        "execution_arn": "dinosaur::arn",
+                        "outcome": "succeeded",
+                        "new_execution_arn": "dinosaur::arn::retry"
+                    },
+                    {
+                        "name": "egg",
+                        "execution_arn": "egg::arn",
+                        "outcome": "skipped",
+                        "reason": "execution has no input"
+                    },
+                    {
+                        "name": "t-rex",
+                        "execution_arn": "t-rex::arn",
+                        "outcome": "failed",
+                        "reason": "Step Functions request failed: boom"
+                    }
+                ]
+            })
+        );
+    }
+}
@@ -0,0 +1,405 @@
+use crate::actions::backoff::Backoff;
+use crate::actions::batch_summary::report;
+use crate::actions::failed_executions::DEFAULT_CONCURRENCY;
+use crate::actions::stop_summary::{StopOutcome, StopResult, StopSummary};
+use crate::actions::{
+    find_machine, get_user_date_range, parse_relative_duration, run_concurrently, select_machine,
+    StepFunctionsAction,
+};
+use crate::aws::model::{
+    ClientOptions, DateRange, ExecutionStatus, NameFilter, StateMachine, StateMachineExecution,
+};
+use crate::aws::step_functions::StepFunctionsMachine;
+use crate::config::settings::Settings;
+use crate::error::PawError;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, Utc};
+use console::Term;
+use dialoguer::{theme::ColorfulTheme, Confirm, Input, MultiSelect};
+use indicatif::{ProgressBar, ProgressStyle};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::PathBuf;
+
+pub struct StopRunningExecutions {
+    client_options: ClientOptions,
+    settings: Settings,
+    options: Option<StopExecutionsOptions>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct StopExecutionsOptions {
+    pub machine: String,
+    pub range: DateRange,
+    pub name_filter: Option<NameFilter>,
+    pub older_than: Option<Duration>,
+    pub error: Option<String>,
+    pub cause: Option<String>,
+    pub concurrency: usize,
+    pub summary_file: Option<PathBuf>,
+    pub dry_run: bool,
+    pub yes: bool,
+}
+
+/// Which running executions are stopped, on top of the date range.
+#[derive(Clone, Default, PartialEq, Debug)]
+struct ExecutionFilter {
+    name_filter: Option<NameFilter>,
+    older_than: Option<Duration>,
+}
+
+impl ExecutionFilter {
+    fn matches(&self, execution: &StateMachineExecution, now: DateTime<Utc>) -> bool {
+        self.name_filter
+            .as_ref()
+            .is_none_or(|filter| filter.matches(&execution.name))
+            && self
+                .older_than
+                .is_none_or(|older_than| now - execution.start_date >= older_than)
+    }
+}
+
+impl StopRunningExecutions {
+    pub fn new(client_options: ClientOptions, settings: Settings) -> Self {
+        StopRunningExecutions {
+            client_options,
+            settings,
+            options: None,
+        }
+    }
+
+    pub fn with_options(client_options: ClientOptions, options: StopExecutionsOptions) -> Self {
+        StopRunningExecutions {
+            client_options,
+            settings: Settings::default(),
+            options: Some(options),
+        }
+    }
+
+    async fn list_running_executions(
+        machine: &StepFunctionsMachine,
+        state_machine: &StateMachine,
+        range: &DateRange,
+        filter: &ExecutionFilter,
+    ) -> Result<Vec<StateMachineExecution>, PawError> {
+        let now = Utc::now();
+
+        Ok(machine
+            .list_executions(state_machine, &[ExecutionStatus::Running], range)
+            .await?
+            .into_iter()
+            .filter(|execution| filter.matches(execution, now))
+            .collect())
+    }
+
+    /// Stops the executions, at most `concurrency` at a time, or only plans it on a dry run.
+    async fn stop_executions(
+        machine: &StepFunctionsMachine,
+        executions: Vec<StateMachineExecution>,
+        error: Option<String>,
+        cause: Option<String>,
+        concurrency: usize,
+        dry_run: bool,
+    ) -> StopSummary {
+        let progress_bar = ProgressBar::new(executions.len() as u64);
+        progress_bar.set_style(ProgressStyle::default_bar()
+            .template("{spinner:.green} [{elapsed_precise}] [{bar:40.cyan/blue}]({pos} of {len}) ID: {msg}")
+            .progress_chars("#>-"));
+        let backoff = Backoff::default();
+
+        let results: Vec<StopResult> = run_concurrently(executions.len(), concurrency, |index| {
+            let execution = &executions[index];
+            let (error, cause) = (&error, &cause);
+            let (progress_bar, backoff) = (&progress_bar, &backoff);
+
+            async move {
+                let outcome = match dry_run {
+                    true => StopOutcome::Planned,
+                    false => match backoff
+                        .retry(|| {
+                            machine.stop_execution(
+                                execution.arn.clone(),
+                                error.clone(),
+                                cause.clone(),
+                            )
+                        })
+                        .await
+                    {
+                        Ok(()) => StopOutcome::Stopped,
+                        Err(error) => StopOutcome::Failed {
+                            reason: error.to_string(),
+                        },
+                    },
+                };
+
+                progress_bar.set_message(execution.name.clone());
+                progress_bar.inc(1);
+
+                StopResult {
+                    name: execution.name.clone(),
+                    execution_arn: execution.arn.clone(),
+                    outcome,
+                }
+            }
+        })
+        .await;
+
+        progress_bar.finish_and_clear();
+
+        StopSummary::new(results)
+    }
+
+    async fn execute_with_options(&self, options: &StopExecutionsOptions) -> Result<(), PawError> {
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let state_machine = find_machine(&machine, &options.machine).await?;
+
+        let filter = ExecutionFilter {
+            name_filter: options.name_filter.clone(),
+            older_than: options.older_than,
+        };
+        let running_executions = StopRunningExecutions::list_running_executions(
+            &machine,
+            &state_machine,
+            &options.range,
+            &filter,
+        )
+        .await?;
+
+        if running_executions.is_empty() {
+            eprintln!("No running executions to stop found for {}", state_machine);
+            return Ok(());
+        }
+
+        if !options.yes && !options.dry_run {
+            for execution in running_executions.iter() {
+                eprintln!("{}", execution);
+            }
+            eprintln!(
+                "{} running executions found, run again with --yes to stop them or --dry-run to preview the stops",
+                running_executions.len()
+            );
+            return Ok(());
+        }
+
+        let summary = StopRunningExecutions::stop_executions(
+            &machine,
+            running_executions,
+            options.error.clone(),
+            options.cause.clone(),
+            options.concurrency,
+            options.dry_run,
+        )
+        .await;
+
+        report(&summary, options.summary_file.as_deref())
+    }
+
+    fn get_user_filter(theme: &ColorfulTheme, term: &Term) -> Result<ExecutionFilter, PawError> {
+        let name_filter = loop {
+            let raw_filter: String = Input::with_theme(theme)
+                .with_prompt("Filter by execution name prefix or /regex/ (leave blank for all):")
+                .allow_empty(true)
+                .interact_text_on(term)?;
+
+            match NameFilter::parse(&raw_filter) {
+                Ok(name_filter) => break name_filter,
+                Err(error) => eprintln!("Invalid filter ({}). Please try again!", error),
+            }
+        };
+
+        let older_than = loop {
+            let raw_age: String = Input::with_theme(theme)
+                .with_prompt(
+                    "Only executions running for longer than (ex. 30m or 2h, leave blank for all):",
+                )
+                .allow_empty(true)
+                .interact_text_on(term)?;
+
+            match raw_age.trim() {
+                "" => break None,
+                raw_age => match parse_relative_duration(raw_age) {
+                    Some(older_than) => break Some(older_than),
+                    None => eprintln!("Invalid duration ({}). Please try again!", raw_age),
+                },
+            }
+        };
+
+        Ok(ExecutionFilter {
+            name_filter,
+            older_than,
+        })
+    }
+}
+
+impl Display for StopRunningExecutions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[async_trait]
+impl StepFunctionsAction for StopRunningExecutions {
+    fn name(&self) -> String {
+        "Stop Running Executions".to_string()
+    }
+
+    async fn execute(&self) -> Result<(), PawError> {
+        if let Some(options) = &self.options {
+            return self.execute_with_options(options).await;
+        }
+
+        let term = Term::buffered_stderr();
+        let theme = ColorfulTheme::default();
+
+        let machine = StepFunctionsMachine::new(self.client_options.clone()).await;
+
+        let selected_machine = select_machine(&machine, &theme, &term).await?;
+        let range = get_user_date_range(&self.settings.range)?;
+        let filter = StopRunningExecutions::get_user_filter(&theme, &term)?;
+
+        let running_executions = StopRunningExecutions::list_running_executions(
+            &machine,
+            &selected_machine,
+            &range,
+            &filter,
+        )
+        .await?;
+
+        if running_executions.is_empty() {
+            eprintln!(
+                "No running executions to stop found for {}",
+                selected_machine
+            );
+            return Ok(());
+        }
+
+        let checked_executions: Vec<(StateMachineExecution, bool)> = running_executions
+            .iter()
+            .map(|execution| (execution.clone(), true))
+            .collect();
+        let selected_executions_to_stop = MultiSelect::with_theme(&theme)
+            .with_prompt("Select the executions to stop:")
+            .items_checked(&checked_executions)
+            .interact_on_opt(&term)?
+            .ok_or(PawError::Cancelled)?;
+        let selected_executions: Vec<StateMachineExecution> = selected_executions_to_stop
+            .into_iter()
+            .map(|index| running_executions[index].clone())
+            .collect();
+
+        if selected_executions.is_empty() {
+            return Ok(());
+        }
+
+        let error: String = Input::with_theme(&theme)
+            .with_prompt("Error to stop the executions with (leave blank for none):")
+            .allow_empty(true)
+            .interact_text_on(&term)?;
+        let cause: String = Input::with_theme(&theme)
+            .with_prompt("Cause to stop the executions with (leave blank for none):")
+            .allow_empty(true)
+            .interact_text_on(&term)?;
+
+        let confirmed = Confirm::with_theme(&theme)
+            .with_prompt(format!("Stop {} executions?", selected_executions.len()))
+            .default(false)
+            .interact_on_opt(&term)?
+            .unwrap_or(false);
+        if !confirmed {
+            return Err(PawError::Cancelled);
+        }
+
+        let summary = StopRunningExecutions::stop_executions(
+            &machine,
+            selected_executions,
+            Some(error.trim().to_string()).filter(|error| !error.is_empty()),
+            Some(cause.trim().to_string()).filter(|cause| !cause.is_empty()),
+            self.settings
+                .retry
+                .concurrency
+                .unwrap_or(DEFAULT_CONCURRENCY),
+            false,
+        )
+        .await;
+
+        report(&summary, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::aws::step_functions::MockStepFunctionsClient;
+
+    fn running_execution(name: &str, start_date: &str) -> StateMachineExecution {
+        StateMachineExecution {
+            arn: format!("{}::arn", name),
+            machine_arn: "dinosaur_machine::arn".to_string(),
+            name: name.to_string(),
+            status: ExecutionStatus::Running,
+            start_date: DateTime::parse_from_rfc3339(start_date)
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
+        }
+    }
+
+    #[test]
+    fn should_return_name() {
+        let stop_executions =
+            StopRunningExecutions::new(ClientOptions::default(), Settings::default());
+
+        assert_eq!(
+            stop_executions.name(),
+            "Stop Running Executions".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_only_plan_stops_on_dry_run() {
+        let mut mock_client = MockStepFunctionsClient::default();
+        mock_client.expect_stop_execution().never();
+        let machine = StepFunctionsMachine::with_client(mock_client);
+
+        let summary = StopRunningExecutions::stop_executions(
+            &machine,
+            vec![running_execution("dinosaur", "1989-09-30T22:10:32Z")],
+            None,
+            None,
+            1,
+            true,
+        )
+        .await;
+
+        assert_eq!(
+            summary,
+            StopSummary::new(vec![StopResult {
+                name: "dinosaur".to_string(),
+                execution_arn: "dinosaur::arn".to_string(),
+                outcome: StopOutcome::Planned,
+            }])
+        );
+    }
+
+    #[test]
+    fn should_filter_executions_by_name_and_age() {
+        let now = DateTime::parse_from_rfc3339("1989-09-30T23:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let filter = ExecutionFilter {
+            name_filter: Some(NameFilter::Prefix("deploy-".to_string())),
+            older_than: Duration::try_hours(1),
+        };
+
+        assert!(filter.matches(&running_execution("deploy-1", "1989-09-30T21:00:00Z"), now));
+        assert!(filter.matches(&running_execution("deploy-2", "1989-09-30T22:00:00Z"), now));
+        assert!(!filter.matches(&running_execution("deploy-3", "1989-09-30T22:30:00Z"), now));
+        assert!(!filter.matches(&running_execution("dinosaur", "1989-09-30T21:00:00Z"), now));
+        assert!(ExecutionFilter::default()
+            .matches(&running_execution("dinosaur", "1989-09-30T22:59:00Z"), now));
+    }
+}
@@ -0,0 +1,156 @@
+use crate::actions::batch_summary::{is_zero, BatchSummary};
+use serde::Serialize;
+use std::io::{Result as IoResult, Write};
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum StopOutcome {
+    /// Dry run, the running execution would be stopped.
+    Planned,
+    Stopped,
+    Failed {
+        reason: String,
+    },
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct StopResult {
+    pub name: String,
+    pub execution_arn: String,
+    #[serde(flatten)]
+    pub outcome: StopOutcome,
+}
+
+/// Outcome of every execution of a stop batch, in the order they were selected.
+#[derive(Clone, Default, PartialEq, Debug, Serialize)]
+pub struct StopSummary {
+    #[serde(skip_serializing_if = "is_zero")]
+    pub planned: usize,
+    pub stopped: usize,
+    pub failed: usize,
+    pub results: Vec<StopResult>,
+}
+
+impl StopSummary {
+    pub fn new(results: Vec<StopResult>) -> Self {
+        let count = |matches: fn(&StopOutcome) -> bool| {
+            results
+                .iter()
+                .filter(|result| matches(&result.outcome))
+                .count()
+        };
+
+        StopSummary {
+            planned: count(|outcome| matches!(outcome, StopOutcome::Planned)),
+            stopped: count(|outcome| matches!(outcome, StopOutcome::Stopped)),
+            failed: count(|outcome| matches!(outcome, StopOutcome::Failed { .. })),
+            results,
+        }
+    }
+}
+
+impl BatchSummary for StopSummary {
+    const DRY_RUN_VERB: &'static str = "stopped";
+
+    fn counters(&self) -> Vec<(usize, &'static str)> {
+        match self.planned {
+            0 => vec![(self.stopped, "stopped"), (self.failed, "failed")],
+            planned => vec![(planned, "planned"), (self.failed, "failed")],
+        }
+    }
+
+    fn planned(&self) -> usize {
+        self.planned
+    }
+
+    fn failed(&self) -> usize {
+        self.failed
+    }
+
+    fn write_results<W: Write>(&self, writer: &mut W) -> IoResult<()> {
+        for result in self.results.iter() {
+            match &result.outcome {
+                StopOutcome::Planned => {
+                    writeln!(writer, "PLANNED    {} -> stop", result.execution_arn)?
+                }
+                StopOutcome::Stopped => writeln!(writer, "STOPPED    {}", result.execution_arn)?,
+                StopOutcome::Failed { reason } => {
+                    writeln!(writer, "FAILED     {}: {}", result.execution_arn, reason)?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(outcomes: Vec<StopOutcome>) -> StopSummary {
+        StopSummary::new(
+            outcomes
+                .into_iter()
+                .zip(["dinosaur", "egg"])
+                .map(|(outcome, name)| StopResult {
+                    name: name.to_string(),
+                    execution_arn: format!("{}::arn", name),
+                    outcome,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn should_print_stopped_executions() {
+        let mut output = vec![];
+        summary(vec![
+            StopOutcome::Stopped,
+            StopOutcome::Failed {
+                reason: "Step Functions request failed: boom".to_string(),
+            },
+        ])
+        .print(&mut output)
+        .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "1 stopped, 1 failed\n\
+             STOPPED    dinosaur::arn\n\
+             FAILED     egg::arn: Step Functions request failed: boom\n"
+        );
+    }
+
+    #[test]
+    fn should_print_dry_run_summary() {
+        let mut output = vec![];
+        summary(vec![StopOutcome::Planned])
+            .print(&mut output)
+            .unwrap();
+
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Dry run, nothing was stopped: 1 planned, 0 failed\n\
+             PLANNED    dinosaur::arn -> stop\n"
+        );
+    }
+
+    #[test]
+    fn should_serialize_summary() {
+        assert_eq!(
+            serde_json::to_value(summary(vec![StopOutcome::Stopped])).unwrap(),
+            serde_json::json!({
+                "stopped": 1,
+                "failed": 0,
+                "results": [
+                    {
+                        "name": "dinosaur",
+                        "execution_arn": "dinosaur::arn",
+                        "outcome": "stopped"
+                    }
+                ]
+            })
+        );
+    }
+}
@@ -0,0 +1,780 @@
+use crate::asl::model::{Definition, EdgeKind, State, StateGraph, StateKind, Transition};
+use clap::ValueEnum;
+use std::collections::HashMap;
+
+/// Text formats a definition can be drawn in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, ValueEnum)]
+pub enum DiagramFormat {
+    /// Graphviz DOT, for `dot -Tsvg`.
+    Dot,
+    /// Mermaid flowchart, rendered by GitHub and most documentation tools.
+    Mermaid,
+}
+
+/// What an execution did in a state, drawn over the diagram of the definition it ran.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StateActivity {
+    pub status: StateStatus,
+    /// Times the state was entered, more than once in loops and Map iterations.
+    pub runs: u32,
+    pub retries: u32,
+}
+
+/// Outcome of a state, ordered from the least to the most worth noticing, so the outcome of a
+/// state run several times is the most noticeable one.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum StateStatus {
+    Succeeded,
+    /// Failed, then moved on through a Catch.
+    Caught,
+    Running,
+    Failed,
+}
+
+const START_ID: &str = "start_node";
+// `end` is a keyword of Mermaid.
+const END_ID: &str = "end_node";
+const CATCH_COLOR: &str = "#c0392b";
+const UNVISITED_COLOR: &str = "#999999";
+
+/// Draws the states of a definition and their transitions. Parallel branches and Map
+/// processors are drawn as subgraphs, Catch edges are dashed and red.
+pub fn render(definition: &Definition, title: &str, format: DiagramFormat) -> String {
+    draw(Diagram::build(definition, None), title, format)
+}
+
+/// Draws a definition with the states an execution visited colored by how they ended, and
+/// the states it did not visit grayed out. `activity` is keyed by state name.
+pub fn render_execution(
+    definition: &Definition,
+    title: &str,
+    format: DiagramFormat,
+    activity: &HashMap<String, StateActivity>,
+) -> String {
+    draw(Diagram::build(definition, Some(activity)), title, format)
+}
+
+fn draw(diagram: Diagram, title: &str, format: DiagramFormat) -> String {
+    match format {
+        DiagramFormat::Dot => diagram.to_dot(title),
+        DiagramFormat::Mermaid => diagram.to_mermaid(),
+    }
+}
+
+struct Node {
+    id: String,
+    label: String,
+    /// State type, `None` for the start and end nodes.
+    kind: Option<&'static str>,
+    style: Option<Style>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Style {
+    Succeeded,
+    Caught,
+    Running,
+    Failed,
+    Unvisited,
+}
+
+impl Style {
+    const ALL: [Style; 5] = [
+        Style::Succeeded,
+        Style::Caught,
+        Style::Running,
+        Style::Failed,
+        Style::Unvisited,
+    ];
+
+    fn class(&self) -> &'static str {
+        match self {
+            Style::Succeeded => "succeeded",
+            Style::Caught => "caught",
+            Style::Running => "running",
+            Style::Failed => "failed",
+            Style::Unvisited => "unvisited",
+        }
+    }
+
+    fn fill(&self) -> &'static str {
+        match self {
+            Style::Succeeded => "#d4edda",
+            Style::Caught => "#fff3cd",
+            Style::Running => "#cce5ff",
+            Style::Failed => "#f8d7da",
+            Style::Unvisited => "#ffffff",
+        }
+    }
+
+    fn stroke(&self) -> &'static str {
+        match self {
+            Style::Succeeded => "#28a745",
+            Style::Caught => "#e67e22",
+            Style::Running => "#007bff",
+            Style::Failed => CATCH_COLOR,
+            Style::Unvisited => UNVISITED_COLOR,
+        }
+    }
+}
+
+impl From<StateStatus> for Style {
+    fn from(status: StateStatus) -> Self {
+        match status {
+            StateStatus::Succeeded => Style::Succeeded,
+            StateStatus::Caught => Style::Caught,
+            StateStatus::Running => Style::Running,
+            StateStatus::Failed => Style::Failed,
+        }
+    }
+}
+
+struct Cluster {
+    id: String,
+    label: String,
+    items: Vec<Item>,
+}
+
+enum Item {
+    Node(Node),
+    Cluster(Cluster),
+}
+
+struct Link {
+    from: String,
+    to: String,
+    label: Option<String>,
+    catch: bool,
+}
+
+struct Diagram<'a> {
+    items: Vec<Item>,
+    links: Vec<Link>,
+    next_id: usize,
+    activity: Option<&'a HashMap<String, StateActivity>>,
+}
+
+impl<'a> Diagram<'a> {
+    fn build(
+        definition: &Definition,
+        activity: Option<&'a HashMap<String, StateActivity>>,
+    ) -> Diagram<'a> {
+        let mut diagram = Diagram {
+            items: vec![],
+            links: vec![],
+            next_id: 0,
+            activity,
+        };
+
+        let mut items = vec![Item::Node(Node {
+            id: START_ID.to_string(),
+            label: "Start".to_string(),
+            kind: None,
+            style: None,
+        })];
+        let start = diagram.add_graph(&definition.graph, &mut items, true);
+        items.push(Item::Node(Node {
+            id: END_ID.to_string(),
+            label: "End".to_string(),
+            kind: None,
+            style: None,
+        }));
+        if let Some(start) = start {
+            diagram
+                .links
+                .insert(0, Diagram::link(START_ID, &start, None));
+        }
+        diagram.items = items;
+
+        diagram
+    }
+
+    /// Adds the states of a graph to `items` and their transitions to the links, returns the
+    /// id of the state the graph starts at.
+    fn add_graph(
+        &mut self,
+        graph: &StateGraph,
+        items: &mut Vec<Item>,
+        root: bool,
+    ) -> Option<String> {
+        let ids: HashMap<&str, String> = graph
+            .states
+            .iter()
+            .map(|state| (state.name.as_str(), self.new_id("state")))
+            .collect();
+
+        for state in &graph.states {
+            let id = &ids[state.name.as_str()];
+            items.push(Item::Node(self.state_node(state, id)));
+            self.add_edges(state, id, &ids);
+
+            let ends = matches!(state.kind, StateKind::Succeed | StateKind::Fail(_))
+                || matches!(&state.transition, Some(transition) if transition.value == Transition::End);
+            if root && ends {
+                self.links.push(Diagram::link(id, END_ID, None));
+            }
+
+            match &state.kind {
+                StateKind::Parallel(parallel) => {
+                    for (index, branch) in parallel.branches.iter().enumerate() {
+                        let label = format!("{} branch {}", state.name, index + 1);
+                        self.add_cluster(id, label, branch, items);
+                    }
+                }
+                StateKind::Map(map) => {
+                    let label = format!("{} iterator", state.name);
+                    self.add_cluster(id, label, &map.processor, items);
+                }
+                _ => {}
+            }
+        }
+
+        ids.get(graph.start_at.value.as_str()).cloned()
+    }
+
+    /// Node of a state, styled by its type, or by what the execution did in it.
+    fn state_node(&self, state: &State, id: &str) -> Node {
+        let mut label = state.name.clone();
+        let style = match self.activity {
+            None => match state.kind {
+                StateKind::Succeed => Some(Style::Succeeded),
+                StateKind::Fail(_) => Some(Style::Failed),
+                _ => None,
+            },
+            Some(activity) => match activity.get(&state.name) {
+                None => Some(Style::Unvisited),
+                Some(activity) => {
+                    let mut notes = vec![];
+                    if activity.runs > 1 {
+                        notes.push(format!("{} runs", activity.runs));
+                    }
+                    match activity.retries {
+                        0 => {}
+                        1 => notes.push("1 retry".to_string()),
+                        retries => notes.push(format!("{} retries", retries)),
+                    }
+                    if !notes.is_empty() {
+                        label = format!("{}\n{}", label, notes.join(", "));
+                    }
+                    Some(activity.status.into())
+                }
+            },
+        };
+
+        Node {
+            id: id.to_string(),
+            label,
+            kind: Some(state.kind.type_name()),
+            style,
+        }
+    }
+
+    fn add_cluster(
+        &mut self,
+        parent: &str,
+        label: String,
+        graph: &StateGraph,
+        items: &mut Vec<Item>,
+    ) {
+        let mut cluster = Cluster {
+            id: self.new_id("cluster"),
+            label,
+            items: vec![],
+        };
+        if let Some(start) = self.add_graph(graph, &mut cluster.items, false) {
+            self.links.push(Diagram::link(parent, &start, None));
+        }
+        items.push(Item::Cluster(cluster));
+    }
+
+    /// Links of the Next, Choice, Default and Catch fields. Transitions to unknown states are
+    /// left out, the linter reports them.
+    fn add_edges(&mut self, state: &State, id: &str, ids: &HashMap<&str, String>) {
+        let mut rules = match &state.kind {
+            StateKind::Choice(choice) => choice.choices.iter(),
+            _ => [].iter(),
+        };
+        let mut catchers = match state.kind.error_handling() {
+            Some(errors) => errors.catch.iter(),
+            None => [].iter(),
+        };
+
+        for edge in state.edges() {
+            let (label, catch) = match edge.kind {
+                EdgeKind::Next => (None, false),
+                EdgeKind::Choice => (rules.next().map(|rule| rule.condition.to_string()), false),
+                EdgeKind::Default => (Some("Default".to_string()), false),
+                EdgeKind::Catch => (
+                    catchers
+                        .next()
+                        .map(|catcher| catcher.error_equals.join(", ")),
+                    true,
+                ),
+            };
+            if let Some(target) = ids.get(edge.target) {
+                self.links.push(Link {
+                    catch,
+                    ..Diagram::link(id, target, label)
+                });
+            }
+        }
+    }
+
+    fn link(from: &str, to: &str, label: Option<String>) -> Link {
+        Link {
+            from: from.to_string(),
+            to: to.to_string(),
+            label,
+            catch: false,
+        }
+    }
+
+    fn new_id(&mut self, prefix: &str) -> String {
+        let id = format!("{}{}", prefix, self.next_id);
+        self.next_id += 1;
+        id
+    }
+
+    fn to_dot(&self, title: &str) -> String {
+        let mut dot = format!("digraph \"{}\" {{\n", dot_escape(title));
+        dot.push_str("    node [shape=box, style=rounded];\n");
+        dot_items(&self.items, 1, &mut dot);
+
+        for link in &self.links {
+            let mut attributes = vec![];
+            if let Some(label) = &link.label {
+                attributes.push(format!("label=\"{}\"", dot_escape(label)));
+            }
+            if link.catch {
+                attributes.push(format!(
+                    "style=dashed, color=\"{0}\", fontcolor=\"{0}\"",
+                    CATCH_COLOR
+                ));
+            }
+            match attributes.is_empty() {
+                true => dot.push_str(&format!("    {} -> {};\n", link.from, link.to)),
+                false => dot.push_str(&format!(
+                    "    {} -> {} [{}];\n",
+                    link.from,
+                    link.to,
+                    attributes.join(", ")
+                )),
+            }
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+
+    fn to_mermaid(&self) -> String {
+        let mut mermaid = "flowchart TD\n".to_string();
+        let mut classes: Vec<(Style, Vec<&str>)> =
+            Style::ALL.iter().map(|style| (*style, vec![])).collect();
+        mermaid_items(&self.items, 1, &mut mermaid, &mut classes);
+
+        for link in &self.links {
+            let arrow = match link.catch {
+                true => "-.->",
+                false => "-->",
+            };
+            match &link.label {
+                Some(label) => mermaid.push_str(&format!(
+                    "    {} {}|\"{}\"| {}\n",
+                    link.from,
+                    arrow,
+                    mermaid_escape(label),
+                    link.to
+                )),
+                None => mermaid.push_str(&format!("    {} {} {}\n", link.from, arrow, link.to)),
+            }
+        }
+
+        for (style, ids) in classes.iter().filter(|(_, ids)| !ids.is_empty()) {
+            let color = match style {
+                Style::Unvisited => format!(",color:{}", UNVISITED_COLOR),
+                _ => String::new(),
+            };
+            mermaid.push_str(&format!(
+                "    classDef {} fill:{},stroke:{}{}\n",
+                style.class(),
+                style.fill(),
+                style.stroke(),
+                color
+            ));
+            mermaid.push_str(&format!("    class {} {}\n", ids.join(","), style.class()));
+        }
+
+        let catch_links: Vec<String> = self
+            .links
+            .iter()
+            .enumerate()
+            .filter(|(_, link)| link.catch)
+            .map(|(index, _)| index.to_string())
+            .collect();
+        if !catch_links.is_empty() {
+            mermaid.push_str(&format!(
+                "    linkStyle {} stroke:{},color:{}\n",
+                catch_links.join(","),
+                CATCH_COLOR,
+                CATCH_COLOR
+            ));
+        }
+
+        mermaid
+    }
+}
+
+fn dot_items(items: &[Item], depth: usize, dot: &mut String) {
+    let indent = "    ".repeat(depth);
+
+    for item in items {
+        match item {
+            Item::Node(node) => {
+                let mut attributes = vec![format!("label=\"{}\"", dot_escape(&node.label))];
+                match node.kind {
+                    None if node.id == START_ID => attributes.push("shape=circle".to_string()),
+                    None => attributes.push("shape=doublecircle".to_string()),
+                    Some("Choice") => attributes.push("shape=diamond".to_string()),
+                    Some("Wait") => attributes.push("shape=hexagon".to_string()),
+                    Some(_) => {}
+                }
+
+                let mut styles = vec!["rounded"];
+                match node.kind {
+                    Some("Pass") => styles.push("dashed"),
+                    Some("Parallel" | "Map") => styles.push("bold"),
+                    _ => {}
+                }
+                let colors = match node.style {
+                    None => None,
+                    Some(Style::Unvisited) => {
+                        Some(format!("color=\"{0}\", fontcolor=\"{0}\"", UNVISITED_COLOR))
+                    }
+                    Some(style) => {
+                        styles.push("filled");
+                        Some(format!("fillcolor=\"{}\"", style.fill()))
+                    }
+                };
+                if styles.len() > 1 {
+                    attributes.push(format!("style=\"{}\"", styles.join(",")));
+                }
+                attributes.extend(colors);
+
+                dot.push_str(&format!(
+                    "{}{} [{}];\n",
+                    indent,
+                    node.id,
+                    attributes.join(", ")
+                ));
+            }
+            Item::Cluster(cluster) => {
+                dot.push_str(&format!("{}subgraph {} {{\n", indent, cluster.id));
+                dot.push_str(&format!(
+                    "{}    label=\"{}\";\n",
+                    indent,
+                    dot_escape(&cluster.label)
+                ));
+                dot_items(&cluster.items, depth + 1, dot);
+                dot.push_str(&format!("{}}}\n", indent));
+            }
+        }
+    }
+}
+
+fn mermaid_items<'a>(
+    items: &'a [Item],
+    depth: usize,
+    mermaid: &mut String,
+    classes: &mut [(Style, Vec<&'a str>)],
+) {
+    let indent = "    ".repeat(depth);
+
+    for item in items {
+        match item {
+            Item::Node(node) => {
+                let label = mermaid_escape(&node.label);
+                let shape = match node.kind {
+                    None if node.id == START_ID => format!("((\"{}\"))", label),
+                    None => format!("(((\"{}\")))", label),
+                    Some("Choice") => format!("{{\"{}\"}}", label),
+                    Some("Wait") => format!("{{{{\"{}\"}}}}", label),
+                    Some("Succeed" | "Fail") => format!("([\"{}\"])", label),
+                    Some("Parallel" | "Map") => format!("[[\"{}\"]]", label),
+                    Some(_) => format!("[\"{}\"]", label),
+                };
+                mermaid.push_str(&format!("{}{}{}\n", indent, node.id, shape));
+
+                if let Some((_, ids)) = classes
+                    .iter_mut()
+                    .find(|(style, _)| Some(*style) == node.style)
+                {
+                    ids.push(&node.id);
+                }
+            }
+            Item::Cluster(cluster) => {
+                mermaid.push_str(&format!(
+                    "{}subgraph {}[\"{}\"]\n",
+                    indent,
+                    cluster.id,
+                    mermaid_escape(&cluster.label)
+                ));
+                mermaid_items(&cluster.items, depth + 1, mermaid, classes);
+                mermaid.push_str(&format!("{}end\n", indent));
+            }
+        }
+    }
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Mermaid labels are quoted, quotes and angle brackets are written as entities.
+fn mermaid_escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+        .replace('\n', "<br>")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::asl::parse_definition;
+
+    fn definition() -> Definition {
+        parse_definition(
+            r#"{
+  "StartAt": "Hungry?",
+  "States": {
+    "Hungry?": {
+      "Type": "Choice",
+      "Choices": [{"Variable": "$.hunger", "NumericGreaterThan": 5, "Next": "Hunt"}],
+      "Default": "Nap"
+    },
+    "Hunt": {
+      "Type": "Parallel",
+      "Branches": [
+        {"StartAt": "Roar", "States": {"Roar": {"Type": "Pass", "End": true}}},
+        {"StartAt": "Chase", "States": {"Chase": {"Type": "Task", "Resource": "arn:aws:lambda:us-east-1:123456789012:function:chase", "End": true}}}
+      ],
+      "Catch": [{"ErrorEquals": ["Escaped", "States.Timeout"], "Next": "Starve"}],
+      "Next": "Eat"
+    },
+    "Eat": {
+      "Type": "Map",
+      "Iterator": {"StartAt": "Chew", "States": {"Chew": {"Type": "Wait", "Seconds": 1, "End": true}}},
+      "End": true
+    },
+    "Nap": {"Type": "Succeed"},
+    "Starve": {"Type": "Fail", "Error": "Starved"}
+  }
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn should_render_dot() {
+        assert_eq!(
+            render(&definition(), "t-rex.json", DiagramFormat::Dot),
+            r##"digraph "t-rex.json" {
+    node [shape=box, style=rounded];
+    start_node [label="Start", shape=circle];
+    state0 [label="Hungry?", shape=diamond];
+    state1 [label="Hunt", style="rounded,bold"];
+    subgraph cluster5 {
+        label="Hunt branch 1";
+        state6 [label="Roar", style="rounded,dashed"];
+    }
+    subgraph cluster7 {
+        label="Hunt branch 2";
+        state8 [label="Chase"];
+    }
+    state2 [label="Eat", style="rounded,bold"];
+    subgraph cluster9 {
+        label="Eat iterator";
+        state10 [label="Chew", shape=hexagon];
+    }
+    state3 [label="Nap", style="rounded,filled", fillcolor="#d4edda"];
+    state4 [label="Starve", style="rounded,filled", fillcolor="#f8d7da"];
+    end_node [label="End", shape=doublecircle];
+    start_node -> state0;
+    state0 -> state1 [label="$.hunger > 5"];
+    state0 -> state3 [label="Default"];
+    state1 -> state2;
+    state1 -> state4 [label="Escaped, States.Timeout", style=dashed, color="#c0392b", fontcolor="#c0392b"];
+    state1 -> state6;
+    state1 -> state8;
+    state2 -> end_node;
+    state2 -> state10;
+    state3 -> end_node;
+    state4 -> end_node;
+}
+"##
+        );
+    }
+
+    #[test]
+    fn should_render_mermaid() {
+        assert_eq!(
+            render(&definition(), "t-rex.json", DiagramFormat::Mermaid),
+            r##"flowchart TD
+    start_node(("Start"))
+    state0{"Hungry?"}
+    state1[["Hunt"]]
+    subgraph cluster5["Hunt branch 1"]
+        state6["Roar"]
+    end
+    subgraph cluster7["Hunt branch 2"]
+        state8["Chase"]
+    end
+    state2[["Eat"]]
+    subgraph cluster9["Eat iterator"]
+        state10{{"Chew"}}
+    end
+    state3(["Nap"])
+    state4(["Starve"])
+    end_node((("End")))
+    start_node --> state0
+    state0 -->|"$.hunger #gt; 5"| state1
+    state0 -->|"Default"| state3
+    state1 --> state2
+    state1 -.->|"Escaped, States.Timeout"| state4
+    state1 --> state6
+    state1 --> state8
+    state2 --> end_node
+    state2 --> state10
+    state3 --> end_node
+    state4 --> end_node
+    classDef succeeded fill:#d4edda,stroke:#28a745
+    class state3 succeeded
+    classDef failed fill:#f8d7da,stroke:#c0392b
+    class state4 failed
+    linkStyle 4 stroke:#c0392b,color:#c0392b
+"##
+        );
+    }
+
+    #[test]
+    fn should_color_the_states_an_execution_visited() {
+        let activity = HashMap::from([
+            (
+                "Hungry?".to_string(),
+                StateActivity {
+                    status: StateStatus::Succeeded,
+                    runs: 1,
+                    retries: 0,
+                },
+            ),
+            (
+                "Hunt".to_string(),
+                StateActivity {
+                    status: StateStatus::Caught,
+                    runs: 1,
+                    retries: 0,
+                },
+            ),
+            (
+                "Roar".to_string(),
+                StateActivity {
+                    status: StateStatus::Succeeded,
+                    runs: 1,
+                    retries: 0,
+                },
+            ),
+            (
+                "Chase".to_string(),
+                StateActivity {
+                    status: StateStatus::Failed,
+                    runs: 3,
+                    retries: 2,
+                },
+            ),
+            (
+                "Starve".to_string(),
+                StateActivity {
+                    status: StateStatus::Running,
+                    runs: 1,
+                    retries: 0,
+                },
+            ),
+        ]);
+
+        let dot = render_execution(&definition(), "hunt-42", DiagramFormat::Dot, &activity);
+        for line in [
+            r##"state1 [label="Hunt", style="rounded,bold,filled", fillcolor="#fff3cd"];"##,
+            r##"state8 [label="Chase\n3 runs, 2 retries", style="rounded,filled", fillcolor="#f8d7da"];"##,
+            r##"state3 [label="Nap", color="#999999", fontcolor="#999999"];"##,
+            r##"state4 [label="Starve", style="rounded,filled", fillcolor="#cce5ff"];"##,
+        ] {
+            assert!(dot.contains(line), "{}", line);
+        }
+
+        assert_eq!(
+            render_execution(&definition(), "hunt-42", DiagramFormat::Mermaid, &activity),
+            r##"flowchart TD
+    start_node(("Start"))
+    state0{"Hungry?"}
+    state1[["Hunt"]]
+    subgraph cluster5["Hunt branch 1"]
+        state6["Roar"]
+    end
+    subgraph cluster7["Hunt branch 2"]
+        state8["Chase<br>3 runs, 2 retries"]
+    end
+    state2[["Eat"]]
+    subgraph cluster9["Eat iterator"]
+        state10{{"Chew"}}
+    end
+    state3(["Nap"])
+    state4(["Starve"])
+    end_node((("End")))
+    start_node --> state0
+    state0 -->|"$.hunger #gt; 5"| state1
+    state0 -->|"Default"| state3
+    state1 --> state2
+    state1 -.->|"Escaped, States.Timeout"| state4
+    state1 --> state6
+    state1 --> state8
+    state2 --> end_node
+    state2 --> state10
+    state3 --> end_node
+    state4 --> end_node
+    classDef succeeded fill:#d4edda,stroke:#28a745
+    class state0,state6 succeeded
+    classDef caught fill:#fff3cd,stroke:#e67e22
+    class state1 caught
+    classDef running fill:#cce5ff,stroke:#007bff
+    class state4 running
+    classDef failed fill:#f8d7da,stroke:#c0392b
+    class state8 failed
+    classDef unvisited fill:#ffffff,stroke:#999999,color:#999999
+    class state2,state10,state3 unvisited
+    linkStyle 4 stroke:#c0392b,color:#c0392b
+"##
+        );
+    }
+
+    #[test]
+    fn should_escape_labels_and_skip_unknown_targets() {
+        let definition = parse_definition(
+            r#"{"StartAt": "Say \"roar\"", "States": {"Say \"roar\"": {"Type": "Pass", "Next": "Meteor"}}}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            render(&definition, "t-rex", DiagramFormat::Mermaid),
+            r#"flowchart TD
+    start_node(("Start"))
+    state0["Say #quot;roar#quot;"]
+    end_node((("End")))
+    start_node --> state0
+"#
+        );
+        assert!(render(&definition, "t-rex", DiagramFormat::Dot)
+            .contains(r#"state0 [label="Say \"roar\"", style="rounded,dashed"];"#));
+    }
+}
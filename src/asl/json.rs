@@ -0,0 +1,434 @@
+use crate::asl::{AslError, Position};
+use serde_json::{Map, Number, Value};
+
+/// JSON value that remembers where it starts in the definition, so errors and findings can
+/// point at the line and column of the field they are about.
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonNode {
+    pub value: JsonValue,
+    pub position: Position,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum JsonValue {
+    Null,
+    Bool(bool),
+    Number(Number),
+    String(String),
+    Array(Vec<JsonNode>),
+    /// Members in the order they were written.
+    Object(Vec<(JsonKey, JsonNode)>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct JsonKey {
+    pub name: String,
+    pub position: Position,
+}
+
+impl JsonNode {
+    pub fn type_name(&self) -> &'static str {
+        match self.value {
+            JsonValue::Null => "null",
+            JsonValue::Bool(_) => "a boolean",
+            JsonValue::Number(_) => "a number",
+            JsonValue::String(_) => "a string",
+            JsonValue::Array(_) => "an array",
+            JsonValue::Object(_) => "an object",
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match &self.value {
+            JsonValue::Null => Value::Null,
+            JsonValue::Bool(value) => Value::Bool(*value),
+            JsonValue::Number(value) => Value::Number(value.clone()),
+            JsonValue::String(value) => Value::String(value.clone()),
+            JsonValue::Array(items) => Value::Array(items.iter().map(JsonNode::to_value).collect()),
+            JsonValue::Object(members) => Value::Object(
+                members
+                    .iter()
+                    .map(|(key, node)| (key.name.clone(), node.to_value()))
+                    .collect::<Map<String, Value>>(),
+            ),
+        }
+    }
+}
+
+/// Deepest nesting of objects and arrays accepted, as serde_json does, so a definition
+/// cannot overflow the stack of the parser.
+const MAX_NESTING_DEPTH: usize = 128;
+
+pub fn parse(source: &str) -> Result<JsonNode, AslError> {
+    let mut parser = JsonParser::new(source);
+
+    parser.skip_whitespace();
+    let node = parser.parse_value(0)?;
+    parser.skip_whitespace();
+    match parser.peek() {
+        None => Ok(node),
+        Some(_) => Err(parser.error("unexpected characters after the end of the JSON value")),
+    }
+}
+
+struct JsonParser<'a> {
+    source: &'a str,
+    position: usize,
+    line_starts: Vec<usize>,
+}
+
+impl<'a> JsonParser<'a> {
+    fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.match_indices('\n').map(|(index, _)| index + 1))
+            .collect();
+
+        JsonParser {
+            source,
+            position: 0,
+            line_starts,
+        }
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let character = self.peek()?;
+        self.position += character.len_utf8();
+        Some(character)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(' ' | '\t' | '\n' | '\r')) {
+            self.position += 1;
+        }
+    }
+
+    /// Line and column, both starting at 1, of a byte offset of the source.
+    fn locate(&self, offset: usize) -> Position {
+        let line = self.line_starts.partition_point(|start| *start <= offset);
+        let line_start = self.line_starts[line - 1];
+
+        Position {
+            line,
+            column: self.source[line_start..offset].chars().count() + 1,
+        }
+    }
+
+    fn error(&self, message: &str) -> AslError {
+        AslError::new(self.locate(self.position), message)
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), AslError> {
+        match self.peek() {
+            Some(character) if character == expected => {
+                self.position += 1;
+                Ok(())
+            }
+            Some(character) => Err(self.error(&format!(
+                "expected '{}' but found '{}'",
+                expected, character
+            ))),
+            None => Err(self.error(&format!("expected '{}' but the definition ended", expected))),
+        }
+    }
+
+    fn check_depth(&self, depth: usize) -> Result<(), AslError> {
+        match depth > MAX_NESTING_DEPTH {
+            true => Err(self.error(&format!(
+                "nesting too deep, objects and arrays can be nested {} levels at most",
+                MAX_NESTING_DEPTH
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// Parses the value at the current position, inside `depth` objects and arrays.
+    fn parse_value(&mut self, depth: usize) -> Result<JsonNode, AslError> {
+        let position = self.locate(self.position);
+
+        let value = match self.peek() {
+            Some('{') => self.parse_object(depth + 1)?,
+            Some('[') => self.parse_array(depth + 1)?,
+            Some('"') => JsonValue::String(self.parse_string()?),
+            Some('-' | '0'..='9') => JsonValue::Number(self.parse_number()?),
+            Some(_) if self.rest().starts_with("true") => {
+                self.position += "true".len();
+                JsonValue::Bool(true)
+            }
+            Some(_) if self.rest().starts_with("false") => {
+                self.position += "false".len();
+                JsonValue::Bool(false)
+            }
+            Some(_) if self.rest().starts_with("null") => {
+                self.position += "null".len();
+                JsonValue::Null
+            }
+            Some(character) => {
+                return Err(self.error(&format!("expected a JSON value but found '{}'", character)))
+            }
+            None => return Err(self.error("expected a JSON value but the definition ended")),
+        };
+
+        Ok(JsonNode { value, position })
+    }
+
+    fn parse_object(&mut self, depth: usize) -> Result<JsonValue, AslError> {
+        self.check_depth(depth)?;
+        self.expect('{')?;
+        let mut members: Vec<(JsonKey, JsonNode)> = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some('}') {
+            self.position += 1;
+            return Ok(JsonValue::Object(members));
+        }
+
+        loop {
+            self.skip_whitespace();
+            let key_position = self.locate(self.position);
+            if self.peek() != Some('"') {
+                return Err(self.error("expected a quoted field name"));
+            }
+            let name = self.parse_string()?;
+            if members.iter().any(|(key, _)| key.name == name) {
+                return Err(AslError::new(
+                    key_position,
+                    &format!("duplicate field \"{}\"", name),
+                ));
+            }
+
+            self.skip_whitespace();
+            self.expect(':')?;
+            self.skip_whitespace();
+            let node = self.parse_value(depth)?;
+            members.push((
+                JsonKey {
+                    name,
+                    position: key_position,
+                },
+                node,
+            ));
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some('}') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Object(members));
+                }
+                _ => return Err(self.error("expected ',' or '}' after the field")),
+            }
+        }
+    }
+
+    fn parse_array(&mut self, depth: usize) -> Result<JsonValue, AslError> {
+        self.check_depth(depth)?;
+        self.expect('[')?;
+        let mut items: Vec<JsonNode> = vec![];
+
+        self.skip_whitespace();
+        if self.peek() == Some(']') {
+            self.position += 1;
+            return Ok(JsonValue::Array(items));
+        }
+
+        loop {
+            self.skip_whitespace();
+            items.push(self.parse_value(depth)?);
+
+            self.skip_whitespace();
+            match self.peek() {
+                Some(',') => self.position += 1,
+                Some(']') => {
+                    self.position += 1;
+                    return Ok(JsonValue::Array(items));
+                }
+                _ => return Err(self.error("expected ',' or ']' after the item")),
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, AslError> {
+        self.expect('"')?;
+        let mut value = String::new();
+
+        loop {
+            let escape_position = self.position;
+            match self.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.next() {
+                    Some('"') => value.push('"'),
+                    Some('\\') => value.push('\\'),
+                    Some('/') => value.push('/'),
+                    Some('b') => value.push('\u{8}'),
+                    Some('f') => value.push('\u{c}'),
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('u') => {
+                        let character = self.parse_unicode_escape().ok_or_else(|| {
+                            AslError::new(self.locate(escape_position), "invalid unicode escape")
+                        })?;
+                        value.push(character);
+                    }
+                    _ => {
+                        return Err(AslError::new(
+                            self.locate(escape_position),
+                            "invalid escape sequence",
+                        ))
+                    }
+                },
+                Some(character) if character < ' ' => {
+                    self.position -= 1;
+                    return Err(self.error("control character inside a string"));
+                }
+                Some(character) => value.push(character),
+                None => return Err(self.error("string is not closed")),
+            }
+        }
+    }
+
+    /// Reads the 4 hex digits after `\u`, and the low surrogate that follows a high one.
+    fn parse_unicode_escape(&mut self) -> Option<char> {
+        let high = self.parse_hex()?;
+        if !(0xD800..0xDC00).contains(&high) {
+            return char::from_u32(high);
+        }
+
+        if !self.rest().starts_with("\\u") {
+            return None;
+        }
+        self.position += 2;
+        let low = self.parse_hex()?;
+        if !(0xDC00..0xE000).contains(&low) {
+            return None;
+        }
+
+        char::from_u32(0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00))
+    }
+
+    fn parse_hex(&mut self) -> Option<u32> {
+        let digits = self.rest().get(..4)?;
+        let value = u32::from_str_radix(digits, 16).ok()?;
+        self.position += 4;
+        Some(value)
+    }
+
+    fn parse_number(&mut self) -> Result<Number, AslError> {
+        let start = self.position;
+        let number_length = self
+            .rest()
+            .find(|character: char| !matches!(character, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(self.rest().len());
+        let raw_number = &self.rest()[..number_length];
+
+        match serde_json::from_str::<Number>(raw_number) {
+            Ok(number) => {
+                self.position += number_length;
+                Ok(number)
+            }
+            Err(_) => Err(AslError::new(
+                self.locate(start),
+                &format!("invalid number {}", raw_number),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_values_with_their_position() {
+        let node =
+            parse("{\n  \"Count\": 3,\n  \"Tags\": [true, null, \"caf\\u00e9\"]\n}").unwrap();
+
+        assert_eq!(node.position, Position { line: 1, column: 1 });
+        let JsonValue::Object(members) = &node.value else {
+            panic!("expected an object");
+        };
+        assert_eq!(members[0].0.name, "Count");
+        assert_eq!(members[0].0.position, Position { line: 2, column: 3 });
+        assert_eq!(
+            members[0].1.position,
+            Position {
+                line: 2,
+                column: 12
+            }
+        );
+        assert_eq!(
+            members[1].1.position,
+            Position {
+                line: 3,
+                column: 11
+            }
+        );
+        assert_eq!(
+            node.to_value(),
+            serde_json::json!({"Count": 3, "Tags": [true, null, "café"]})
+        );
+    }
+
+    #[test]
+    fn should_report_where_the_json_is_invalid() {
+        let error = |source: &str| parse(source).unwrap_err().to_string();
+
+        assert_eq!(
+            error("{\n  \"StartAt\": \"Roar\"\n  \"States\": {}\n}"),
+            "line 3, column 3: expected ',' or '}' after the field"
+        );
+        assert_eq!(
+            error("{\"Roar\": 1, \"Roar\": 2}"),
+            "line 1, column 13: duplicate field \"Roar\""
+        );
+        assert_eq!(
+            error("[1, 2"),
+            "line 1, column 6: expected ',' or ']' after the item"
+        );
+        assert_eq!(
+            error("{\"Seconds\": 01}"),
+            "line 1, column 13: invalid number 01"
+        );
+        assert_eq!(
+            error("\"\\x\""),
+            "line 1, column 2: invalid escape sequence"
+        );
+        assert_eq!(
+            error("{} {}"),
+            "line 1, column 4: unexpected characters after the end of the JSON value"
+        );
+        assert_eq!(
+            error("{\"Roar\": tru}"),
+            "line 1, column 10: expected a JSON value but found 't'"
+        );
+    }
+
+    #[test]
+    fn should_reject_definitions_nested_too_deep() {
+        let nested = |depth: usize| {
+            format!(
+                "{{\"Parameters\": {}1{}}}",
+                "{\"Egg\": [".repeat(depth),
+                "]}".repeat(depth)
+            )
+        };
+
+        assert!(parse(&nested(MAX_NESTING_DEPTH / 2 - 1)).is_ok());
+        assert_eq!(
+            parse(&nested(MAX_NESTING_DEPTH / 2)).unwrap_err().to_string(),
+            "line 1, column 591: nesting too deep, objects and arrays can be nested 128 levels at most"
+        );
+        assert!(parse(&nested(6000))
+            .unwrap_err()
+            .to_string()
+            .contains("nesting too deep"));
+    }
+}
@@ -0,0 +1,1044 @@
+use crate::asl::json::{JsonNode, JsonValue};
+use crate::asl::model::{
+    ComparisonValue, Condition, Definition, Located, QueryLanguage, Retrier, State, StateGraph,
+    StateKind, WaitDuration,
+};
+use crate::asl::path::{check_intrinsic, check_path, PathKind};
+use crate::asl::{parse_definition, Position};
+use crate::output::Tabular;
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Above this number of attempts a retrier is reported as too aggressive.
+const MAX_RETRY_ATTEMPTS: u64 = 10;
+
+/// Above this number of attempts a retrier without backoff is reported as too aggressive.
+const MAX_RETRY_ATTEMPTS_WITHOUT_BACKOFF: u64 = 3;
+
+/// Longest an Express execution can run, in seconds.
+const EXPRESS_MAX_DURATION: u64 = 300;
+
+const DEFAULT_MAX_ATTEMPTS: u64 = 3;
+const DEFAULT_BACKOFF_RATE: f64 = 2.0;
+const DEFAULT_INTERVAL_SECONDS: u64 = 1;
+/// Shortest wait between retries Step Functions accepts, in seconds.
+const MIN_INTERVAL_SECONDS: u64 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Rule {
+    InvalidDefinition,
+    UnknownState,
+    UnreachableState,
+    MissingTransition,
+    ChoiceWithoutDefault,
+    TaskWithoutCatch,
+    AggressiveRetry,
+    LoopWithoutWait,
+    InvalidPath,
+    PayloadTemplate,
+    LiteralPath,
+    ExpressUnsupported,
+    UnknownField,
+}
+
+impl Rule {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rule::InvalidDefinition => "invalid-definition",
+            Rule::UnknownState => "unknown-state",
+            Rule::UnreachableState => "unreachable-state",
+            Rule::MissingTransition => "missing-transition",
+            Rule::ChoiceWithoutDefault => "choice-without-default",
+            Rule::TaskWithoutCatch => "task-without-catch",
+            Rule::AggressiveRetry => "aggressive-retry",
+            Rule::LoopWithoutWait => "loop-without-wait",
+            Rule::InvalidPath => "invalid-path",
+            Rule::PayloadTemplate => "payload-template",
+            Rule::LiteralPath => "literal-path",
+            Rule::ExpressUnsupported => "express-unsupported",
+            Rule::UnknownField => "unknown-field",
+        }
+    }
+
+    /// Errors are rejected by Step Functions or fail every execution that reaches them,
+    /// warnings are risky but valid.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Rule::ChoiceWithoutDefault
+            | Rule::TaskWithoutCatch
+            | Rule::AggressiveRetry
+            | Rule::LoopWithoutWait
+            | Rule::LiteralPath
+            | Rule::UnknownField => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+/// Problem found in a definition, at the line and column it is about.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub rule: Rule,
+    pub position: Position,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    pub message: String,
+}
+
+impl Display for Finding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(
+            f,
+            "{}:{}: {}: {} [{}]",
+            self.position.line,
+            self.position.column,
+            self.severity.as_str(),
+            self.message,
+            self.rule.as_str()
+        )
+    }
+}
+
+impl Tabular for Finding {
+    fn headers() -> Vec<&'static str> {
+        vec!["line", "column", "severity", "rule", "state", "message"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.position.line.to_string(),
+            self.position.column.to_string(),
+            self.severity.as_str().to_string(),
+            self.rule.as_str().to_string(),
+            self.state.clone().unwrap_or_default(),
+            self.message.clone(),
+        ]
+    }
+}
+
+/// Lints the source of a definition, reporting a definition that does not parse as a single
+/// finding. `express` checks it against the limits of Express workflows.
+pub fn lint_source(source: &str, express: bool) -> Vec<Finding> {
+    match parse_definition(source) {
+        Ok(definition) => lint(&definition, express),
+        Err(error) => vec![Finding {
+            severity: Rule::InvalidDefinition.severity(),
+            rule: Rule::InvalidDefinition,
+            position: error.position,
+            state: None,
+            message: error.message,
+        }],
+    }
+}
+
+/// Findings of a definition, in the order they appear in it.
+pub fn lint(definition: &Definition, express: bool) -> Vec<Finding> {
+    let mut linter = Linter {
+        express,
+        findings: vec![],
+    };
+
+    if express {
+        if let Some(timeout_seconds) = definition
+            .timeout_seconds
+            .filter(|timeout_seconds| *timeout_seconds > EXPRESS_MAX_DURATION)
+        {
+            linter.report(
+                Rule::ExpressUnsupported,
+                definition.graph.position,
+                None,
+                format!(
+                    "TimeoutSeconds is {} but Express executions run for at most {} seconds",
+                    timeout_seconds, EXPRESS_MAX_DURATION
+                ),
+            );
+        }
+    }
+    linter.lint_graph(&definition.graph, false);
+    for field in &definition.unknown_fields {
+        linter.report(
+            Rule::UnknownField,
+            field.position,
+            None,
+            format!("unknown field \"{}\" in {}", field.name, field.context),
+        );
+    }
+
+    linter
+        .findings
+        .sort_by_key(|finding| (finding.position.line, finding.position.column));
+    linter.findings
+}
+
+struct Linter {
+    express: bool,
+    findings: Vec<Finding>,
+}
+
+impl Linter {
+    fn report(&mut self, rule: Rule, position: Position, state: Option<&State>, message: String) {
+        self.findings.push(Finding {
+            severity: rule.severity(),
+            rule,
+            position,
+            state: state.map(|state| state.name.clone()),
+            message,
+        });
+    }
+
+    /// `caught` tells whether a Parallel or Map state around the graph catches its errors.
+    fn lint_graph(&mut self, graph: &StateGraph, caught: bool) {
+        self.check_targets(graph);
+        self.check_reachability(graph);
+        self.check_loops(graph);
+
+        for state in &graph.states {
+            self.lint_state(state, caught);
+        }
+    }
+
+    fn check_targets(&mut self, graph: &StateGraph) {
+        if graph.state(&graph.start_at.value).is_none() {
+            self.report(
+                Rule::UnknownState,
+                graph.start_at.position,
+                None,
+                format!("StartAt names unknown state \"{}\"", graph.start_at.value),
+            );
+        }
+
+        for state in &graph.states {
+            for edge in state.edges() {
+                if graph.state(edge.target).is_none() {
+                    self.report(
+                        Rule::UnknownState,
+                        edge.position,
+                        Some(state),
+                        format!(
+                            "{} names unknown state \"{}\"",
+                            edge.kind.field(),
+                            edge.target
+                        ),
+                    );
+                }
+            }
+        }
+    }
+
+    fn check_reachability(&mut self, graph: &StateGraph) {
+        let mut reached: HashSet<&str> = HashSet::new();
+        let mut pending: VecDeque<&str> = VecDeque::from([graph.start_at.value.as_str()]);
+
+        while let Some(name) = pending.pop_front() {
+            let Some(state) = graph.state(name) else {
+                continue;
+            };
+            if reached.insert(&state.name) {
+                pending.extend(state.edges().iter().map(|edge| edge.target));
+            }
+        }
+
+        for state in &graph.states {
+            if !reached.contains(state.name.as_str()) {
+                self.report(
+                    Rule::UnreachableState,
+                    state.position,
+                    Some(state),
+                    format!("state \"{}\" can never be reached", state.name),
+                );
+            }
+        }
+    }
+
+    /// Reports the cycles that go through no Wait state, which run as fast as Step Functions
+    /// allows and burn through state transitions.
+    fn check_loops(&mut self, graph: &StateGraph) {
+        let indexes: HashMap<&str, usize> = graph
+            .states
+            .iter()
+            .enumerate()
+            .filter(|(_, state)| !matches!(state.kind, StateKind::Wait(_)))
+            .map(|(index, state)| (state.name.as_str(), index))
+            .collect();
+        let successors: Vec<Vec<usize>> = graph
+            .states
+            .iter()
+            .map(|state| match state.kind {
+                StateKind::Wait(_) => vec![],
+                _ => state
+                    .edges()
+                    .iter()
+                    .filter_map(|edge| indexes.get(edge.target).copied())
+                    .collect(),
+            })
+            .collect();
+
+        let reachable: Vec<HashSet<usize>> = (0..graph.states.len())
+            .map(|start| {
+                let mut reached = HashSet::new();
+                let mut pending = successors[start].clone();
+                while let Some(index) = pending.pop() {
+                    if reached.insert(index) {
+                        pending.extend(&successors[index]);
+                    }
+                }
+                reached
+            })
+            .collect();
+
+        let mut reported: HashSet<usize> = HashSet::new();
+        for index in 0..graph.states.len() {
+            if reported.contains(&index) || !reachable[index].contains(&index) {
+                continue;
+            }
+
+            let members: Vec<usize> = (index..graph.states.len())
+                .filter(|other| {
+                    reachable[index].contains(other) && reachable[*other].contains(&index)
+                })
+                .collect();
+            reported.extend(&members);
+
+            let state = &graph.states[index];
+            let message = match members.as_slice() {
+                [_] => format!(
+                    "state \"{}\" loops back to itself without a Wait state",
+                    state.name
+                ),
+                _ => format!(
+                    "states {} loop without a Wait state",
+                    members
+                        .iter()
+                        .map(|member| format!("\"{}\"", graph.states[*member].name))
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            };
+            self.report(Rule::LoopWithoutWait, state.position, Some(state), message);
+        }
+    }
+
+    fn lint_state(&mut self, state: &State, caught: bool) {
+        let needs_transition = !matches!(
+            state.kind,
+            StateKind::Choice(_) | StateKind::Succeed | StateKind::Fail(_)
+        );
+        if needs_transition && state.transition.is_none() {
+            self.report(
+                Rule::MissingTransition,
+                state.position,
+                Some(state),
+                format!(
+                    "{} state \"{}\" has neither \"Next\" nor \"End\"",
+                    state.kind.type_name(),
+                    state.name
+                ),
+            );
+        }
+
+        self.check_optional_path(state, "InputPath", &state.input_path, PathKind::Path);
+        self.check_optional_path(state, "OutputPath", &state.output_path, PathKind::Path);
+        self.check_template(state, state.assign.as_ref());
+
+        if let Some(errors) = state.kind.error_handling() {
+            for retrier in &errors.retry {
+                self.check_retrier(state, retrier);
+            }
+            for catcher in &errors.catch {
+                self.check_optional_path(
+                    state,
+                    "ResultPath",
+                    &catcher.result_path,
+                    PathKind::Reference,
+                );
+                self.check_template(state, catcher.assign.as_ref());
+            }
+        }
+
+        match &state.kind {
+            StateKind::Task(task) => {
+                self.check_optional_path(
+                    state,
+                    "ResultPath",
+                    &task.result.result_path,
+                    PathKind::Reference,
+                );
+                self.check_template(state, task.result.parameters.as_ref());
+                self.check_template(state, task.result.result_selector.as_ref());
+                self.check_template(state, task.credentials.as_ref());
+                self.check_path(
+                    state,
+                    "TimeoutSecondsPath",
+                    task.timeout_seconds_path.as_ref(),
+                    PathKind::Reference,
+                );
+                self.check_path(
+                    state,
+                    "HeartbeatSecondsPath",
+                    task.heartbeat_seconds_path.as_ref(),
+                    PathKind::Reference,
+                );
+
+                if task.errors.catch.is_empty() && !caught {
+                    self.report(
+                        Rule::TaskWithoutCatch,
+                        state.position,
+                        Some(state),
+                        format!(
+                            "Task state \"{}\" has no Catch, an error left after its retries fails the execution",
+                            state.name
+                        ),
+                    );
+                }
+                if self.express {
+                    self.check_express_resource(state, &task.resource);
+                }
+            }
+            StateKind::Pass(pass) => {
+                self.check_optional_path(
+                    state,
+                    "ResultPath",
+                    &pass.result.result_path,
+                    PathKind::Reference,
+                );
+                self.check_template(state, pass.result.parameters.as_ref());
+                if let Some(output) = &pass.output {
+                    self.check_literal_result(state, output);
+                }
+            }
+            StateKind::Choice(choice) => {
+                if choice.default.is_none() {
+                    self.report(
+                        Rule::ChoiceWithoutDefault,
+                        state.position,
+                        Some(state),
+                        format!(
+                            "Choice state \"{}\" has no Default, an input matching no rule fails the execution with States.NoChoiceMatched",
+                            state.name
+                        ),
+                    );
+                }
+                for rule in &choice.choices {
+                    self.check_condition(state, &rule.condition);
+                    self.check_template(state, rule.assign.as_ref());
+                }
+            }
+            StateKind::Wait(wait) => match &wait.wait {
+                WaitDuration::Seconds(seconds)
+                    if self.express && *seconds > EXPRESS_MAX_DURATION =>
+                {
+                    self.report(
+                        Rule::ExpressUnsupported,
+                        state.position,
+                        Some(state),
+                        format!(
+                            "Wait state \"{}\" waits {} seconds but Express executions run for at most {} seconds",
+                            state.name, seconds, EXPRESS_MAX_DURATION
+                        ),
+                    );
+                }
+                WaitDuration::SecondsPath(path) => {
+                    self.check_path(state, "SecondsPath", Some(path), PathKind::Reference)
+                }
+                WaitDuration::TimestampPath(path) => {
+                    self.check_path(state, "TimestampPath", Some(path), PathKind::Reference)
+                }
+                _ => {}
+            },
+            StateKind::Succeed => {}
+            StateKind::Fail(fail) => {
+                for (name, path) in [
+                    ("ErrorPath", &fail.error_path),
+                    ("CausePath", &fail.cause_path),
+                ] {
+                    if let Some(path) = path {
+                        self.check_expression(state, name, path);
+                    }
+                }
+            }
+            StateKind::Parallel(parallel) => {
+                self.check_optional_path(
+                    state,
+                    "ResultPath",
+                    &parallel.result.result_path,
+                    PathKind::Reference,
+                );
+                self.check_template(state, parallel.result.parameters.as_ref());
+                self.check_template(state, parallel.result.result_selector.as_ref());
+
+                let branches_caught = caught || !parallel.errors.catch.is_empty();
+                for branch in &parallel.branches {
+                    self.lint_graph(branch, branches_caught);
+                }
+            }
+            StateKind::Map(map) => {
+                self.check_optional_path(
+                    state,
+                    "ResultPath",
+                    &map.result.result_path,
+                    PathKind::Reference,
+                );
+                self.check_path(
+                    state,
+                    "ItemsPath",
+                    map.items_path.as_ref(),
+                    PathKind::Reference,
+                );
+                self.check_path(
+                    state,
+                    "MaxConcurrencyPath",
+                    map.max_concurrency_path.as_ref(),
+                    PathKind::Reference,
+                );
+                self.check_path(
+                    state,
+                    "ToleratedFailureCountPath",
+                    map.tolerated_failure_count_path.as_ref(),
+                    PathKind::Reference,
+                );
+                self.check_path(
+                    state,
+                    "ToleratedFailurePercentagePath",
+                    map.tolerated_failure_percentage_path.as_ref(),
+                    PathKind::Reference,
+                );
+                for template in [
+                    &map.result.parameters,
+                    &map.result.result_selector,
+                    &map.item_reader,
+                    &map.item_batcher,
+                    &map.result_writer,
+                ] {
+                    self.check_template(state, template.as_ref());
+                }
+
+                if let Some(mode) = &map.mode {
+                    if self.express && mode.value == "DISTRIBUTED" {
+                        self.report(
+                            Rule::ExpressUnsupported,
+                            mode.position,
+                            Some(state),
+                            format!(
+                                "Map state \"{}\" is distributed, which Express workflows do not support",
+                                state.name
+                            ),
+                        );
+                    }
+                }
+
+                self.lint_graph(&map.processor, caught || !map.errors.catch.is_empty());
+            }
+        }
+    }
+
+    fn check_retrier(&mut self, state: &State, retrier: &Retrier) {
+        let max_attempts = retrier.max_attempts.unwrap_or(DEFAULT_MAX_ATTEMPTS);
+        let backoff_rate = retrier.backoff_rate.unwrap_or(DEFAULT_BACKOFF_RATE);
+        let interval_seconds = retrier.interval_seconds.unwrap_or(DEFAULT_INTERVAL_SECONDS);
+
+        if interval_seconds < MIN_INTERVAL_SECONDS {
+            self.report(
+                Rule::InvalidDefinition,
+                retrier.position,
+                Some(state),
+                format!(
+                    "retrier of {} waits {} seconds between retries, IntervalSeconds must be at least {}",
+                    retrier.error_equals.join(", "),
+                    interval_seconds,
+                    MIN_INTERVAL_SECONDS
+                ),
+            );
+        }
+
+        let message = if max_attempts > MAX_RETRY_ATTEMPTS {
+            format!(
+                "retrier of {} retries up to {} times, more than {}",
+                retrier.error_equals.join(", "),
+                max_attempts,
+                MAX_RETRY_ATTEMPTS
+            )
+        } else if interval_seconds < MIN_INTERVAL_SECONDS
+            && max_attempts > MAX_RETRY_ATTEMPTS_WITHOUT_BACKOFF
+        {
+            // Backing off a zero interval still retries right away.
+            format!(
+                "retrier of {} retries {} times without waiting, a tight retry loop",
+                retrier.error_equals.join(", "),
+                max_attempts
+            )
+        } else if backoff_rate <= 1.0 && max_attempts > MAX_RETRY_ATTEMPTS_WITHOUT_BACKOFF {
+            format!(
+                "retrier of {} retries {} times every {} seconds without backoff",
+                retrier.error_equals.join(", "),
+                max_attempts,
+                interval_seconds
+            )
+        } else {
+            return;
+        };
+
+        self.report(
+            Rule::AggressiveRetry,
+            retrier.position,
+            Some(state),
+            message,
+        );
+    }
+
+    fn check_express_resource(&mut self, state: &State, resource: &Located<String>) {
+        let unsupported = if resource.value.contains(":activity:") {
+            "activities"
+        } else if resource.value.ends_with(".waitForTaskToken") {
+            "the .waitForTaskToken integration pattern"
+        } else if resource.value.ends_with(".sync") || resource.value.contains(".sync:") {
+            "the .sync integration pattern"
+        } else {
+            return;
+        };
+
+        self.report(
+            Rule::ExpressUnsupported,
+            resource.position,
+            Some(state),
+            format!("Express workflows do not support {}", unsupported),
+        );
+    }
+
+    fn check_condition(&mut self, state: &State, condition: &Condition) {
+        match condition {
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                for condition in conditions {
+                    self.check_condition(state, condition);
+                }
+            }
+            Condition::Not(condition) => self.check_condition(state, condition),
+            Condition::Expression(_) => {}
+            Condition::Comparison(comparison) => {
+                self.check_path(
+                    state,
+                    "Variable",
+                    Some(&comparison.variable),
+                    PathKind::Path,
+                );
+                if let ComparisonValue::Path(path) = &comparison.value {
+                    let name = format!("{}Path", comparison.operator.as_str());
+                    self.check_path(state, &name, Some(path), PathKind::Path);
+                }
+            }
+        }
+    }
+
+    /// Path fields that can be null, as `InputPath` or `ResultPath`.
+    fn check_optional_path(
+        &mut self,
+        state: &State,
+        name: &str,
+        path: &Option<Located<Option<String>>>,
+        kind: PathKind,
+    ) {
+        if let Some(Located {
+            value: Some(value),
+            position,
+        }) = path
+        {
+            let path = Located {
+                value: value.clone(),
+                position: *position,
+            };
+            self.check_path(state, name, Some(&path), kind);
+        }
+    }
+
+    fn check_path(
+        &mut self,
+        state: &State,
+        name: &str,
+        path: Option<&Located<String>>,
+        kind: PathKind,
+    ) {
+        let Some(path) = path else {
+            return;
+        };
+
+        if let Err(error) = check_path(&path.value, kind) {
+            self.report(
+                Rule::InvalidPath,
+                path.position,
+                Some(state),
+                format!(
+                    "\"{}\" is not a valid JSONPath, {}: {}",
+                    name, error, path.value
+                ),
+            );
+        }
+    }
+
+    /// Value of a `.$` field or of `ErrorPath` and `CausePath`: a path or an intrinsic function.
+    fn check_expression(&mut self, state: &State, name: &str, expression: &Located<String>) {
+        let (rule, result) = if expression.value.starts_with("States.") {
+            (Rule::PayloadTemplate, check_intrinsic(&expression.value))
+        } else if expression.value.starts_with('$') {
+            (
+                Rule::InvalidPath,
+                check_path(&expression.value, PathKind::Path),
+            )
+        } else {
+            (
+                Rule::PayloadTemplate,
+                Err("expected a path or an intrinsic function".to_string()),
+            )
+        };
+
+        if let Err(error) = result {
+            self.report(
+                rule,
+                expression.position,
+                Some(state),
+                format!("\"{}\" is invalid, {}: {}", name, error, expression.value),
+            );
+        }
+    }
+
+    /// Checks the `.$` fields of a payload template, and the fields holding a path that were
+    /// probably meant to end with `.$`. JSONata templates use `{% %}` expressions instead, so
+    /// they are not checked.
+    fn check_template(&mut self, state: &State, template: Option<&JsonNode>) {
+        let Some(template) = template.filter(|_| state.query_language == QueryLanguage::JsonPath)
+        else {
+            return;
+        };
+
+        match &template.value {
+            JsonValue::Object(members) => {
+                for (key, value) in members {
+                    match (key.name.ends_with(".$"), &value.value) {
+                        (true, JsonValue::String(expression)) => self.check_expression(
+                            state,
+                            &key.name,
+                            &Located {
+                                value: expression.clone(),
+                                position: value.position,
+                            },
+                        ),
+                        (true, _) => self.report(
+                            Rule::PayloadTemplate,
+                            value.position,
+                            Some(state),
+                            format!(
+                                "\"{}\" must hold a path or an intrinsic function but holds {}",
+                                key.name,
+                                value.type_name()
+                            ),
+                        ),
+                        (false, JsonValue::String(text)) if looks_like_path(text) => self.report(
+                            Rule::LiteralPath,
+                            key.position,
+                            Some(state),
+                            format!(
+                                "\"{}\" is passed as the literal string \"{}\", name it \"{}.$\" to resolve the path",
+                                key.name, text, key.name
+                            ),
+                        ),
+                        (false, _) => self.check_template(state, Some(value)),
+                    }
+                }
+            }
+            JsonValue::Array(items) => {
+                for item in items {
+                    self.check_template(state, Some(item));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// `Result` of a Pass state is output as written, its `.$` fields are not resolved.
+    fn check_literal_result(&mut self, state: &State, result: &JsonNode) {
+        match &result.value {
+            JsonValue::Object(members) => {
+                for (key, value) in members {
+                    if key.name.ends_with(".$") {
+                        self.report(
+                            Rule::LiteralPath,
+                            key.position,
+                            Some(state),
+                            format!(
+                                "\"{}\" is not resolved in the Result of a Pass state, use Parameters instead",
+                                key.name
+                            ),
+                        );
+                    }
+                    self.check_literal_result(state, value);
+                }
+            }
+            JsonValue::Array(items) => {
+                for item in items {
+                    self.check_literal_result(state, item);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn looks_like_path(text: &str) -> bool {
+    text == "$"
+        || ["$.", "$$.", "$["]
+            .iter()
+            .any(|prefix| text.starts_with(prefix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(source: &str, express: bool) -> Vec<String> {
+        lint_source(source, express)
+            .iter()
+            .map(|finding| finding.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn should_not_report_a_clean_definition() {
+        let source = r#"{
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:states:::lambda:invoke",
+      "Parameters": {"FunctionName": "feed", "Payload.$": "States.JsonMerge($.meal, $$.Execution.Input, false)"},
+      "Retry": [{"ErrorEquals": ["States.TaskFailed"], "MaxAttempts": 5}],
+      "Catch": [{"ErrorEquals": ["States.ALL"], "ResultPath": "$.error", "Next": "Extinct"}],
+      "Next": "Full?"
+    },
+    "Full?": {
+      "Type": "Choice",
+      "Choices": [{"Variable": "$.hunger", "NumericGreaterThan": 5, "Next": "Nap"}],
+      "Default": "Roar"
+    },
+    "Nap": {"Type": "Wait", "Seconds": 60, "Next": "Feed"},
+    "Roar": {"Type": "Succeed"},
+    "Extinct": {"Type": "Fail", "ErrorPath": "$.error.Error"}
+  }
+}"#;
+
+        assert_eq!(findings(source, false), Vec::<String>::new());
+    }
+
+    #[test]
+    fn should_report_graph_problems() {
+        let source = r#"{
+  "StartAt": "Hungry?",
+  "States": {
+    "Hungry?": {
+      "Type": "Choice",
+      "Choices": [{"Variable": "$.hunger", "NumericGreaterThan": 5, "Next": "Hunt"}]
+    },
+    "Hunt": {"Type": "Pass", "Next": "Chase"},
+    "Chase": {"Type": "Pass", "Next": "Hungry?"},
+    "Roar": {"Type": "Pass", "Next": "Meteor"},
+    "Stomp": {"Type": "Pass"}
+  }
+}"#;
+
+        assert_eq!(
+            findings(source, false),
+            vec![
+                "4:5: warning: states \"Hungry?\", \"Hunt\", \"Chase\" loop without a Wait state [loop-without-wait]",
+                "4:5: warning: Choice state \"Hungry?\" has no Default, an input matching no rule fails the execution with States.NoChoiceMatched [choice-without-default]",
+                "10:5: error: state \"Roar\" can never be reached [unreachable-state]",
+                "10:38: error: Next names unknown state \"Meteor\" [unknown-state]",
+                "11:5: error: state \"Stomp\" can never be reached [unreachable-state]",
+                "11:5: error: Pass state \"Stomp\" has neither \"Next\" nor \"End\" [missing-transition]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_risky_error_handling() {
+        let source = r#"{
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:lambda:us-east-1:123456789012:function:feed",
+      "Retry": [
+        {"ErrorEquals": ["States.ALL"], "MaxAttempts": 50},
+        {"ErrorEquals": ["Lambda.TooManyRequestsException"], "MaxAttempts": 8, "BackoffRate": 1}
+      ],
+      "Next": "Herd"
+    },
+    "Herd": {
+      "Type": "Parallel",
+      "Branches": [{"StartAt": "Stomp", "States": {"Stomp": {"Type": "Task", "Resource": "arn", "End": true}}}],
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Next": "Extinct"}],
+      "End": true
+    },
+    "Extinct": {"Type": "Fail"}
+  }
+}"#;
+
+        assert_eq!(
+            findings(source, false),
+            vec![
+                "4:5: warning: Task state \"Feed\" has no Catch, an error left after its retries fails the execution [task-without-catch]",
+                "8:9: warning: retrier of States.ALL retries up to 50 times, more than 10 [aggressive-retry]",
+                "9:9: warning: retrier of Lambda.TooManyRequestsException retries 8 times every 1 seconds without backoff [aggressive-retry]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_retriers_that_do_not_wait() {
+        let source = r#"{
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:lambda:us-east-1:123456789012:function:feed",
+      "Retry": [
+        {"ErrorEquals": ["States.Timeout"], "IntervalSeconds": 0, "MaxAttempts": 2},
+        {"ErrorEquals": ["States.ALL"], "IntervalSeconds": 0, "MaxAttempts": 10}
+      ],
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Next": "Extinct"}],
+      "End": true
+    },
+    "Extinct": {"Type": "Fail"}
+  }
+}"#;
+
+        assert_eq!(
+            findings(source, false),
+            vec![
+                "8:9: error: retrier of States.Timeout waits 0 seconds between retries, IntervalSeconds must be at least 1 [invalid-definition]",
+                "9:9: error: retrier of States.ALL waits 0 seconds between retries, IntervalSeconds must be at least 1 [invalid-definition]",
+                "9:9: warning: retrier of States.ALL retries 10 times without waiting, a tight retry loop [aggressive-retry]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_invalid_paths_and_templates() {
+        let source = r#"{
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:states:::lambda:invoke",
+      "InputPath": "$.meal.",
+      "Parameters": {
+        "FunctionName": "feed",
+        "Payload": {"size": "$.meal.size", "name.$": "name", "teeth.$": 3, "roar.$": "States.Roar()"}
+      },
+      "ResultPath": "$.meals[*]",
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Next": "Meal"}],
+      "Next": "Meal"
+    },
+    "Meal": {"Type": "Pass", "Result": {"size.$": "$.size"}, "End": true}
+  }
+}"#;
+
+        assert_eq!(
+            findings(source, false),
+            vec![
+                "7:20: error: \"InputPath\" is not a valid JSONPath, missing field name after '.' at character 7: $.meal. [invalid-path]",
+                "10:21: warning: \"size\" is passed as the literal string \"$.meal.size\", name it \"size.$\" to resolve the path [literal-path]",
+                "10:54: error: \"name.$\" is invalid, expected a path or an intrinsic function: name [payload-template]",
+                "10:73: error: \"teeth.$\" must hold a path or an intrinsic function but holds a number [payload-template]",
+                "10:86: error: \"roar.$\" is invalid, unknown intrinsic function States.Roar: States.Roar() [payload-template]",
+                "12:21: error: \"ResultPath\" is not a valid JSONPath, subscript [*] at character 8 selects several nodes, which a reference path cannot: $.meals[*] [invalid-path]",
+                "16:41: warning: \"size.$\" is not resolved in the Result of a Pass state, use Parameters instead [literal-path]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_report_features_express_workflows_do_not_support() {
+        let source = r#"{
+  "StartAt": "Hatch",
+  "TimeoutSeconds": 600,
+  "States": {
+    "Hatch": {
+      "Type": "Task",
+      "Resource": "arn:aws:states:::ecs:runTask.sync",
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Next": "Nap"}],
+      "Next": "Nap"
+    },
+    "Nap": {"Type": "Wait", "Seconds": 3600, "Next": "Eggs"},
+    "Eggs": {
+      "Type": "Map",
+      "ItemProcessor": {
+        "ProcessorConfig": {"Mode": "DISTRIBUTED", "ExecutionType": "EXPRESS"},
+        "StartAt": "Egg",
+        "States": {"Egg": {"Type": "Succeed"}}
+      },
+      "End": true
+    }
+  }
+}"#;
+
+        assert_eq!(findings(source, false), Vec::<String>::new());
+        assert_eq!(
+            findings(source, true),
+            vec![
+                "1:1: error: TimeoutSeconds is 600 but Express executions run for at most 300 seconds [express-unsupported]",
+                "7:19: error: Express workflows do not support the .sync integration pattern [express-unsupported]",
+                "11:5: error: Wait state \"Nap\" waits 3600 seconds but Express executions run for at most 300 seconds [express-unsupported]",
+                "15:37: error: Map state \"Eggs\" is distributed, which Express workflows do not support [express-unsupported]",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_warn_about_unknown_fields_without_checking_jsonata_templates() {
+        let source = r#"{
+  "QueryLanguage": "JSONata",
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {"Type": "Pass", "Assign": {"meal": "$.meal"}, "Output": {"fed": true}, "Next": "Roar"},
+    "Roar": {"Type": "Succeed", "Roar": true}
+  }
+}"#;
+
+        assert_eq!(
+            findings(source, false),
+            vec!["6:33: warning: unknown field \"Roar\" in state \"Roar\" [unknown-field]"]
+        );
+    }
+
+    #[test]
+    fn should_report_a_definition_that_does_not_parse() {
+        let findings = lint_source("{\"StartAt\": \"Roar\"}", false);
+
+        assert_eq!(
+            findings,
+            vec![Finding {
+                severity: Severity::Error,
+                rule: Rule::InvalidDefinition,
+                position: Position { line: 1, column: 1 },
+                state: None,
+                message: "the definition is missing the \"States\" field".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,48 @@
+pub mod diagram;
+pub mod json;
+pub mod lint;
+pub mod model;
+mod parser;
+mod path;
+
+use serde::Serialize;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+pub use parser::parse_definition;
+
+/// Line and column of a definition, both starting at 1.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for Position {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+/// Definition that is not valid JSON or not a valid Amazon States Language document.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AslError {
+    pub position: Position,
+    pub message: String,
+}
+
+impl AslError {
+    pub fn new(position: Position, message: &str) -> Self {
+        AslError {
+            position,
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for AslError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}: {}", self.position, self.message)
+    }
+}
+
+impl std::error::Error for AslError {}
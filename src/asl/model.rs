@@ -0,0 +1,504 @@
+use crate::asl::json::JsonNode;
+use crate::asl::Position;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+/// Parsed Amazon States Language definition of a state machine.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Definition {
+    pub comment: Option<String>,
+    pub version: Option<String>,
+    pub timeout_seconds: Option<u64>,
+    pub query_language: QueryLanguage,
+    pub graph: StateGraph,
+    /// Fields the parser does not know, in the order they are written.
+    pub unknown_fields: Vec<UnknownField>,
+}
+
+/// Language of the paths and expressions of a state, JSONPath unless the definition or the
+/// state says otherwise.
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum QueryLanguage {
+    #[default]
+    JsonPath,
+    Jsonata,
+}
+
+/// Field the parser does not know, left out of the model so the linter can warn about it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct UnknownField {
+    pub name: String,
+    /// Where the field is, ex. `state "Roar"` or `retrier`.
+    pub context: String,
+    pub position: Position,
+}
+
+/// States run from `start_at`, the top level of a definition, a Parallel branch or the
+/// processor of a Map state.
+#[derive(Clone, PartialEq, Debug)]
+pub struct StateGraph {
+    pub start_at: Located<String>,
+    /// States in the order they were written.
+    pub states: Vec<State>,
+    pub position: Position,
+}
+
+impl StateGraph {
+    pub fn state(&self, name: &str) -> Option<&State> {
+        self.states.iter().find(|state| state.name == name)
+    }
+}
+
+/// Value of a field, with where it is written in the definition.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Located<T> {
+    pub value: T,
+    pub position: Position,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct State {
+    pub name: String,
+    pub position: Position,
+    pub comment: Option<String>,
+    pub query_language: QueryLanguage,
+    /// `None` for the Choice, Succeed and Fail states, which have no Next or End field, and for
+    /// states that are missing both.
+    pub transition: Option<Located<Transition>>,
+    pub input_path: Option<Located<Option<String>>>,
+    pub output_path: Option<Located<Option<String>>>,
+    /// Variables assigned when the state completes.
+    pub assign: Option<JsonNode>,
+    /// Template of the output of a JSONata state.
+    pub output: Option<JsonNode>,
+    pub kind: StateKind,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Transition {
+    Next(String),
+    End,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum EdgeKind {
+    Next,
+    Choice,
+    Default,
+    Catch,
+}
+
+impl EdgeKind {
+    /// Field of the definition the edge comes from.
+    pub fn field(&self) -> &'static str {
+        match self {
+            EdgeKind::Next | EdgeKind::Choice => "Next",
+            EdgeKind::Default => "Default",
+            EdgeKind::Catch => "Catch",
+        }
+    }
+}
+
+/// Transition from a state to another state of the same graph.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Edge<'a> {
+    pub target: &'a str,
+    pub kind: EdgeKind,
+    pub position: Position,
+}
+
+impl State {
+    /// Every state this state can move to, in the order they are written.
+    pub fn edges(&self) -> Vec<Edge<'_>> {
+        let mut edges = vec![];
+
+        if let Some(Located {
+            value: Transition::Next(target),
+            position,
+        }) = &self.transition
+        {
+            edges.push(Edge {
+                target,
+                kind: EdgeKind::Next,
+                position: *position,
+            });
+        }
+        if let StateKind::Choice(choice) = &self.kind {
+            for rule in &choice.choices {
+                edges.push(Edge {
+                    target: &rule.next.value,
+                    kind: EdgeKind::Choice,
+                    position: rule.next.position,
+                });
+            }
+            if let Some(default) = &choice.default {
+                edges.push(Edge {
+                    target: &default.value,
+                    kind: EdgeKind::Default,
+                    position: default.position,
+                });
+            }
+        }
+        if let Some(errors) = self.kind.error_handling() {
+            for catcher in &errors.catch {
+                edges.push(Edge {
+                    target: &catcher.next.value,
+                    kind: EdgeKind::Catch,
+                    position: catcher.next.position,
+                });
+            }
+        }
+
+        edges
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum StateKind {
+    Task(TaskState),
+    Pass(PassState),
+    Choice(ChoiceState),
+    Wait(WaitState),
+    Succeed,
+    Fail(FailState),
+    Parallel(ParallelState),
+    Map(Box<MapState>),
+}
+
+impl StateKind {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            StateKind::Task(_) => "Task",
+            StateKind::Pass(_) => "Pass",
+            StateKind::Choice(_) => "Choice",
+            StateKind::Wait(_) => "Wait",
+            StateKind::Succeed => "Succeed",
+            StateKind::Fail(_) => "Fail",
+            StateKind::Parallel(_) => "Parallel",
+            StateKind::Map(_) => "Map",
+        }
+    }
+
+    /// Retry and Catch of the states that have them.
+    pub fn error_handling(&self) -> Option<&ErrorHandling> {
+        match self {
+            StateKind::Task(task) => Some(&task.errors),
+            StateKind::Parallel(parallel) => Some(&parallel.errors),
+            StateKind::Map(map) => Some(&map.errors),
+            _ => None,
+        }
+    }
+}
+
+/// Fields shared by the states that pass their result on: Task, Pass, Parallel and Map.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ResultHandling {
+    /// `Some(None)` when the result is discarded with `"ResultPath": null`.
+    pub result_path: Option<Located<Option<String>>>,
+    /// Payload template of the input, `ItemSelector` for Map states.
+    pub parameters: Option<JsonNode>,
+    /// Payload template of the result, not available on Pass states.
+    pub result_selector: Option<JsonNode>,
+    /// Template of the input of a JSONata state, which replaces `Parameters`.
+    pub arguments: Option<JsonNode>,
+}
+
+/// Error handling of the Task, Parallel and Map states.
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ErrorHandling {
+    pub retry: Vec<Retrier>,
+    pub catch: Vec<Catcher>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TaskState {
+    pub resource: Located<String>,
+    pub result: ResultHandling,
+    pub errors: ErrorHandling,
+    pub timeout_seconds: Option<u64>,
+    pub timeout_seconds_path: Option<Located<String>>,
+    pub heartbeat_seconds: Option<u64>,
+    pub heartbeat_seconds_path: Option<Located<String>>,
+    pub credentials: Option<JsonNode>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PassState {
+    pub result: ResultHandling,
+    /// Fixed output of the state, `Result` in the definition.
+    pub output: Option<JsonNode>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChoiceState {
+    pub choices: Vec<ChoiceRule>,
+    pub default: Option<Located<String>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChoiceRule {
+    pub condition: Condition,
+    pub next: Located<String>,
+    pub assign: Option<JsonNode>,
+    pub position: Position,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum Condition {
+    And(Vec<Condition>),
+    Or(Vec<Condition>),
+    Not(Box<Condition>),
+    Comparison(Comparison),
+    /// `Condition` of a JSONata choice rule, ex. `{% $states.input.age >= 18 %}`.
+    Expression(Located<String>),
+}
+
+impl Display for Condition {
+    /// Short form of the condition, ex. `$.age >= 18 and not ($.name == "rex")`.
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let operand = |condition: &Condition| match condition {
+            Condition::Comparison(_) | Condition::Not(_) | Condition::Expression(_) => {
+                condition.to_string()
+            }
+            _ => format!("({})", condition),
+        };
+
+        match self {
+            Condition::And(conditions) | Condition::Or(conditions) => {
+                let separator = match self {
+                    Condition::And(_) => " and ",
+                    _ => " or ",
+                };
+                let operands: Vec<String> = conditions.iter().map(operand).collect();
+                write!(f, "{}", operands.join(separator))
+            }
+            Condition::Not(condition) => write!(f, "not ({})", condition),
+            Condition::Comparison(comparison) => write!(f, "{}", comparison),
+            Condition::Expression(expression) => write!(f, "{}", expression.value),
+        }
+    }
+}
+
+impl Display for Comparison {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let variable = &self.variable.value;
+        let symbol = match self.operator {
+            ComparisonOperator::StringEquals
+            | ComparisonOperator::NumericEquals
+            | ComparisonOperator::BooleanEquals
+            | ComparisonOperator::TimestampEquals => "==",
+            ComparisonOperator::StringLessThan
+            | ComparisonOperator::NumericLessThan
+            | ComparisonOperator::TimestampLessThan => "<",
+            ComparisonOperator::StringGreaterThan
+            | ComparisonOperator::NumericGreaterThan
+            | ComparisonOperator::TimestampGreaterThan => ">",
+            ComparisonOperator::StringLessThanEquals
+            | ComparisonOperator::NumericLessThanEquals
+            | ComparisonOperator::TimestampLessThanEquals => "<=",
+            ComparisonOperator::StringGreaterThanEquals
+            | ComparisonOperator::NumericGreaterThanEquals
+            | ComparisonOperator::TimestampGreaterThanEquals => ">=",
+            ComparisonOperator::StringMatches => "matches",
+            type_test => {
+                let tested = match type_test {
+                    ComparisonOperator::IsNull => "null",
+                    ComparisonOperator::IsPresent => "present",
+                    ComparisonOperator::IsNumeric => "numeric",
+                    ComparisonOperator::IsString => "a string",
+                    ComparisonOperator::IsBoolean => "a boolean",
+                    _ => "a timestamp",
+                };
+                let negation = match self.value {
+                    ComparisonValue::Bool(false) => "not ",
+                    _ => "",
+                };
+                return write!(f, "{} is {}{}", variable, negation, tested);
+            }
+        };
+
+        match &self.value {
+            ComparisonValue::String(value) => write!(f, "{} {} \"{}\"", variable, symbol, value),
+            ComparisonValue::Number(value) => write!(f, "{} {} {}", variable, symbol, value),
+            ComparisonValue::Bool(value) => write!(f, "{} {} {}", variable, symbol, value),
+            ComparisonValue::Path(path) => write!(f, "{} {} {}", variable, symbol, path.value),
+        }
+    }
+}
+
+/// Test of a variable of the input, ex. `"Variable": "$.age", "NumericGreaterThan": 18`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Comparison {
+    pub variable: Located<String>,
+    pub operator: ComparisonOperator,
+    pub value: ComparisonValue,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ComparisonOperator {
+    StringEquals,
+    StringLessThan,
+    StringGreaterThan,
+    StringLessThanEquals,
+    StringGreaterThanEquals,
+    StringMatches,
+    NumericEquals,
+    NumericLessThan,
+    NumericGreaterThan,
+    NumericLessThanEquals,
+    NumericGreaterThanEquals,
+    BooleanEquals,
+    TimestampEquals,
+    TimestampLessThan,
+    TimestampGreaterThan,
+    TimestampLessThanEquals,
+    TimestampGreaterThanEquals,
+    IsNull,
+    IsPresent,
+    IsNumeric,
+    IsString,
+    IsBoolean,
+    IsTimestamp,
+}
+
+impl ComparisonOperator {
+    pub const ALL: [ComparisonOperator; 23] = [
+        ComparisonOperator::StringEquals,
+        ComparisonOperator::StringLessThan,
+        ComparisonOperator::StringGreaterThan,
+        ComparisonOperator::StringLessThanEquals,
+        ComparisonOperator::StringGreaterThanEquals,
+        ComparisonOperator::StringMatches,
+        ComparisonOperator::NumericEquals,
+        ComparisonOperator::NumericLessThan,
+        ComparisonOperator::NumericGreaterThan,
+        ComparisonOperator::NumericLessThanEquals,
+        ComparisonOperator::NumericGreaterThanEquals,
+        ComparisonOperator::BooleanEquals,
+        ComparisonOperator::TimestampEquals,
+        ComparisonOperator::TimestampLessThan,
+        ComparisonOperator::TimestampGreaterThan,
+        ComparisonOperator::TimestampLessThanEquals,
+        ComparisonOperator::TimestampGreaterThanEquals,
+        ComparisonOperator::IsNull,
+        ComparisonOperator::IsPresent,
+        ComparisonOperator::IsNumeric,
+        ComparisonOperator::IsString,
+        ComparisonOperator::IsBoolean,
+        ComparisonOperator::IsTimestamp,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComparisonOperator::StringEquals => "StringEquals",
+            ComparisonOperator::StringLessThan => "StringLessThan",
+            ComparisonOperator::StringGreaterThan => "StringGreaterThan",
+            ComparisonOperator::StringLessThanEquals => "StringLessThanEquals",
+            ComparisonOperator::StringGreaterThanEquals => "StringGreaterThanEquals",
+            ComparisonOperator::StringMatches => "StringMatches",
+            ComparisonOperator::NumericEquals => "NumericEquals",
+            ComparisonOperator::NumericLessThan => "NumericLessThan",
+            ComparisonOperator::NumericGreaterThan => "NumericGreaterThan",
+            ComparisonOperator::NumericLessThanEquals => "NumericLessThanEquals",
+            ComparisonOperator::NumericGreaterThanEquals => "NumericGreaterThanEquals",
+            ComparisonOperator::BooleanEquals => "BooleanEquals",
+            ComparisonOperator::TimestampEquals => "TimestampEquals",
+            ComparisonOperator::TimestampLessThan => "TimestampLessThan",
+            ComparisonOperator::TimestampGreaterThan => "TimestampGreaterThan",
+            ComparisonOperator::TimestampLessThanEquals => "TimestampLessThanEquals",
+            ComparisonOperator::TimestampGreaterThanEquals => "TimestampGreaterThanEquals",
+            ComparisonOperator::IsNull => "IsNull",
+            ComparisonOperator::IsPresent => "IsPresent",
+            ComparisonOperator::IsNumeric => "IsNumeric",
+            ComparisonOperator::IsString => "IsString",
+            ComparisonOperator::IsBoolean => "IsBoolean",
+            ComparisonOperator::IsTimestamp => "IsTimestamp",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum ComparisonValue {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    /// Compared to another variable, with the `Path` variant of the operator
+    /// (ex. `NumericEqualsPath`).
+    Path(Located<String>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct WaitState {
+    pub wait: WaitDuration,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum WaitDuration {
+    Seconds(u64),
+    Timestamp(String),
+    SecondsPath(Located<String>),
+    TimestampPath(Located<String>),
+    /// JSONata expression of `Seconds` or `Timestamp`, evaluated when the state runs.
+    Expression(Located<String>),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct FailState {
+    pub error: Option<String>,
+    pub cause: Option<String>,
+    pub error_path: Option<Located<String>>,
+    pub cause_path: Option<Located<String>>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct ParallelState {
+    pub branches: Vec<StateGraph>,
+    pub result: ResultHandling,
+    pub errors: ErrorHandling,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MapState {
+    /// States run for every item, `ItemProcessor` or the older `Iterator`.
+    pub processor: StateGraph,
+    /// INLINE or DISTRIBUTED, inline when the definition does not say.
+    pub mode: Option<Located<String>>,
+    /// STANDARD or EXPRESS, the type of the child executions of a distributed Map.
+    pub execution_type: Option<Located<String>>,
+    pub items_path: Option<Located<String>>,
+    /// Items of a JSONata Map state, an array or an expression, which replaces `ItemsPath`.
+    pub items: Option<JsonNode>,
+    pub max_concurrency: Option<u64>,
+    pub max_concurrency_path: Option<Located<String>>,
+    pub item_reader: Option<JsonNode>,
+    pub item_batcher: Option<JsonNode>,
+    pub result_writer: Option<JsonNode>,
+    pub tolerated_failure_count: Option<u64>,
+    pub tolerated_failure_count_path: Option<Located<String>>,
+    pub tolerated_failure_percentage: Option<f64>,
+    pub tolerated_failure_percentage_path: Option<Located<String>>,
+    pub label: Option<String>,
+    pub result: ResultHandling,
+    pub errors: ErrorHandling,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Retrier {
+    pub error_equals: Vec<String>,
+    pub interval_seconds: Option<u64>,
+    pub max_attempts: Option<u64>,
+    pub backoff_rate: Option<f64>,
+    pub max_delay_seconds: Option<u64>,
+    pub jitter_strategy: Option<String>,
+    pub position: Position,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Catcher {
+    pub error_equals: Vec<String>,
+    pub next: Located<String>,
+    pub result_path: Option<Located<Option<String>>>,
+    pub assign: Option<JsonNode>,
+    /// Template of the output of a JSONata catcher, which replaces `ResultPath`.
+    pub output: Option<JsonNode>,
+    pub position: Position,
+}
@@ -0,0 +1,1410 @@
+use crate::asl::json::{self, JsonKey, JsonNode, JsonValue};
+use crate::asl::model::{
+    Catcher, ChoiceRule, ChoiceState, Comparison, ComparisonOperator, ComparisonValue, Condition,
+    Definition, ErrorHandling, FailState, Located, MapState, ParallelState, PassState,
+    QueryLanguage, ResultHandling, Retrier, State, StateGraph, StateKind, TaskState, Transition,
+    UnknownField, WaitDuration, WaitState,
+};
+use crate::asl::{AslError, Position};
+use chrono::DateTime;
+use std::cell::RefCell;
+
+/// Parses an Amazon States Language definition written with JSONPath or JSONata.
+///
+/// Only the structure of the definition is checked: fields must have the right type and
+/// required fields must be there, but the targets of transitions and the paths are not
+/// resolved, which is left to the linter. Unknown fields are kept in the definition so the
+/// linter can warn about them.
+pub fn parse_definition(source: &str) -> Result<Definition, AslError> {
+    let root = json::parse(source)?;
+    let unknown_fields = RefCell::new(vec![]);
+    let mut reader = ObjectReader::new(&root, "the definition", &unknown_fields)?;
+
+    parse_query_language(&mut reader)?;
+    let comment = reader.string("Comment")?.map(|comment| comment.value);
+    let version = reader.string("Version")?.map(|version| version.value);
+    let timeout_seconds = reader.unsigned("TimeoutSeconds")?;
+    let graph = parse_graph(&mut reader)?;
+    let query_language = reader.query_language;
+    reader.finish();
+
+    Ok(Definition {
+        comment,
+        version,
+        timeout_seconds,
+        query_language,
+        graph,
+        unknown_fields: unknown_fields.into_inner(),
+    })
+}
+
+/// Fields of a JSON object, read one by one so the fields left unread at the end can be
+/// reported as unknown.
+struct ObjectReader<'a> {
+    members: &'a [(JsonKey, JsonNode)],
+    read: Vec<bool>,
+    position: Position,
+    context: String,
+    /// Language of the object, inherited from the object it is in unless it has its own
+    /// `QueryLanguage`.
+    query_language: QueryLanguage,
+    unknown_fields: &'a RefCell<Vec<UnknownField>>,
+}
+
+impl<'a> ObjectReader<'a> {
+    fn new(
+        node: &'a JsonNode,
+        context: &str,
+        unknown_fields: &'a RefCell<Vec<UnknownField>>,
+    ) -> Result<Self, AslError> {
+        match &node.value {
+            JsonValue::Object(members) => Ok(ObjectReader {
+                members,
+                read: vec![false; members.len()],
+                position: node.position,
+                context: context.to_string(),
+                query_language: QueryLanguage::default(),
+                unknown_fields,
+            }),
+            _ => Err(AslError::new(
+                node.position,
+                &format!("{} must be an object but is {}", context, describe(node)),
+            )),
+        }
+    }
+
+    /// Reader of an object inside this one, in the same language.
+    fn object(&self, node: &'a JsonNode, context: &str) -> Result<Self, AslError> {
+        let mut reader = ObjectReader::new(node, context, self.unknown_fields)?;
+        reader.query_language = self.query_language;
+
+        Ok(reader)
+    }
+
+    fn is_jsonata(&self) -> bool {
+        self.query_language == QueryLanguage::Jsonata
+    }
+
+    /// JSONata expression given instead of a value, as `"Seconds": "{% $states.input.delay %}"`.
+    fn expression(&self, node: &JsonNode) -> bool {
+        match &node.value {
+            JsonValue::String(value) => self.is_jsonata() && is_expression(value),
+            _ => false,
+        }
+    }
+
+    fn field(&mut self, name: &str) -> Option<&'a JsonNode> {
+        let index = self.members.iter().position(|(key, _)| key.name == name)?;
+        self.read[index] = true;
+
+        Some(&self.members[index].1)
+    }
+
+    fn required(&mut self, name: &str) -> Result<&'a JsonNode, AslError> {
+        self.field(name).ok_or_else(|| {
+            AslError::new(
+                self.position,
+                &format!("{} is missing the \"{}\" field", self.context, name),
+            )
+        })
+    }
+
+    fn string(&mut self, name: &str) -> Result<Option<Located<String>>, AslError> {
+        self.field(name)
+            .map(|node| string_value(name, node))
+            .transpose()
+    }
+
+    fn required_string(&mut self, name: &str) -> Result<Located<String>, AslError> {
+        string_value(name, self.required(name)?)
+    }
+
+    /// Path field that can be set to null to discard the data, as `InputPath` or `ResultPath`.
+    fn path(&mut self, name: &str) -> Result<Option<Located<Option<String>>>, AslError> {
+        self.field(name)
+            .map(|node| match &node.value {
+                JsonValue::Null => Ok(Located {
+                    value: None,
+                    position: node.position,
+                }),
+                _ => string_value(name, node).map(|path| Located {
+                    value: Some(path.value),
+                    position: path.position,
+                }),
+            })
+            .transpose()
+    }
+
+    /// Number field, `None` when a JSONata expression gives the number when the state runs.
+    fn unsigned(&mut self, name: &str) -> Result<Option<u64>, AslError> {
+        self.field(name)
+            .filter(|node| !self.expression(node))
+            .map(|node| unsigned_value(name, node))
+            .transpose()
+    }
+
+    fn float(&mut self, name: &str) -> Result<Option<f64>, AslError> {
+        self.field(name)
+            .filter(|node| !self.expression(node))
+            .map(|node| float_value(name, node))
+            .transpose()
+    }
+
+    fn boolean(&mut self, name: &str) -> Result<Option<Located<bool>>, AslError> {
+        self.field(name)
+            .map(|node| match node.value {
+                JsonValue::Bool(value) => Ok(Located {
+                    value,
+                    position: node.position,
+                }),
+                _ => Err(unexpected(name, node, "a boolean")),
+            })
+            .transpose()
+    }
+
+    fn array(&mut self, name: &str) -> Result<Option<&'a [JsonNode]>, AslError> {
+        self.field(name)
+            .map(|node| array_value(name, node))
+            .transpose()
+    }
+
+    /// Records the fields that were not read, which Step Functions may not accept, for the
+    /// linter to report.
+    fn finish(self) {
+        let unknown = self
+            .members
+            .iter()
+            .zip(&self.read)
+            .filter(|(_, read)| !**read)
+            .map(|((key, _), _)| UnknownField {
+                name: key.name.clone(),
+                context: self.context.clone(),
+                position: key.position,
+            });
+
+        self.unknown_fields.borrow_mut().extend(unknown);
+    }
+}
+
+fn is_expression(value: &str) -> bool {
+    let value = value.trim();
+    value.starts_with("{%") && value.ends_with("%}")
+}
+
+/// Type of a node for error messages, or the number itself for numbers out of range.
+fn describe(node: &JsonNode) -> String {
+    match &node.value {
+        JsonValue::Number(number) => number.to_string(),
+        _ => node.type_name().to_string(),
+    }
+}
+
+fn unexpected(name: &str, node: &JsonNode, expected: &str) -> AslError {
+    AslError::new(
+        node.position,
+        &format!(
+            "\"{}\" must be {} but is {}",
+            name,
+            expected,
+            describe(node)
+        ),
+    )
+}
+
+fn string_value(name: &str, node: &JsonNode) -> Result<Located<String>, AslError> {
+    match &node.value {
+        JsonValue::String(value) => Ok(Located {
+            value: value.clone(),
+            position: node.position,
+        }),
+        _ => Err(unexpected(name, node, "a string")),
+    }
+}
+
+fn unsigned_value(name: &str, node: &JsonNode) -> Result<u64, AslError> {
+    match &node.value {
+        JsonValue::Number(number) => number.as_u64(),
+        _ => None,
+    }
+    .ok_or_else(|| unexpected(name, node, "a non-negative integer"))
+}
+
+fn float_value(name: &str, node: &JsonNode) -> Result<f64, AslError> {
+    match &node.value {
+        JsonValue::Number(number) => number.as_f64(),
+        _ => None,
+    }
+    .ok_or_else(|| unexpected(name, node, "a number"))
+}
+
+fn array_value<'a>(name: &str, node: &'a JsonNode) -> Result<&'a [JsonNode], AslError> {
+    match &node.value {
+        JsonValue::Array(items) => Ok(items),
+        _ => Err(unexpected(name, node, "an array")),
+    }
+}
+
+fn non_empty_array<'a>(name: &str, node: &'a JsonNode) -> Result<&'a [JsonNode], AslError> {
+    let items = array_value(name, node)?;
+    if items.is_empty() {
+        return Err(AslError::new(
+            node.position,
+            &format!("\"{}\" must not be empty", name),
+        ));
+    }
+
+    Ok(items)
+}
+
+/// String field restricted to a few values, as `JitterStrategy` or `Mode`.
+fn one_of(
+    name: &str,
+    value: Option<Located<String>>,
+    allowed: &[&str],
+) -> Result<Option<Located<String>>, AslError> {
+    match value {
+        Some(value) if !allowed.contains(&value.value.as_str()) => Err(AslError::new(
+            value.position,
+            &format!(
+                "\"{}\" must be one of {} but is \"{}\"",
+                name,
+                allowed.join(", "),
+                value.value
+            ),
+        )),
+        value => Ok(value),
+    }
+}
+
+/// Switches the reader to the `QueryLanguage` of the object, when it has one.
+fn parse_query_language(reader: &mut ObjectReader) -> Result<(), AslError> {
+    match one_of(
+        "QueryLanguage",
+        reader.string("QueryLanguage")?,
+        &["JSONPath", "JSONata"],
+    )? {
+        Some(language) if language.value == "JSONata" => {
+            reader.query_language = QueryLanguage::Jsonata
+        }
+        Some(_) => reader.query_language = QueryLanguage::JsonPath,
+        None => {}
+    }
+
+    Ok(())
+}
+
+/// Two fields that cannot be given together, as `TimeoutSeconds` and `TimeoutSecondsPath`.
+fn check_exclusive(reader: &mut ObjectReader, first: &str, second: &str) -> Result<(), AslError> {
+    match (reader.field(first), reader.field(second)) {
+        (Some(_), Some(node)) => Err(AslError::new(
+            node.position,
+            &format!(
+                "{} cannot have both \"{}\" and \"{}\"",
+                reader.context, first, second
+            ),
+        )),
+        _ => Ok(()),
+    }
+}
+
+fn parse_graph(reader: &mut ObjectReader) -> Result<StateGraph, AslError> {
+    let start_at = reader.required_string("StartAt")?;
+    let states_node = reader.required("States")?;
+    let JsonValue::Object(members) = &states_node.value else {
+        return Err(unexpected("States", states_node, "an object"));
+    };
+    if members.is_empty() {
+        return Err(AslError::new(
+            states_node.position,
+            "\"States\" must have at least one state",
+        ));
+    }
+
+    let states = members
+        .iter()
+        .map(|(key, node)| parse_state(reader, key, node))
+        .collect::<Result<Vec<State>, AslError>>()?;
+
+    Ok(StateGraph {
+        start_at,
+        states,
+        position: reader.position,
+    })
+}
+
+/// States of a Parallel branch or of the `Iterator` of a Map state.
+fn parse_nested_graph<'a>(
+    parent: &ObjectReader<'a>,
+    node: &'a JsonNode,
+    context: &str,
+) -> Result<StateGraph, AslError> {
+    let mut reader = parent.object(node, context)?;
+    reader.string("Comment")?;
+    let graph = parse_graph(&mut reader)?;
+    reader.finish();
+
+    Ok(graph)
+}
+
+fn parse_state<'a>(
+    parent: &ObjectReader<'a>,
+    key: &JsonKey,
+    node: &'a JsonNode,
+) -> Result<State, AslError> {
+    let mut reader = parent.object(node, &format!("state \"{}\"", key.name))?;
+
+    let state_type = reader.required_string("Type")?;
+    parse_query_language(&mut reader)?;
+    let comment = reader.string("Comment")?.map(|comment| comment.value);
+
+    let kind = match state_type.value.as_str() {
+        "Task" => StateKind::Task(parse_task(&mut reader)?),
+        "Pass" => StateKind::Pass(parse_pass(&mut reader)?),
+        "Choice" => StateKind::Choice(parse_choice(&mut reader)?),
+        "Wait" => StateKind::Wait(parse_wait(&mut reader)?),
+        "Succeed" => StateKind::Succeed,
+        "Fail" => StateKind::Fail(parse_fail(&mut reader)?),
+        "Parallel" => StateKind::Parallel(parse_parallel(&mut reader)?),
+        "Map" => StateKind::Map(Box::new(parse_map(&mut reader)?)),
+        other => {
+            return Err(AslError::new(
+                state_type.position,
+                &format!("unknown state type \"{}\"", other),
+            ))
+        }
+    };
+
+    let (input_path, output_path, output) = match kind {
+        StateKind::Fail(_) => (None, None, None),
+        _ if reader.is_jsonata() => (None, None, reader.field("Output").cloned()),
+        _ => (reader.path("InputPath")?, reader.path("OutputPath")?, None),
+    };
+    let transition = match kind {
+        StateKind::Choice(_) | StateKind::Succeed | StateKind::Fail(_) => None,
+        _ => parse_transition(&mut reader)?,
+    };
+    let assign = match kind {
+        StateKind::Succeed | StateKind::Fail(_) => None,
+        _ => reader.field("Assign").cloned(),
+    };
+    let query_language = reader.query_language;
+    reader.finish();
+
+    Ok(State {
+        name: key.name.clone(),
+        position: key.position,
+        comment,
+        query_language,
+        transition,
+        input_path,
+        output_path,
+        assign,
+        output,
+        kind,
+    })
+}
+
+/// `Next` or `End`, `None` when both are missing so the linter can report it.
+fn parse_transition(reader: &mut ObjectReader) -> Result<Option<Located<Transition>>, AslError> {
+    let next = reader.string("Next")?;
+    let end = reader.boolean("End")?;
+
+    match (next, end) {
+        (Some(_), Some(end)) if end.value => Err(AslError::new(
+            end.position,
+            &format!("{} cannot have both \"Next\" and \"End\"", reader.context),
+        )),
+        (Some(next), _) => Ok(Some(Located {
+            value: Transition::Next(next.value),
+            position: next.position,
+        })),
+        (None, Some(end)) if end.value => Ok(Some(Located {
+            value: Transition::End,
+            position: end.position,
+        })),
+        (None, _) => Ok(None),
+    }
+}
+
+/// `Arguments` in JSONata states, the JSONPath fields that shape the input and result otherwise.
+fn parse_result_handling(reader: &mut ObjectReader) -> Result<ResultHandling, AslError> {
+    if reader.is_jsonata() {
+        return Ok(ResultHandling {
+            arguments: reader.field("Arguments").cloned(),
+            ..ResultHandling::default()
+        });
+    }
+
+    Ok(ResultHandling {
+        result_path: reader.path("ResultPath")?,
+        parameters: reader.field("Parameters").cloned(),
+        result_selector: reader.field("ResultSelector").cloned(),
+        arguments: None,
+    })
+}
+
+fn parse_error_handling(reader: &mut ObjectReader) -> Result<ErrorHandling, AslError> {
+    let retry = match reader.array("Retry")? {
+        Some(retriers) => retriers
+            .iter()
+            .map(|retrier| parse_retrier(reader, retrier))
+            .collect::<Result<Vec<Retrier>, AslError>>()?,
+        None => vec![],
+    };
+    let catch = match reader.array("Catch")? {
+        Some(catchers) => catchers
+            .iter()
+            .map(|catcher| parse_catcher(reader, catcher))
+            .collect::<Result<Vec<Catcher>, AslError>>()?,
+        None => vec![],
+    };
+
+    Ok(ErrorHandling { retry, catch })
+}
+
+fn parse_error_equals(reader: &mut ObjectReader) -> Result<Vec<String>, AslError> {
+    non_empty_array("ErrorEquals", reader.required("ErrorEquals")?)?
+        .iter()
+        .map(|node| string_value("ErrorEquals", node).map(|error| error.value))
+        .collect()
+}
+
+fn parse_retrier<'a>(parent: &ObjectReader<'a>, node: &'a JsonNode) -> Result<Retrier, AslError> {
+    let mut reader = parent.object(node, "retrier")?;
+
+    reader.string("Comment")?;
+    let error_equals = parse_error_equals(&mut reader)?;
+    let interval_seconds = reader.unsigned("IntervalSeconds")?;
+    let max_attempts = reader.unsigned("MaxAttempts")?;
+    let backoff_rate = match reader.field("BackoffRate") {
+        Some(node) => match float_value("BackoffRate", node)? {
+            rate if rate < 1.0 => return Err(unexpected("BackoffRate", node, "at least 1.0")),
+            rate => Some(rate),
+        },
+        None => None,
+    };
+    let max_delay_seconds = reader.unsigned("MaxDelaySeconds")?;
+    let jitter_strategy = one_of(
+        "JitterStrategy",
+        reader.string("JitterStrategy")?,
+        &["FULL", "NONE"],
+    )?
+    .map(|strategy| strategy.value);
+    reader.finish();
+
+    Ok(Retrier {
+        error_equals,
+        interval_seconds,
+        max_attempts,
+        backoff_rate,
+        max_delay_seconds,
+        jitter_strategy,
+        position: node.position,
+    })
+}
+
+fn parse_catcher<'a>(parent: &ObjectReader<'a>, node: &'a JsonNode) -> Result<Catcher, AslError> {
+    let mut reader = parent.object(node, "catcher")?;
+
+    reader.string("Comment")?;
+    let error_equals = parse_error_equals(&mut reader)?;
+    let next = reader.required_string("Next")?;
+    let (result_path, output) = match reader.is_jsonata() {
+        true => (None, reader.field("Output").cloned()),
+        false => (reader.path("ResultPath")?, None),
+    };
+    let assign = reader.field("Assign").cloned();
+    reader.finish();
+
+    Ok(Catcher {
+        error_equals,
+        next,
+        result_path,
+        assign,
+        output,
+        position: node.position,
+    })
+}
+
+fn parse_task(reader: &mut ObjectReader) -> Result<TaskState, AslError> {
+    check_exclusive(reader, "TimeoutSeconds", "TimeoutSecondsPath")?;
+    check_exclusive(reader, "HeartbeatSeconds", "HeartbeatSecondsPath")?;
+
+    Ok(TaskState {
+        resource: reader.required_string("Resource")?,
+        result: parse_result_handling(reader)?,
+        errors: parse_error_handling(reader)?,
+        timeout_seconds: reader.unsigned("TimeoutSeconds")?,
+        timeout_seconds_path: reader.string("TimeoutSecondsPath")?,
+        heartbeat_seconds: reader.unsigned("HeartbeatSeconds")?,
+        heartbeat_seconds_path: reader.string("HeartbeatSecondsPath")?,
+        credentials: reader.field("Credentials").cloned(),
+    })
+}
+
+/// JSONata Pass states shape their output with the `Output` field of the state.
+fn parse_pass(reader: &mut ObjectReader) -> Result<PassState, AslError> {
+    if reader.is_jsonata() {
+        return Ok(PassState {
+            result: ResultHandling::default(),
+            output: None,
+        });
+    }
+
+    Ok(PassState {
+        result: ResultHandling {
+            result_path: reader.path("ResultPath")?,
+            parameters: reader.field("Parameters").cloned(),
+            result_selector: None,
+            arguments: None,
+        },
+        output: reader.field("Result").cloned(),
+    })
+}
+
+fn parse_choice(reader: &mut ObjectReader) -> Result<ChoiceState, AslError> {
+    let choices = non_empty_array("Choices", reader.required("Choices")?)?
+        .iter()
+        .map(|rule| parse_choice_rule(reader, rule))
+        .collect::<Result<Vec<ChoiceRule>, AslError>>()?;
+
+    Ok(ChoiceState {
+        choices,
+        default: reader.string("Default")?,
+    })
+}
+
+fn parse_choice_rule<'a>(
+    parent: &ObjectReader<'a>,
+    node: &'a JsonNode,
+) -> Result<ChoiceRule, AslError> {
+    let mut reader = parent.object(node, "choice rule")?;
+
+    reader.string("Comment")?;
+    let next = reader.required_string("Next")?;
+    let assign = reader.field("Assign").cloned();
+    let condition = match reader.is_jsonata() {
+        true => Condition::Expression(reader.required_string("Condition")?),
+        false => parse_condition(&mut reader)?,
+    };
+    reader.finish();
+
+    Ok(ChoiceRule {
+        condition,
+        next,
+        assign,
+        position: node.position,
+    })
+}
+
+/// Condition inside `And`, `Or` or `Not`, which cannot have a `Next` field.
+fn parse_nested_condition<'a>(
+    parent: &ObjectReader<'a>,
+    node: &'a JsonNode,
+) -> Result<Condition, AslError> {
+    let mut reader = parent.object(node, "condition")?;
+    let condition = parse_condition(&mut reader)?;
+    reader.finish();
+
+    Ok(condition)
+}
+
+fn parse_conditions<'a>(
+    parent: &ObjectReader<'a>,
+    name: &str,
+    node: &'a JsonNode,
+) -> Result<Vec<Condition>, AslError> {
+    non_empty_array(name, node)?
+        .iter()
+        .map(|condition| parse_nested_condition(parent, condition))
+        .collect()
+}
+
+fn parse_condition(reader: &mut ObjectReader) -> Result<Condition, AslError> {
+    if let Some(node) = reader.field("And") {
+        return Ok(Condition::And(parse_conditions(reader, "And", node)?));
+    }
+    if let Some(node) = reader.field("Or") {
+        return Ok(Condition::Or(parse_conditions(reader, "Or", node)?));
+    }
+    if let Some(node) = reader.field("Not") {
+        return Ok(Condition::Not(Box::new(parse_nested_condition(
+            reader, node,
+        )?)));
+    }
+
+    let variable = reader.required_string("Variable")?;
+    let mut operators: Vec<(ComparisonOperator, String, &JsonNode)> = vec![];
+    for operator in ComparisonOperator::ALL {
+        let mut names = vec![operator.as_str().to_string()];
+        if accepts_path(operator) {
+            names.push(format!("{}Path", operator.as_str()));
+        }
+        for name in names {
+            if let Some(node) = reader.field(&name) {
+                operators.push((operator, name, node));
+            }
+        }
+    }
+
+    let (operator, name, node) = match operators.as_slice() {
+        [] => {
+            return Err(AslError::new(
+                reader.position,
+                &format!("{} has no comparison operator", reader.context),
+            ))
+        }
+        [operator] => operator,
+        [_, (_, name, node), ..] => {
+            return Err(AslError::new(
+                node.position,
+                &format!(
+                    "{} has more than one comparison operator, \"{}\" is extra",
+                    reader.context, name
+                ),
+            ))
+        }
+    };
+
+    let value = if name.ends_with("Path") {
+        ComparisonValue::Path(string_value(name, node)?)
+    } else {
+        match operator {
+            ComparisonOperator::NumericEquals
+            | ComparisonOperator::NumericLessThan
+            | ComparisonOperator::NumericGreaterThan
+            | ComparisonOperator::NumericLessThanEquals
+            | ComparisonOperator::NumericGreaterThanEquals => {
+                ComparisonValue::Number(float_value(name, node)?)
+            }
+            ComparisonOperator::BooleanEquals
+            | ComparisonOperator::IsNull
+            | ComparisonOperator::IsPresent
+            | ComparisonOperator::IsNumeric
+            | ComparisonOperator::IsString
+            | ComparisonOperator::IsBoolean
+            | ComparisonOperator::IsTimestamp => match node.value {
+                JsonValue::Bool(value) => ComparisonValue::Bool(value),
+                _ => return Err(unexpected(name, node, "a boolean")),
+            },
+            _ => ComparisonValue::String(string_value(name, node)?.value),
+        }
+    };
+
+    Ok(Condition::Comparison(Comparison {
+        variable,
+        operator: *operator,
+        value,
+    }))
+}
+
+/// Whether the operator has a variant comparing to another variable, as `StringEqualsPath`.
+fn accepts_path(operator: ComparisonOperator) -> bool {
+    !matches!(
+        operator,
+        ComparisonOperator::StringMatches
+            | ComparisonOperator::IsNull
+            | ComparisonOperator::IsPresent
+            | ComparisonOperator::IsNumeric
+            | ComparisonOperator::IsString
+            | ComparisonOperator::IsBoolean
+            | ComparisonOperator::IsTimestamp
+    )
+}
+
+fn parse_wait(reader: &mut ObjectReader) -> Result<WaitState, AslError> {
+    let mut durations: Vec<(&JsonNode, WaitDuration)> = vec![];
+
+    if let Some(node) = reader.field("Seconds") {
+        let seconds = match reader.expression(node) {
+            true => WaitDuration::Expression(string_value("Seconds", node)?),
+            false => WaitDuration::Seconds(unsigned_value("Seconds", node)?),
+        };
+        durations.push((node, seconds));
+    }
+    if let Some(node) = reader.field("Timestamp") {
+        let timestamp = string_value("Timestamp", node)?;
+        if reader.expression(node) {
+            durations.push((node, WaitDuration::Expression(timestamp)));
+        } else if DateTime::parse_from_rfc3339(&timestamp.value).is_err() {
+            return Err(AslError::new(
+                node.position,
+                &format!(
+                    "\"Timestamp\" must be an RFC3339 timestamp but is \"{}\"",
+                    timestamp.value
+                ),
+            ));
+        } else {
+            durations.push((node, WaitDuration::Timestamp(timestamp.value)));
+        }
+    }
+    if let Some(node) = reader.field("SecondsPath") {
+        durations.push((
+            node,
+            WaitDuration::SecondsPath(string_value("SecondsPath", node)?),
+        ));
+    }
+    if let Some(node) = reader.field("TimestampPath") {
+        durations.push((
+            node,
+            WaitDuration::TimestampPath(string_value("TimestampPath", node)?),
+        ));
+    }
+
+    match durations.as_slice() {
+        [] => Err(AslError::new(
+            reader.position,
+            &format!(
+                "{} needs one of \"Seconds\", \"Timestamp\", \"SecondsPath\" or \"TimestampPath\"",
+                reader.context
+            ),
+        )),
+        [(_, wait)] => Ok(WaitState { wait: wait.clone() }),
+        [_, (node, _), ..] => Err(AslError::new(
+            node.position,
+            &format!("{} can only wait in one way", reader.context),
+        )),
+    }
+}
+
+fn parse_fail(reader: &mut ObjectReader) -> Result<FailState, AslError> {
+    check_exclusive(reader, "Error", "ErrorPath")?;
+    check_exclusive(reader, "Cause", "CausePath")?;
+
+    Ok(FailState {
+        error: reader.string("Error")?.map(|error| error.value),
+        cause: reader.string("Cause")?.map(|cause| cause.value),
+        error_path: reader.string("ErrorPath")?,
+        cause_path: reader.string("CausePath")?,
+    })
+}
+
+fn parse_parallel(reader: &mut ObjectReader) -> Result<ParallelState, AslError> {
+    let branches = non_empty_array("Branches", reader.required("Branches")?)?
+        .iter()
+        .map(|branch| parse_nested_graph(reader, branch, "branch"))
+        .collect::<Result<Vec<StateGraph>, AslError>>()?;
+
+    Ok(ParallelState {
+        branches,
+        result: parse_result_handling(reader)?,
+        errors: parse_error_handling(reader)?,
+    })
+}
+
+fn parse_map(reader: &mut ObjectReader) -> Result<MapState, AslError> {
+    check_exclusive(reader, "ItemProcessor", "Iterator")?;
+    check_exclusive(reader, "ItemSelector", "Parameters")?;
+    check_exclusive(reader, "MaxConcurrency", "MaxConcurrencyPath")?;
+    check_exclusive(reader, "ToleratedFailureCount", "ToleratedFailureCountPath")?;
+    check_exclusive(
+        reader,
+        "ToleratedFailurePercentage",
+        "ToleratedFailurePercentagePath",
+    )?;
+
+    let (processor, mode, execution_type) = match reader.field("Iterator") {
+        Some(node) => (
+            parse_nested_graph(reader, node, "\"Iterator\"")?,
+            None,
+            None,
+        ),
+        None => {
+            let node = reader.required("ItemProcessor")?;
+            parse_item_processor(reader, node)?
+        }
+    };
+    let parameters = match reader.field("ItemSelector") {
+        Some(node) => Some(node.clone()),
+        None => reader.field("Parameters").cloned(),
+    };
+    let (items_path, items) = match reader.is_jsonata() {
+        true => (None, reader.field("Items").cloned()),
+        false => (reader.string("ItemsPath")?, None),
+    };
+
+    Ok(MapState {
+        processor,
+        mode,
+        execution_type,
+        items_path,
+        items,
+        max_concurrency: reader.unsigned("MaxConcurrency")?,
+        max_concurrency_path: reader.string("MaxConcurrencyPath")?,
+        item_reader: reader.field("ItemReader").cloned(),
+        item_batcher: reader.field("ItemBatcher").cloned(),
+        result_writer: reader.field("ResultWriter").cloned(),
+        tolerated_failure_count: reader.unsigned("ToleratedFailureCount")?,
+        tolerated_failure_count_path: reader.string("ToleratedFailureCountPath")?,
+        tolerated_failure_percentage: reader.float("ToleratedFailurePercentage")?,
+        tolerated_failure_percentage_path: reader.string("ToleratedFailurePercentagePath")?,
+        label: reader.string("Label")?.map(|label| label.value),
+        result: ResultHandling {
+            parameters,
+            ..parse_result_handling(reader)?
+        },
+        errors: parse_error_handling(reader)?,
+    })
+}
+
+type ItemProcessor = (StateGraph, Option<Located<String>>, Option<Located<String>>);
+
+fn parse_item_processor<'a>(
+    parent: &ObjectReader<'a>,
+    node: &'a JsonNode,
+) -> Result<ItemProcessor, AslError> {
+    let mut reader = parent.object(node, "\"ItemProcessor\"")?;
+
+    reader.string("Comment")?;
+    let (mode, execution_type) = match reader.field("ProcessorConfig") {
+        Some(config_node) => {
+            let mut config = reader.object(config_node, "\"ProcessorConfig\"")?;
+            let mode = one_of("Mode", config.string("Mode")?, &["INLINE", "DISTRIBUTED"])?;
+            let execution_type = one_of(
+                "ExecutionType",
+                config.string("ExecutionType")?,
+                &["STANDARD", "EXPRESS"],
+            )?;
+            config.finish();
+            (mode, execution_type)
+        }
+        None => (None, None),
+    };
+    let graph = parse_graph(&mut reader)?;
+    reader.finish();
+
+    Ok((graph, mode, execution_type))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEFINITION: &str = r#"{
+  "Comment": "Feeds the dinosaurs",
+  "StartAt": "Hungry?",
+  "TimeoutSeconds": 3600,
+  "States": {
+    "Hungry?": {
+      "Type": "Choice",
+      "Choices": [
+        {
+          "And": [
+            {"Variable": "$.hunger", "NumericGreaterThan": 5},
+            {"Not": {"Variable": "$.asleep", "BooleanEquals": true}}
+          ],
+          "Next": "Feed"
+        },
+        {"Variable": "$.species", "StringEqualsPath": "$.carnivore", "Next": "Hunt"}
+      ],
+      "Default": "Nap"
+    },
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:states:::lambda:invoke",
+      "Parameters": {"FunctionName": "feed", "Payload.$": "$"},
+      "ResultSelector": {"eaten.$": "$.Payload"},
+      "ResultPath": "$.meal",
+      "TimeoutSeconds": 30,
+      "Retry": [
+        {"ErrorEquals": ["States.Timeout"], "IntervalSeconds": 2, "MaxAttempts": 3, "BackoffRate": 1.5, "JitterStrategy": "FULL"}
+      ],
+      "Catch": [{"ErrorEquals": ["States.ALL"], "ResultPath": null, "Next": "Extinct"}],
+      "Next": "Herd"
+    },
+    "Hunt": {"Type": "Pass", "Result": {"prey": "ammonite"}, "ResultPath": "$.prey", "Next": "Herd"},
+    "Nap": {"Type": "Wait", "SecondsPath": "$.nap", "Next": "Hungry?"},
+    "Herd": {
+      "Type": "Parallel",
+      "Branches": [
+        {"StartAt": "Roar", "States": {"Roar": {"Type": "Succeed"}}},
+        {"StartAt": "Stomp", "States": {"Stomp": {"Type": "Pass", "End": true}}}
+      ],
+      "Next": "Eggs"
+    },
+    "Eggs": {
+      "Type": "Map",
+      "ItemsPath": "$.eggs",
+      "MaxConcurrency": 2,
+      "ItemProcessor": {
+        "ProcessorConfig": {"Mode": "DISTRIBUTED", "ExecutionType": "EXPRESS"},
+        "StartAt": "Hatch",
+        "States": {"Hatch": {"Type": "Task", "Resource": "arn:aws:lambda:us-east-1:123456789012:function:hatch", "End": true}}
+      },
+      "OutputPath": null,
+      "End": true
+    },
+    "Extinct": {"Type": "Fail", "Error": "Meteor", "CausePath": "$.cause"}
+  }
+}"#;
+
+    fn located<T>(value: T, line: usize, column: usize) -> Located<T> {
+        Located {
+            value,
+            position: Position { line, column },
+        }
+    }
+
+    #[test]
+    fn should_parse_every_state_type() {
+        let definition = parse_definition(DEFINITION).unwrap();
+
+        assert_eq!(definition.comment, Some("Feeds the dinosaurs".to_string()));
+        assert_eq!(definition.timeout_seconds, Some(3600));
+        assert_eq!(
+            definition.graph.start_at,
+            located("Hungry?".to_string(), 3, 14)
+        );
+        assert_eq!(
+            definition
+                .graph
+                .states
+                .iter()
+                .map(|state| (state.name.as_str(), state.kind.type_name()))
+                .collect::<Vec<(&str, &str)>>(),
+            vec![
+                ("Hungry?", "Choice"),
+                ("Feed", "Task"),
+                ("Hunt", "Pass"),
+                ("Nap", "Wait"),
+                ("Herd", "Parallel"),
+                ("Eggs", "Map"),
+                ("Extinct", "Fail"),
+            ]
+        );
+
+        let feed = definition.graph.state("Feed").unwrap();
+        assert_eq!(
+            feed.position,
+            Position {
+                line: 20,
+                column: 5
+            }
+        );
+        assert_eq!(
+            feed.transition,
+            Some(located(Transition::Next("Herd".to_string()), 31, 15))
+        );
+        let StateKind::Task(task) = &feed.kind else {
+            panic!("expected a Task state");
+        };
+        assert_eq!(task.resource.value, "arn:aws:states:::lambda:invoke");
+        assert_eq!(task.timeout_seconds, Some(30));
+        assert_eq!(
+            task.result.result_path,
+            Some(located(Some("$.meal".to_string()), 25, 21))
+        );
+        assert_eq!(
+            task.result.parameters.as_ref().unwrap().to_value(),
+            serde_json::json!({"FunctionName": "feed", "Payload.$": "$"})
+        );
+        assert_eq!(
+            task.errors.retry,
+            vec![Retrier {
+                error_equals: vec!["States.Timeout".to_string()],
+                interval_seconds: Some(2),
+                max_attempts: Some(3),
+                backoff_rate: Some(1.5),
+                max_delay_seconds: None,
+                jitter_strategy: Some("FULL".to_string()),
+                position: Position {
+                    line: 28,
+                    column: 9
+                },
+            }]
+        );
+        assert_eq!(task.errors.catch[0].next.value, "Extinct");
+        assert_eq!(
+            task.errors.catch[0].result_path,
+            Some(located(None, 30, 63))
+        );
+
+        let StateKind::Wait(nap) = &definition.graph.state("Nap").unwrap().kind else {
+            panic!("expected a Wait state");
+        };
+        assert_eq!(
+            nap.wait,
+            WaitDuration::SecondsPath(located("$.nap".to_string(), 34, 44))
+        );
+
+        let StateKind::Parallel(herd) = &definition.graph.state("Herd").unwrap().kind else {
+            panic!("expected a Parallel state");
+        };
+        assert_eq!(herd.branches.len(), 2);
+        assert_eq!(herd.branches[0].states[0].kind, StateKind::Succeed);
+        assert_eq!(herd.branches[0].states[0].transition, None);
+        assert_eq!(
+            herd.branches[1].states[0].transition,
+            Some(located(Transition::End, 39, 74))
+        );
+
+        let eggs = definition.graph.state("Eggs").unwrap();
+        assert_eq!(eggs.output_path, Some(located(None, 52, 21)));
+        let StateKind::Map(map) = &eggs.kind else {
+            panic!("expected a Map state");
+        };
+        assert_eq!(map.mode, Some(located("DISTRIBUTED".to_string(), 48, 37)));
+        assert_eq!(map.max_concurrency, Some(2));
+        assert_eq!(map.processor.states[0].name, "Hatch");
+
+        let StateKind::Fail(extinct) = &definition.graph.state("Extinct").unwrap().kind else {
+            panic!("expected a Fail state");
+        };
+        assert_eq!(extinct.error, Some("Meteor".to_string()));
+        assert_eq!(extinct.cause_path.as_ref().unwrap().value, "$.cause");
+    }
+
+    #[test]
+    fn should_parse_choice_conditions() {
+        let definition = parse_definition(DEFINITION).unwrap();
+        let StateKind::Choice(choice) = &definition.graph.state("Hungry?").unwrap().kind else {
+            panic!("expected a Choice state");
+        };
+
+        assert_eq!(choice.default, Some(located("Nap".to_string(), 18, 18)));
+        assert_eq!(choice.choices[0].next.value, "Feed");
+        assert_eq!(
+            choice.choices[0].condition,
+            Condition::And(vec![
+                Condition::Comparison(Comparison {
+                    variable: located("$.hunger".to_string(), 11, 26),
+                    operator: ComparisonOperator::NumericGreaterThan,
+                    value: ComparisonValue::Number(5.0),
+                }),
+                Condition::Not(Box::new(Condition::Comparison(Comparison {
+                    variable: located("$.asleep".to_string(), 12, 34),
+                    operator: ComparisonOperator::BooleanEquals,
+                    value: ComparisonValue::Bool(true),
+                }))),
+            ])
+        );
+        assert_eq!(
+            choice.choices[1].condition,
+            Condition::Comparison(Comparison {
+                variable: located("$.species".to_string(), 16, 22),
+                operator: ComparisonOperator::StringEquals,
+                value: ComparisonValue::Path(located("$.carnivore".to_string(), 16, 55)),
+            })
+        );
+        assert_eq!(
+            choice.choices[0].condition.to_string(),
+            "$.hunger > 5 and not ($.asleep == true)"
+        );
+        assert_eq!(
+            choice.choices[1].condition.to_string(),
+            "$.species == $.carnivore"
+        );
+    }
+
+    #[test]
+    fn should_parse_map_with_iterator() {
+        let definition = parse_definition(
+            r#"{"StartAt": "Eggs", "States": {"Eggs": {"Type": "Map", "Parameters": {"egg.$": "$$.Map.Item.Value"}, "Iterator": {"StartAt": "Hatch", "States": {"Hatch": {"Type": "Succeed"}}}, "End": true}}}"#,
+        )
+        .unwrap();
+
+        let StateKind::Map(map) = &definition.graph.states[0].kind else {
+            panic!("expected a Map state");
+        };
+        assert_eq!(map.mode, None);
+        assert_eq!(map.processor.start_at.value, "Hatch");
+        assert_eq!(
+            map.result.parameters.as_ref().unwrap().to_value(),
+            serde_json::json!({"egg.$": "$$.Map.Item.Value"})
+        );
+    }
+
+    #[test]
+    fn should_leave_missing_transitions_to_the_linter() {
+        let definition =
+            parse_definition(r#"{"StartAt": "Roar", "States": {"Roar": {"Type": "Pass"}}}"#)
+                .unwrap();
+
+        assert_eq!(definition.graph.states[0].transition, None);
+    }
+
+    #[test]
+    fn should_report_where_the_definition_is_invalid() {
+        let error = |states: &str| {
+            parse_definition(&format!(
+                "{{\"StartAt\": \"Roar\",\n\"States\": {{\n\"Roar\": {}\n}}}}",
+                states
+            ))
+            .unwrap_err()
+            .to_string()
+        };
+
+        assert_eq!(
+            parse_definition("{\"States\": {}}")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 1: the definition is missing the \"StartAt\" field"
+        );
+        assert_eq!(
+            parse_definition("{\"StartAt\": \"Roar\", \"States\": {}}")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 31: \"States\" must have at least one state"
+        );
+        assert_eq!(
+            parse_definition("{\"QueryLanguage\": \"JSONPath2\"}")
+                .unwrap_err()
+                .to_string(),
+            "line 1, column 19: \"QueryLanguage\" must be one of JSONPath, JSONata but is \"JSONPath2\""
+        );
+        assert_eq!(
+            error("{\"Type\": \"Roar\"}"),
+            "line 3, column 18: unknown state type \"Roar\""
+        );
+        assert_eq!(
+            error("{\"Type\": \"Task\", \"End\": true}"),
+            "line 3, column 9: state \"Roar\" is missing the \"Resource\" field"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Pass\", \"Next\": \"Roar\", \"End\": true}"),
+            "line 3, column 49: state \"Roar\" cannot have both \"Next\" and \"End\""
+        );
+        assert_eq!(
+            error("{\"Type\": \"Wait\", \"Seconds\": -1, \"End\": true}"),
+            "line 3, column 37: \"Seconds\" must be a non-negative integer but is -1"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Wait\", \"Seconds\": 1, \"Timestamp\": \"2022-04-01T00:00:00Z\", \"End\": true}"),
+            "line 3, column 53: state \"Roar\" can only wait in one way"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Task\", \"Resource\": \"arn\", \"Retry\": [{\"ErrorEquals\": []}], \"End\": true}"),
+            "line 3, column 71: \"ErrorEquals\" must not be empty"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Task\", \"Resource\": \"arn\", \"Retry\": [{\"ErrorEquals\": [\"States.ALL\"], \"BackoffRate\": 0.5}], \"End\": true}"),
+            "line 3, column 102: \"BackoffRate\" must be at least 1.0 but is 0.5"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Choice\", \"Choices\": [{\"Variable\": \"$.a\", \"Next\": \"Roar\"}]}"),
+            "line 3, column 40: choice rule has no comparison operator"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Choice\", \"Choices\": [{\"Variable\": \"$.a\", \"IsNull\": true, \"IsString\": true, \"Next\": \"Roar\"}]}"),
+            "line 3, column 88: choice rule has more than one comparison operator, \"IsString\" is extra"
+        );
+        assert_eq!(
+            error(
+                "{\"Type\": \"Parallel\", \"Branches\": [{\"StartAt\": \"Stomp\"}], \"End\": true}"
+            ),
+            "line 3, column 43: branch is missing the \"States\" field"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Task\", \"Resource\": \"arn\", \"Catch\": [{\"ErrorEquals\": [\"States.ALL\"]}], \"End\": true}"),
+            "line 3, column 55: catcher is missing the \"Next\" field"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Wait\", \"QueryLanguage\": \"JSONata\", \"Seconds\": \"soon\", \"End\": true}"),
+            "line 3, column 65: \"Seconds\" must be a non-negative integer but is a string"
+        );
+        assert_eq!(
+            error("{\"Type\": \"Choice\", \"QueryLanguage\": \"JSONata\", \"Choices\": [{\"Variable\": \"$.a\", \"IsNull\": true, \"Next\": \"Roar\"}]}"),
+            "line 3, column 68: choice rule is missing the \"Condition\" field"
+        );
+    }
+
+    #[test]
+    fn should_parse_nested_parallel_and_map_states_with_error_handling() {
+        let definition = parse_definition(
+            r#"{
+  "StartAt": "Herd",
+  "States": {
+    "Herd": {
+      "Type": "Parallel",
+      "Branches": [{
+        "StartAt": "Eggs",
+        "States": {
+          "Eggs": {
+            "Type": "Map",
+            "ItemProcessor": {
+              "StartAt": "Hatch",
+              "States": {
+                "Hatch": {
+                  "Type": "Task",
+                  "Resource": "arn:aws:lambda:us-east-1:123456789012:function:hatch",
+                  "Retry": [{"ErrorEquals": ["Lambda.TooManyRequestsException"], "MaxAttempts": 6}],
+                  "Catch": [{"ErrorEquals": ["States.ALL"], "ResultPath": "$.error", "Next": "Rotten"}],
+                  "End": true
+                },
+                "Rotten": {"Type": "Fail", "Error": "Rotten"}
+              }
+            },
+            "Retry": [{"ErrorEquals": ["States.ALL"], "MaxAttempts": 1}],
+            "End": true
+          }
+        }
+      }],
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Next": "Extinct"}],
+      "End": true
+    },
+    "Extinct": {"Type": "Fail"}
+  }
+}"#,
+        )
+        .unwrap();
+
+        let StateKind::Parallel(herd) = &definition.graph.states[0].kind else {
+            panic!("expected a Parallel state");
+        };
+        assert_eq!(herd.errors.catch[0].next.value, "Extinct");
+        let StateKind::Map(eggs) = &herd.branches[0].states[0].kind else {
+            panic!("expected a Map state");
+        };
+        assert_eq!(eggs.errors.retry[0].max_attempts, Some(1));
+        let hatch = eggs.processor.state("Hatch").unwrap();
+        let StateKind::Task(task) = &hatch.kind else {
+            panic!("expected a Task state");
+        };
+        assert_eq!(
+            task.errors.retry[0].error_equals,
+            vec!["Lambda.TooManyRequestsException".to_string()]
+        );
+        assert_eq!(
+            task.errors.catch[0].result_path,
+            Some(located(Some("$.error".to_string()), 18, 75))
+        );
+        assert_eq!(task.errors.catch[0].next.value, "Rotten");
+        assert_eq!(definition.unknown_fields, vec![]);
+    }
+
+    #[test]
+    fn should_parse_jsonata_states() {
+        let definition = parse_definition(
+            r#"{
+  "QueryLanguage": "JSONata",
+  "StartAt": "Feed",
+  "States": {
+    "Feed": {
+      "Type": "Task",
+      "Resource": "arn:aws:states:::lambda:invoke",
+      "Arguments": {"FunctionName": "feed", "Payload": "{% $states.input %}"},
+      "Output": "{% $states.result.Payload %}",
+      "Assign": {"meal": "{% $states.result.Payload.meal %}"},
+      "TimeoutSeconds": "{% $timeout %}",
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Output": "{% $states.errorOutput %}", "Next": "Nap"}],
+      "Next": "Full?"
+    },
+    "Full?": {
+      "Type": "Choice",
+      "Choices": [{"Condition": "{% $meal.size > 5 %}", "Next": "Eggs"}],
+      "Default": "Nap"
+    },
+    "Nap": {"Type": "Wait", "Seconds": "{% $states.input.nap %}", "Next": "Feed"},
+    "Eggs": {
+      "Type": "Map",
+      "Items": "{% $states.input.eggs %}",
+      "ItemProcessor": {
+        "StartAt": "Hatch",
+        "States": {"Hatch": {"Type": "Pass", "QueryLanguage": "JSONPath", "ResultPath": "$.hatched", "End": true}}
+      },
+      "End": true
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(definition.query_language, QueryLanguage::Jsonata);
+        assert_eq!(definition.unknown_fields, vec![]);
+
+        let feed = definition.graph.state("Feed").unwrap();
+        assert_eq!(feed.query_language, QueryLanguage::Jsonata);
+        assert_eq!(
+            feed.output.as_ref().unwrap().to_value(),
+            serde_json::json!("{% $states.result.Payload %}")
+        );
+        assert!(feed.assign.is_some());
+        let StateKind::Task(task) = &feed.kind else {
+            panic!("expected a Task state");
+        };
+        assert_eq!(task.timeout_seconds, None);
+        assert_eq!(
+            task.result.arguments.as_ref().unwrap().to_value(),
+            serde_json::json!({"FunctionName": "feed", "Payload": "{% $states.input %}"})
+        );
+        assert!(task.errors.catch[0].output.is_some());
+
+        let StateKind::Choice(choice) = &definition.graph.state("Full?").unwrap().kind else {
+            panic!("expected a Choice state");
+        };
+        assert_eq!(
+            choice.choices[0].condition,
+            Condition::Expression(located("{% $meal.size > 5 %}".to_string(), 17, 33))
+        );
+
+        let StateKind::Wait(nap) = &definition.graph.state("Nap").unwrap().kind else {
+            panic!("expected a Wait state");
+        };
+        assert_eq!(
+            nap.wait,
+            WaitDuration::Expression(located("{% $states.input.nap %}".to_string(), 20, 40))
+        );
+
+        let StateKind::Map(eggs) = &definition.graph.state("Eggs").unwrap().kind else {
+            panic!("expected a Map state");
+        };
+        assert_eq!(eggs.items_path, None);
+        assert!(eggs.items.is_some());
+        let hatch = &eggs.processor.states[0];
+        assert_eq!(hatch.query_language, QueryLanguage::JsonPath);
+        let StateKind::Pass(pass) = &hatch.kind else {
+            panic!("expected a Pass state");
+        };
+        assert_eq!(
+            pass.result.result_path.as_ref().unwrap().value,
+            Some("$.hatched".to_string())
+        );
+    }
+
+    #[test]
+    fn should_record_unknown_fields_instead_of_failing() {
+        let definition = parse_definition(
+            r#"{
+  "StartAt": "Roar",
+  "Roar": true,
+  "States": {
+    "Roar": {
+      "Type": "Pass",
+      "Resource": "arn",
+      "Output": {"roar": true},
+      "Retry": [],
+      "End": true
+    },
+    "Stomp": {
+      "Type": "Task",
+      "Resource": "arn",
+      "Arguments": {"stomp": true},
+      "Catch": [{"ErrorEquals": ["States.ALL"], "Output": {}, "Next": "Roar"}],
+      "End": true
+    }
+  }
+}"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            definition
+                .unknown_fields
+                .iter()
+                .map(|field| (
+                    field.name.as_str(),
+                    field.context.as_str(),
+                    field.position.line
+                ))
+                .collect::<Vec<(&str, &str, usize)>>(),
+            vec![
+                ("Resource", "state \"Roar\"", 7),
+                ("Output", "state \"Roar\"", 8),
+                ("Retry", "state \"Roar\"", 9),
+                ("Output", "catcher", 16),
+                ("Arguments", "state \"Stomp\"", 15),
+                ("Roar", "the definition", 3),
+            ]
+        );
+    }
+}
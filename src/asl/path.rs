@@ -0,0 +1,312 @@
+/// Kind of path a field takes. Reference paths (ex. `ResultPath` or `ItemsPath`) must point at
+/// a single node, so they cannot use wildcards, filters, slices or deep scans.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PathKind {
+    Path,
+    Reference,
+}
+
+/// Intrinsic functions available to payload templates, ex. `States.Format('{}', $.name)`.
+const INTRINSIC_FUNCTIONS: [&str; 18] = [
+    "States.Format",
+    "States.StringToJson",
+    "States.JsonToString",
+    "States.Array",
+    "States.ArrayPartition",
+    "States.ArrayContains",
+    "States.ArrayRange",
+    "States.ArrayGetItem",
+    "States.ArrayLength",
+    "States.ArrayUnique",
+    "States.Base64Encode",
+    "States.Base64Decode",
+    "States.Hash",
+    "States.JsonMerge",
+    "States.MathRandom",
+    "States.MathAdd",
+    "States.StringSplit",
+    "States.UUID",
+];
+
+/// Checks the syntax of a JSONPath, starting at the input (`$`), the context object (`$$`) or
+/// a variable (`$name`). Errors say at which character of the path the problem is.
+pub fn check_path(path: &str, kind: PathKind) -> Result<(), String> {
+    let mut position = match path.strip_prefix("$$") {
+        Some(_) => 2,
+        None if path.starts_with('$') => 1 + variable_name_length(&path[1..]),
+        None => return Err("must start with '$'".to_string()),
+    };
+
+    while position < path.len() {
+        let rest = &path[position..];
+        let character_number = path[..position].chars().count() + 1;
+
+        if let Some(after_dots) = rest.strip_prefix("..") {
+            if kind == PathKind::Reference {
+                return Err(format!(
+                    "deep scan '..' at character {} is not allowed in a reference path",
+                    character_number
+                ));
+            }
+            position += 2 + field_name(after_dots, character_number, 2, kind)?;
+        } else if let Some(after_dot) = rest.strip_prefix('.') {
+            position += 1 + field_name(after_dot, character_number, 1, kind)?;
+        } else if rest.starts_with('[') {
+            position += subscript(rest, character_number, kind)?;
+        } else {
+            let character = rest.chars().next().unwrap_or_default();
+            return Err(format!(
+                "unexpected '{}' at character {}",
+                character, character_number
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Checks a call to an intrinsic function, with its arguments when they are paths or other
+/// intrinsic functions.
+pub fn check_intrinsic(expression: &str) -> Result<(), String> {
+    let Some(open) = expression.find('(') else {
+        return Err("intrinsic function is missing its arguments".to_string());
+    };
+    let name = &expression[..open];
+    if !INTRINSIC_FUNCTIONS.contains(&name) {
+        return Err(format!("unknown intrinsic function {}", name));
+    }
+
+    for argument in split_arguments(&expression[open + 1..])? {
+        let argument = argument.trim();
+        if argument.starts_with("States.") {
+            check_intrinsic(argument)?;
+        } else if argument.starts_with('$') {
+            check_path(argument, PathKind::Path)
+                .map_err(|error| format!("{}: {}", argument, error))?;
+        } else if argument.is_empty() {
+            return Err(format!("{} has an empty argument", name));
+        }
+    }
+
+    Ok(())
+}
+
+/// Length of the variable name after `$`, 0 for paths of the input.
+fn variable_name_length(rest: &str) -> usize {
+    if !rest.starts_with(|character: char| character.is_ascii_alphabetic() || character == '_') {
+        return 0;
+    }
+
+    rest.find(|character: char| !(character.is_ascii_alphanumeric() || character == '_'))
+        .unwrap_or(rest.len())
+}
+
+/// Length of the field name after the `dots` dots starting at `character_number`.
+fn field_name(
+    rest: &str,
+    character_number: usize,
+    dots: usize,
+    kind: PathKind,
+) -> Result<usize, String> {
+    let length = rest
+        .find(|character: char| matches!(character, '.' | '[') || character.is_whitespace())
+        .unwrap_or(rest.len());
+
+    match &rest[..length] {
+        "" => Err(format!(
+            "missing field name after '.' at character {}",
+            character_number + dots - 1
+        )),
+        "*" if kind == PathKind::Reference => Err(format!(
+            "wildcard at character {} is not allowed in a reference path",
+            character_number + dots
+        )),
+        _ => Ok(length),
+    }
+}
+
+/// Length of a subscript, from its `[` to its `]`.
+fn subscript(rest: &str, character_number: usize, kind: PathKind) -> Result<usize, String> {
+    let mut quote: Option<char> = None;
+    let mut close = None;
+    for (index, character) in rest.char_indices().skip(1) {
+        match (quote, character) {
+            (Some(open), _) if character == open => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(character),
+            (None, ']') => {
+                close = Some(index);
+                break;
+            }
+            (None, _) => {}
+        }
+    }
+    let Some(close) = close else {
+        return Err(format!(
+            "'[' at character {} is not closed",
+            character_number
+        ));
+    };
+
+    let content = rest[1..close].trim();
+    let is_index = |value: &str| {
+        let digits = value.strip_prefix('-').unwrap_or(value);
+        !digits.is_empty() && digits.chars().all(|character| character.is_ascii_digit())
+    };
+    let is_quoted = |value: &str| {
+        value.len() >= 2
+            && ((value.starts_with('\'') && value.ends_with('\''))
+                || (value.starts_with('"') && value.ends_with('"')))
+    };
+
+    let is_filter = content.starts_with("?(") && content.ends_with(')');
+    let is_slice = content.contains(':')
+        && content
+            .split(':')
+            .all(|bound| bound.trim().is_empty() || is_index(bound.trim()));
+    let is_union = content.contains(',')
+        && content
+            .split(',')
+            .all(|item| is_index(item.trim()) || is_quoted(item.trim()));
+
+    let selects_many = content == "*" || is_filter || is_slice || is_union;
+    if !selects_many && !is_index(content) && !is_quoted(content) {
+        return Err(format!(
+            "invalid subscript [{}] at character {}",
+            content, character_number
+        ));
+    }
+
+    if selects_many && kind == PathKind::Reference {
+        return Err(format!(
+            "subscript [{}] at character {} selects several nodes, which a reference path cannot",
+            content, character_number
+        ));
+    }
+
+    Ok(close + 1)
+}
+
+/// Arguments of an intrinsic function, split on the commas outside of strings and nested
+/// calls. `rest` starts after the opening parenthesis.
+fn split_arguments(rest: &str) -> Result<Vec<&str>, String> {
+    let mut arguments = vec![];
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (index, character) in rest.char_indices() {
+        match character {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '\'' => in_string = !in_string,
+            _ if in_string => {}
+            '(' => depth += 1,
+            ',' if depth == 0 => {
+                arguments.push(&rest[start..index]);
+                start = index + 1;
+            }
+            ')' if depth > 0 => depth -= 1,
+            ')' => {
+                if !rest[index + 1..].trim().is_empty() {
+                    return Err(format!(
+                        "unexpected '{}' after the intrinsic function",
+                        rest[index + 1..].trim()
+                    ));
+                }
+                let last = &rest[start..index];
+                if !arguments.is_empty() || !last.trim().is_empty() {
+                    arguments.push(last);
+                }
+                return Ok(arguments);
+            }
+            _ => {}
+        }
+    }
+
+    Err("intrinsic function is missing its closing ')'".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_accept_valid_paths() {
+        for path in [
+            "$",
+            "$.dinosaur",
+            "$.dinosaur.teeth[0]",
+            "$['dinosaur name'].era",
+            "$$.Execution.Id",
+            "$$.Map.Item.Value",
+            "$meal.size",
+            "$.eggs[*].weight",
+            "$..weight",
+            "$.eggs[?(@.weight > 10)]",
+            "$.eggs[1:3]",
+            "$.eggs[0,2]",
+        ] {
+            assert_eq!(check_path(path, PathKind::Path), Ok(()), "{}", path);
+        }
+    }
+
+    #[test]
+    fn should_reject_invalid_paths() {
+        let error = |path: &str| check_path(path, PathKind::Path).unwrap_err();
+
+        assert_eq!(error("dinosaur"), "must start with '$'");
+        assert_eq!(
+            error("$.dinosaur."),
+            "missing field name after '.' at character 11"
+        );
+        assert_eq!(error("$.eggs[0"), "'[' at character 7 is not closed");
+        assert_eq!(
+            error("$.eggs[first]"),
+            "invalid subscript [first] at character 7"
+        );
+        assert_eq!(error("$.dino saur"), "unexpected ' ' at character 7");
+        assert_eq!(error("$-dinosaur"), "unexpected '-' at character 2");
+    }
+
+    #[test]
+    fn should_reject_reference_paths_selecting_several_nodes() {
+        let error = |path: &str| check_path(path, PathKind::Reference).unwrap_err();
+
+        assert_eq!(check_path("$.meal.size", PathKind::Reference), Ok(()));
+        assert_eq!(
+            error("$..weight"),
+            "deep scan '..' at character 2 is not allowed in a reference path"
+        );
+        assert_eq!(
+            error("$.eggs.*"),
+            "wildcard at character 8 is not allowed in a reference path"
+        );
+        assert_eq!(
+            error("$.eggs[*]"),
+            "subscript [*] at character 7 selects several nodes, which a reference path cannot"
+        );
+    }
+
+    #[test]
+    fn should_check_intrinsic_functions() {
+        assert_eq!(
+            check_intrinsic("States.Format('{} roars, {}', $.name, States.ArrayLength($.eggs))"),
+            Ok(())
+        );
+        assert_eq!(check_intrinsic("States.UUID()"), Ok(()));
+        assert_eq!(
+            check_intrinsic("States.Roar($.name)").unwrap_err(),
+            "unknown intrinsic function States.Roar"
+        );
+        assert_eq!(
+            check_intrinsic("States.Format('{}', $.name").unwrap_err(),
+            "intrinsic function is missing its closing ')'"
+        );
+        assert_eq!(
+            check_intrinsic("States.Format('{}', $..name[)").unwrap_err(),
+            "$..name[: '[' at character 8 is not closed"
+        );
+    }
+}
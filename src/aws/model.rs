@@ -1,10 +1,83 @@
+use crate::output::{format_elapsed, Tabular};
 use chrono::{DateTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use http::Uri;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::sync::LazyLock;
 
-#[derive(Clone, PartialEq, Debug)]
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct ClientOptions {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub endpoint_url: Option<Uri>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
 pub struct StateMachine {
     pub arn: String,
     pub name: String,
+    /// Only known once the machine is described.
+    #[serde(flatten)]
+    pub details: Option<StateMachineDetails>,
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct StateMachineDetails {
+    #[serde(rename = "type")]
+    pub machine_type: MachineType,
+    pub status: Option<MachineStatus>,
+    pub creation_date: DateTime<Utc>,
+    pub role_arn: String,
+    pub logging: Option<LoggingConfiguration>,
+    pub tracing_enabled: bool,
+    /// Amazon States Language definition, as JSON.
+    pub definition: String,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MachineType {
+    Standard,
+    Express,
+}
+
+impl MachineType {
+    pub const ALL: [MachineType; 2] = [MachineType::Standard, MachineType::Express];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MachineType::Standard => "STANDARD",
+            MachineType::Express => "EXPRESS",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum MachineStatus {
+    Active,
+    Deleting,
+}
+
+impl MachineStatus {
+    pub const ALL: [MachineStatus; 2] = [MachineStatus::Active, MachineStatus::Deleting];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MachineStatus::Active => "ACTIVE",
+            MachineStatus::Deleting => "DELETING",
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct LoggingConfiguration {
+    /// ALL, ERROR, FATAL or OFF.
+    pub level: String,
+    pub include_execution_data: bool,
+    pub log_groups: Vec<String>,
 }
 
 impl fmt::Display for StateMachine {
@@ -13,14 +86,325 @@ impl fmt::Display for StateMachine {
     }
 }
 
-#[derive(Clone, PartialEq, Debug)]
+impl Tabular for StateMachine {
+    fn headers() -> Vec<&'static str> {
+        vec!["name", "arn"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![self.name.clone(), self.arn.clone()]
+    }
+}
+
+/// Filter applied to state machine or execution names.
+#[derive(Clone, Debug)]
+pub enum NameFilter {
+    Prefix(String),
+    Regex(Regex),
+}
+
+impl NameFilter {
+    /// Parses a filter typed by the user, a regex between slashes (ex. /orders-.*/) or a
+    /// name prefix otherwise. An empty filter matches every name.
+    pub fn parse(raw_filter: &str) -> Result<Option<NameFilter>, regex::Error> {
+        let raw_filter = raw_filter.trim();
+
+        if raw_filter.is_empty() {
+            Ok(None)
+        } else if raw_filter.len() > 1 && raw_filter.starts_with('/') && raw_filter.ends_with('/') {
+            Ok(Some(NameFilter::Regex(Regex::new(
+                &raw_filter[1..raw_filter.len() - 1],
+            )?)))
+        } else {
+            Ok(Some(NameFilter::Prefix(raw_filter.to_string())))
+        }
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NameFilter::Prefix(prefix) => name.starts_with(prefix.as_str()),
+            NameFilter::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl PartialEq for NameFilter {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (NameFilter::Prefix(prefix), NameFilter::Prefix(other_prefix)) => {
+                prefix == other_prefix
+            }
+            (NameFilter::Regex(regex), NameFilter::Regex(other_regex)) => {
+                regex.as_str() == other_regex.as_str()
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Serialize, Deserialize, ValueEnum)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ExecutionStatus {
+    Running,
+    Succeeded,
+    Failed,
+    TimedOut,
+    Aborted,
+    PendingRedrive,
+}
+
+impl ExecutionStatus {
+    pub const ALL: [ExecutionStatus; 6] = [
+        ExecutionStatus::Running,
+        ExecutionStatus::Succeeded,
+        ExecutionStatus::Failed,
+        ExecutionStatus::TimedOut,
+        ExecutionStatus::Aborted,
+        ExecutionStatus::PendingRedrive,
+    ];
+
+    /// Statuses of the executions that ended without succeeding and can be started again.
+    pub const RETRYABLE: [ExecutionStatus; 3] = [
+        ExecutionStatus::Failed,
+        ExecutionStatus::TimedOut,
+        ExecutionStatus::Aborted,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionStatus::Running => "RUNNING",
+            ExecutionStatus::Succeeded => "SUCCEEDED",
+            ExecutionStatus::Failed => "FAILED",
+            ExecutionStatus::TimedOut => "TIMED_OUT",
+            ExecutionStatus::Aborted => "ABORTED",
+            ExecutionStatus::PendingRedrive => "PENDING_REDRIVE",
+        }
+    }
+}
+
+impl fmt::Display for ExecutionStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Range of execution start dates, open on the sides without a date.
+#[derive(Clone, Copy, Default, PartialEq, Debug)]
+pub struct DateRange {
+    pub start: Option<DateTime<Utc>>,
+    pub end: Option<DateTime<Utc>>,
+}
+
+impl DateRange {
+    pub fn new(start: Option<DateTime<Utc>>, end: Option<DateTime<Utc>>) -> Self {
+        DateRange { start, end }
+    }
+
+    pub fn contains(&self, date: DateTime<Utc>) -> bool {
+        !self.is_before_start(date) && self.end.is_none_or(|end| end >= date)
+    }
+
+    pub fn is_before_start(&self, date: DateTime<Utc>) -> bool {
+        self.start.is_some_and(|start| date < start)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
 pub struct StateMachineExecution {
     pub arn: String,
     pub machine_arn: String,
     pub name: String,
+    pub status: ExecutionStatus,
     pub start_date: DateTime<Utc>,
     pub input: Option<String>,
     pub output: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redrive_count: Option<i32>,
+    /// Only known once the execution is described.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub redrive_status: Option<RedriveStatus>,
+    /// Only known once the history of a failed execution is read.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_cause: Option<RootCause>,
+}
+
+/// Whether a failed execution can resume from the state that failed.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedriveStatus {
+    Redrivable,
+    /// Child execution of a Distributed Map, redriven through the map run of its parent.
+    RedrivableByMapRun,
+    NotRedrivable {
+        reason: Option<String>,
+    },
+}
+
+/// Why an execution failed, as recorded in its history.
+#[derive(Clone, Default, PartialEq, Debug, Serialize, Deserialize)]
+pub struct RootCause {
+    pub failed_state: Option<String>,
+    pub error: Option<String>,
+    pub cause: Option<String>,
+    /// Retries of the failed state used up before the execution failed.
+    pub retries_used: u32,
+}
+
+/// Longest cause printed next to an execution, causes are often whole stack traces.
+const MAX_PRINTED_CAUSE_LENGTH: usize = 80;
+
+impl RootCause {
+    /// Finds the state the execution failed in, with the error and cause it failed with.
+    /// Returns nothing unless the history ends with the execution failing, timing out or
+    /// being aborted.
+    pub fn from_history(events: &[HistoryEvent]) -> Option<RootCause> {
+        let end = events.last().filter(|event| {
+            matches!(
+                event.event_type.as_str(),
+                "ExecutionFailed" | "ExecutionTimedOut" | "ExecutionAborted"
+            )
+        })?;
+
+        let failed_state = events
+            .iter()
+            .rev()
+            .find_map(|event| event.state_name.clone());
+        let state_events: Vec<&HistoryEvent> = match &failed_state {
+            Some(failed_state) => {
+                let last_entered = events.iter().rposition(|event| {
+                    event.event_type.ends_with("StateEntered")
+                        && event.state_name.as_ref() == Some(failed_state)
+                });
+                events[last_entered.unwrap_or_default()..]
+                    .iter()
+                    .filter(|event| event.state_name.as_ref() == Some(failed_state))
+                    .collect()
+            }
+            None => vec![],
+        };
+        let state_failures: Vec<&HistoryEvent> = state_events
+            .into_iter()
+            .filter(|event| event.error.is_some())
+            .collect();
+        let last_failure = state_failures.last();
+
+        Some(RootCause {
+            error: end
+                .error
+                .clone()
+                .or_else(|| last_failure.and_then(|event| event.error.clone())),
+            cause: end
+                .cause
+                .clone()
+                .or_else(|| last_failure.and_then(|event| event.cause.clone())),
+            retries_used: state_failures.len().saturating_sub(1) as u32,
+            failed_state,
+        })
+    }
+}
+
+impl RootCause {
+    pub fn signature(&self) -> FailureSignature {
+        FailureSignature {
+            failed_state: self.failed_state.clone(),
+            error: self.error.clone(),
+            cause_pattern: self.cause.as_deref().map(cause_pattern),
+        }
+    }
+}
+
+impl fmt::Display for RootCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_failure(
+            f,
+            self.failed_state.as_deref(),
+            self.error.as_deref(),
+            self.cause.as_deref(),
+        )?;
+
+        match self.retries_used {
+            0 => {}
+            1 => write!(f, ", 1 retry used")?,
+            retries_used => write!(f, ", {} retries used", retries_used)?,
+        }
+
+        Ok(())
+    }
+}
+
+/// What failures share when they most likely have the same cause: the state, the error
+/// and the cause without the ids and numbers that vary from one execution to the other.
+#[derive(Clone, Default, PartialEq, Eq, Hash, Debug, Serialize)]
+pub struct FailureSignature {
+    pub failed_state: Option<String>,
+    pub error: Option<String>,
+    pub cause_pattern: Option<String>,
+}
+
+impl fmt::Display for FailureSignature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_failure(
+            f,
+            self.failed_state.as_deref(),
+            self.error.as_deref(),
+            self.cause_pattern.as_deref(),
+        )
+    }
+}
+
+/// Replacements applied in order, so ARNs and UUIDs are replaced whole before the words
+/// mixing letters and digits and the numbers left.
+static VARYING_PARTS: LazyLock<[(Regex, &str); 4]> = LazyLock::new(|| {
+    [
+        (Regex::new(r#"arn:aws[\w-]*:[^\s"',]+"#).unwrap(), "<arn>"),
+        (
+            Regex::new(
+                r"\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b",
+            )
+            .unwrap(),
+            "<id>",
+        ),
+        (
+            Regex::new(r"\b(?:[A-Za-z]+\d|\d+[A-Za-z])[0-9A-Za-z]*\b").unwrap(),
+            "<id>",
+        ),
+        (Regex::new(r"\d+").unwrap(), "<n>"),
+    ]
+});
+
+/// Cause with the ARNs, ids and numbers replaced by placeholders and the whitespace collapsed.
+fn cause_pattern(cause: &str) -> String {
+    let pattern = VARYING_PARTS
+        .iter()
+        .fold(cause.to_string(), |pattern, (regex, placeholder)| {
+            regex.replace_all(&pattern, *placeholder).into_owned()
+        });
+
+    pattern.split_whitespace().collect::<Vec<&str>>().join(" ")
+}
+
+fn write_failure(
+    f: &mut fmt::Formatter<'_>,
+    failed_state: Option<&str>,
+    error: Option<&str>,
+    cause: Option<&str>,
+) -> fmt::Result {
+    write!(
+        f,
+        "{}: {}",
+        failed_state.unwrap_or("(no state)"),
+        error.unwrap_or("(no error)")
+    )?;
+
+    if let Some(cause) = cause {
+        let cause = cause.split_whitespace().collect::<Vec<&str>>().join(" ");
+        match cause.char_indices().nth(MAX_PRINTED_CAUSE_LENGTH) {
+            Some((end, _)) => write!(f, " ({}...)", &cause[..end])?,
+            None => write!(f, " ({})", cause)?,
+        }
+    }
+
+    Ok(())
 }
 
 impl fmt::Display for StateMachineExecution {
@@ -34,10 +418,183 @@ impl fmt::Display for StateMachineExecution {
     }
 }
 
+impl Tabular for StateMachineExecution {
+    fn headers() -> Vec<&'static str> {
+        vec!["name", "status", "start_date", "arn"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.name.clone(),
+            self.status.to_string(),
+            self.start_date.to_rfc3339_opts(SecondsFormat::Secs, true),
+            self.arn.clone(),
+        ]
+    }
+}
+
+/// Longest execution name Step Functions accepts.
+const MAX_EXECUTION_NAME_LENGTH: usize = 80;
+
+impl StateMachineExecution {
+    /// Deterministic name for the retry of this execution, `<name>-retry-1` for an original
+    /// execution and `<name>-retry-<N+1>` for the retry of a retry. Names too long for the
+    /// suffix are cut short and end with a hash of the whole name instead, so two long names
+    /// sharing a prefix still get different retry names.
+    pub fn retry_name(&self) -> String {
+        let (base_name, attempt) = match self.name.rsplit_once("-retry-") {
+            Some((base_name, attempt)) => match attempt.parse::<u32>() {
+                Ok(attempt) => (base_name, attempt.saturating_add(1)),
+                Err(_) => (self.name.as_str(), 1),
+            },
+            None => (self.name.as_str(), 1),
+        };
+
+        let suffix = format!("-retry-{}", attempt);
+        if base_name.chars().count() + suffix.len() <= MAX_EXECUTION_NAME_LENGTH {
+            return format!("{}{}", base_name, suffix);
+        }
+
+        let hash = format!("-{:08x}", name_hash(base_name) as u32);
+        let prefix: String = base_name
+            .chars()
+            .take(MAX_EXECUTION_NAME_LENGTH - hash.len() - suffix.len())
+            .collect();
+
+        format!("{}{}{}", prefix, hash, suffix)
+    }
+}
+
+/// FNV-1a hash of a name, which unlike the hasher of the standard library stays the same
+/// across versions, so a resumed batch computes the same retry names.
+fn name_hash(name: &str) -> u64 {
+    name.bytes().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct ExecutionInput {
     pub machine_arn: String,
     pub input: String,
+    pub name: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum StartOutcome {
+    Started {
+        execution_arn: String,
+    },
+    /// An execution with the requested name was already started before.
+    AlreadyExists,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum RedriveOutcome {
+    Redriven,
+    /// Step Functions refused to redrive the execution, it has to be restarted instead.
+    NotRedrivable,
+}
+
+/// Event of the history of an execution, flattened from the details of its type.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct HistoryEvent {
+    pub id: i64,
+    pub previous_event_id: i64,
+    pub event_type: String,
+    pub timestamp: DateTime<Utc>,
+    /// State the event happened in, also set on the task and activity events of the state.
+    pub state_name: Option<String>,
+    pub input: Option<String>,
+    pub output: Option<String>,
+    pub error: Option<String>,
+    pub cause: Option<String>,
+}
+
+impl HistoryEvent {
+    pub fn has_data(&self) -> bool {
+        self.input.is_some()
+            || self.output.is_some()
+            || self.error.is_some()
+            || self.cause.is_some()
+    }
+}
+
+impl Tabular for HistoryEvent {
+    fn headers() -> Vec<&'static str> {
+        vec!["id", "timestamp", "type", "state"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        vec![
+            self.id.to_string(),
+            self.timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+            self.event_type.clone(),
+            self.state_name.clone().unwrap_or_default(),
+        ]
+    }
+}
+
+/// History event with the time elapsed since the execution started.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+pub struct TimelineEvent {
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+    pub elapsed_ms: i64,
+}
+
+impl TimelineEvent {
+    pub fn from_history(events: Vec<HistoryEvent>) -> Vec<TimelineEvent> {
+        let Some(start) = events.first().map(|event| event.timestamp) else {
+            return vec![];
+        };
+
+        events
+            .into_iter()
+            .map(|event| TimelineEvent {
+                elapsed_ms: (event.timestamp - start).num_milliseconds(),
+                event,
+            })
+            .collect()
+    }
+}
+
+impl Tabular for TimelineEvent {
+    fn headers() -> Vec<&'static str> {
+        vec!["id", "elapsed", "timestamp", "type", "state"]
+    }
+
+    fn row(&self) -> Vec<String> {
+        let mut row = self.event.row();
+        row.insert(
+            1,
+            format_elapsed(chrono::Duration::milliseconds(self.elapsed_ms)),
+        );
+        row
+    }
+}
+
+/// Timeline event with its input, output, error and cause in the table and csv columns too.
+#[derive(Clone, PartialEq, Debug, Serialize)]
+#[serde(transparent)]
+pub struct DetailedTimelineEvent(pub TimelineEvent);
+
+impl Tabular for DetailedTimelineEvent {
+    fn headers() -> Vec<&'static str> {
+        let mut headers = TimelineEvent::headers();
+        headers.extend(["input", "output", "error", "cause"]);
+        headers
+    }
+
+    fn row(&self) -> Vec<String> {
+        let event = &self.0.event;
+        let mut row = self.0.row();
+        row.extend(
+            [&event.input, &event.output, &event.error, &event.cause]
+                .map(|value| value.clone().unwrap_or_default()),
+        );
+        row
+    }
 }
 
 #[cfg(test)]
@@ -52,9 +609,13 @@ mod tests {
             arn: "dinosaur::arn".to_string(),
             machine_arn: "dinosaur_machine:arn".to_string(),
             name: "dinosaur".to_string(),
-            start_date: now.clone(),
+            status: ExecutionStatus::Failed,
+            start_date: now,
             input: Some("{}".to_string()),
             output: Some("{}".to_string()),
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
         };
 
         assert_eq!(
@@ -71,8 +632,377 @@ mod tests {
         let machine = StateMachine {
             arn: "dinosaur::arn".to_string(),
             name: "dinosaur".to_string(),
+            details: None,
         };
 
         assert_eq!(format!("{}", machine), "dinosaur");
     }
+
+    #[test]
+    fn test_state_machine_serialization() {
+        let mut machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&machine).unwrap(),
+            serde_json::json!({"arn": "dinosaur::arn", "name": "dinosaur"})
+        );
+
+        machine.details = Some(StateMachineDetails {
+            machine_type: MachineType::Standard,
+            status: Some(MachineStatus::Active),
+            creation_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            role_arn: "dinosaur::role".to_string(),
+            logging: None,
+            tracing_enabled: false,
+            definition: "{}".to_string(),
+        });
+
+        assert_eq!(
+            serde_json::to_value(&machine).unwrap(),
+            serde_json::json!({
+                "arn": "dinosaur::arn",
+                "name": "dinosaur",
+                "type": "STANDARD",
+                "status": "ACTIVE",
+                "creation_date": "1989-09-30T22:10:32Z",
+                "role_arn": "dinosaur::role",
+                "logging": null,
+                "tracing_enabled": false,
+                "definition": "{}"
+            })
+        );
+    }
+
+    #[test]
+    fn test_name_filter_parse() {
+        assert_eq!(NameFilter::parse("  ").unwrap(), None);
+        assert_eq!(
+            NameFilter::parse("orders").unwrap(),
+            Some(NameFilter::Prefix("orders".to_string()))
+        );
+        assert_eq!(
+            NameFilter::parse("/orders-.*/").unwrap(),
+            Some(NameFilter::Regex(Regex::new("orders-.*").unwrap()))
+        );
+        assert_eq!(
+            NameFilter::parse("/").unwrap(),
+            Some(NameFilter::Prefix("/".to_string()))
+        );
+        assert!(NameFilter::parse("/orders-(/").is_err());
+    }
+
+    #[test]
+    fn test_name_filter_matches() {
+        let prefix = NameFilter::Prefix("orders".to_string());
+        let regex = NameFilter::Regex(Regex::new("^(orders|payments)-v[0-9]+$").unwrap());
+
+        assert!(prefix.matches("orders-v2"));
+        assert!(!prefix.matches("legacy-orders"));
+        assert!(regex.matches("payments-v10"));
+        assert!(!regex.matches("payments-v10-old"));
+    }
+
+    #[test]
+    fn test_date_range_contains() {
+        let start = DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let end = DateTime::parse_from_rfc3339("1989-10-01T22:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let before = DateTime::parse_from_rfc3339("1989-09-29T22:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert!(DateRange::default().contains(before));
+        assert!(DateRange::new(Some(start), Some(end)).contains(start));
+        assert!(DateRange::new(Some(start), Some(end)).contains(end));
+        assert!(!DateRange::new(Some(start), None).contains(before));
+        assert!(DateRange::new(None, Some(end)).contains(before));
+        assert!(!DateRange::new(None, Some(before)).contains(end));
+        assert!(DateRange::new(Some(start), None).is_before_start(before));
+        assert!(!DateRange::new(None, Some(end)).is_before_start(before));
+    }
+
+    #[test]
+    fn test_execution_status_print_format() {
+        assert_eq!(
+            ExecutionStatus::ALL
+                .iter()
+                .map(|status| status.to_string())
+                .collect::<Vec<String>>(),
+            vec![
+                "RUNNING",
+                "SUCCEEDED",
+                "FAILED",
+                "TIMED_OUT",
+                "ABORTED",
+                "PENDING_REDRIVE"
+            ]
+        );
+        assert_eq!(
+            serde_json::to_value(ExecutionStatus::TimedOut).unwrap(),
+            serde_json::json!("TIMED_OUT")
+        );
+    }
+
+    #[test]
+    fn test_state_machine_execution_retry_name() {
+        let execution = |name: &str| StateMachineExecution {
+            arn: "dinosaur::arn".to_string(),
+            machine_arn: "dinosaur_machine:arn".to_string(),
+            name: name.to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: Utc::now(),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
+        };
+
+        assert_eq!(execution("dinosaur").retry_name(), "dinosaur-retry-1");
+        assert_eq!(
+            execution("dinosaur-retry-1").retry_name(),
+            "dinosaur-retry-2"
+        );
+        assert_eq!(
+            execution("dinosaur-retry-egg").retry_name(),
+            "dinosaur-retry-egg-retry-1"
+        );
+
+        let long_name = execution(&"a".repeat(80)).retry_name();
+        assert_eq!(long_name.len(), 80);
+        assert!(long_name.starts_with(&"a".repeat(63)));
+        assert!(long_name.ends_with("-retry-1"));
+        assert_eq!(execution(&"a".repeat(80)).retry_name(), long_name);
+
+        let other_long_name = execution(&format!("{}b", "a".repeat(79))).retry_name();
+        assert_eq!(other_long_name.len(), 80);
+        assert_ne!(other_long_name, long_name);
+
+        let retry_of_long_name = execution(&long_name).retry_name();
+        assert_eq!(retry_of_long_name, format!("{}-retry-2", &long_name[..72]));
+    }
+
+    #[test]
+    fn test_state_machine_execution_serialization() {
+        let execution = StateMachineExecution {
+            arn: "dinosaur::arn".to_string(),
+            machine_arn: "dinosaur_machine:arn".to_string(),
+            name: "dinosaur".to_string(),
+            status: ExecutionStatus::Failed,
+            start_date: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            input: None,
+            output: None,
+            redrive_count: None,
+            redrive_status: None,
+            root_cause: None,
+        };
+
+        assert_eq!(
+            serde_json::to_value(&execution).unwrap(),
+            serde_json::json!({
+                "arn": "dinosaur::arn",
+                "machine_arn": "dinosaur_machine:arn",
+                "name": "dinosaur",
+                "status": "FAILED",
+                "start_date": "1989-09-30T22:10:32Z",
+                "input": null,
+                "output": null
+            })
+        );
+        assert_eq!(
+            execution.row(),
+            vec![
+                "dinosaur",
+                "FAILED",
+                "1989-09-30T22:10:32Z",
+                "dinosaur::arn"
+            ]
+        );
+    }
+
+    fn history_event(
+        id: i64,
+        event_type: &str,
+        state_name: Option<&str>,
+        error: Option<&str>,
+    ) -> HistoryEvent {
+        HistoryEvent {
+            id,
+            previous_event_id: id - 1,
+            event_type: event_type.to_string(),
+            timestamp: DateTime::parse_from_rfc3339("1989-09-30T22:10:32Z")
+                .unwrap()
+                .with_timezone(&Utc),
+            state_name: state_name.map(String::from),
+            input: None,
+            output: None,
+            error: error.map(String::from),
+            cause: error.map(|error| format!("{} in Charge", error)),
+        }
+    }
+
+    #[test]
+    fn test_timeline_event_rows() {
+        let mut events = vec![
+            history_event(1, "ExecutionStarted", None, None),
+            history_event(2, "TaskFailed", Some("Charge"), Some("States.Timeout")),
+        ];
+        events[1].timestamp += chrono::Duration::milliseconds(1500);
+        let timeline = TimelineEvent::from_history(events);
+
+        assert_eq!(timeline[1].elapsed_ms, 1500);
+        assert_eq!(
+            timeline[1].row(),
+            vec![
+                "2",
+                "+1.500s",
+                "1989-09-30T22:10:33.500Z",
+                "TaskFailed",
+                "Charge"
+            ]
+        );
+        assert_eq!(
+            DetailedTimelineEvent(timeline[1].clone()).row()[5..],
+            ["", "", "States.Timeout", "States.Timeout in Charge"]
+        );
+        assert_eq!(
+            serde_json::to_value(DetailedTimelineEvent(timeline[1].clone())).unwrap()["elapsed_ms"],
+            1500
+        );
+    }
+
+    #[test]
+    fn test_root_cause_from_history() {
+        let events = vec![
+            history_event(1, "ExecutionStarted", None, None),
+            history_event(2, "TaskStateEntered", Some("Reserve"), None),
+            history_event(3, "TaskStateExited", Some("Reserve"), None),
+            history_event(4, "TaskStateEntered", Some("Charge"), None),
+            history_event(5, "TaskScheduled", Some("Charge"), None),
+            history_event(6, "TaskFailed", Some("Charge"), Some("States.Timeout")),
+            history_event(7, "TaskScheduled", Some("Charge"), None),
+            history_event(8, "TaskFailed", Some("Charge"), Some("States.Timeout")),
+            history_event(9, "TaskScheduled", Some("Charge"), None),
+            history_event(10, "TaskFailed", Some("Charge"), Some("Card.Declined")),
+            HistoryEvent {
+                cause: None,
+                ..history_event(11, "ExecutionFailed", None, Some("Card.Declined"))
+            },
+        ];
+
+        assert_eq!(
+            RootCause::from_history(&events),
+            Some(RootCause {
+                failed_state: Some("Charge".to_string()),
+                error: Some("Card.Declined".to_string()),
+                cause: Some("Card.Declined in Charge".to_string()),
+                retries_used: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_root_cause_of_timed_out_execution() {
+        let events = vec![
+            history_event(1, "ExecutionStarted", None, None),
+            history_event(2, "WaitStateEntered", Some("Cooldown"), None),
+            HistoryEvent {
+                cause: None,
+                ..history_event(3, "ExecutionTimedOut", None, Some("States.Timeout"))
+            },
+        ];
+
+        assert_eq!(
+            RootCause::from_history(&events),
+            Some(RootCause {
+                failed_state: Some("Cooldown".to_string()),
+                error: Some("States.Timeout".to_string()),
+                cause: None,
+                retries_used: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_no_root_cause_unless_execution_failed() {
+        let events = vec![
+            history_event(1, "ExecutionStarted", None, None),
+            history_event(2, "TaskStateEntered", Some("Charge"), None),
+            history_event(3, "TaskFailed", Some("Charge"), Some("States.Timeout")),
+            history_event(4, "ExecutionRedriven", None, None),
+        ];
+
+        assert_eq!(RootCause::from_history(&events), None);
+        assert_eq!(RootCause::from_history(&[]), None);
+    }
+
+    #[test]
+    fn test_root_cause_print_format() {
+        let root_cause = RootCause {
+            failed_state: Some("Charge".to_string()),
+            error: Some("Lambda.Unknown".to_string()),
+            cause: Some(format!("The function crashed:\n{}", "x".repeat(100))),
+            retries_used: 1,
+        };
+
+        assert_eq!(
+            root_cause.to_string(),
+            format!(
+                "Charge: Lambda.Unknown (The function crashed: {}...), 1 retry used",
+                "x".repeat(58)
+            )
+        );
+        assert_eq!(RootCause::default().to_string(), "(no state): (no error)");
+    }
+
+    #[test]
+    fn test_failure_signature_strips_ids() {
+        let root_cause = |cause: &str| RootCause {
+            failed_state: Some("Charge".to_string()),
+            error: Some("Lambda.Unknown".to_string()),
+            cause: Some(cause.to_string()),
+            retries_used: 0,
+        };
+
+        let signature = root_cause(
+            "Order 1234 failed (RequestId: 0f8fad5b-d9cb-469f-a165-70867728950e, trace 5e8d9c2a)",
+        )
+        .signature();
+
+        assert_eq!(
+            signature,
+            root_cause(
+                "Order 98 failed  (RequestId: 7c9e6679-7425-40de-944b-e07fc1f90ae7, trace a4b11f09)"
+            )
+            .signature()
+        );
+        assert_eq!(
+            signature.cause_pattern,
+            Some("Order <n> failed (RequestId: <id>, trace <id>)".to_string())
+        );
+        assert_eq!(
+            root_cause(
+                "User arn:aws:sts::123456789012:assumed-role/charge/1 is not authorized to perform states:StartExecution"
+            )
+            .signature()
+            .cause_pattern,
+            Some("User <arn> is not authorized to perform states:StartExecution".to_string())
+        );
+        assert_ne!(signature, root_cause("Card declined").signature());
+        assert_eq!(
+            signature.to_string(),
+            "Charge: Lambda.Unknown (Order <n> failed (RequestId: <id>, trace <id>))"
+        );
+    }
 }
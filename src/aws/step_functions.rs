@@ -1,26 +1,49 @@
-use crate::aws::model::{ExecutionInput, StateMachine, StateMachineExecution};
-use aws_sdk_sfn::Error;
+use crate::aws::model::{
+    ClientOptions, DateRange, ExecutionInput, ExecutionStatus, HistoryEvent, LoggingConfiguration,
+    MachineStatus, MachineType, NameFilter, RedriveOutcome, RedriveStatus, RootCause, StartOutcome,
+    StateMachine, StateMachineDetails, StateMachineExecution,
+};
+use crate::error::PawError;
+use aws_sdk_sfn::types::{
+    ExecutionRedriveStatus as SdkRedriveStatus, ExecutionStatus as SdkExecutionStatus,
+    HistoryEvent as SdkHistoryEvent, LoggingConfiguration as SdkLoggingConfiguration,
+};
 use chrono::{DateTime, TimeZone, Utc};
+#[cfg(test)]
+pub use external_client::MockStepFunctionsClient;
 #[double]
 use external_client::StepFunctionsClient;
 use mockall_double::double;
+use std::collections::HashMap;
 
-#[allow(dead_code)]
+// The client returns the errors of the SDK as they are, paw boxes them in `PawError`.
+#[allow(dead_code, clippy::result_large_err)]
 mod external_client {
-    use aws_config::from_env;
+    use crate::aws::model::{ClientOptions, ExecutionStatus};
+    use aws_config::default_provider::{
+        credentials::DefaultCredentialsChain, region::DefaultRegionChain,
+    };
+    use aws_config::meta::region::RegionProviderChain;
+    use aws_config::{BehaviorVersion, Region};
     use aws_sdk_sfn::{
-        error::{
-            DescribeExecutionError, ListExecutionsError, ListStateMachinesError,
-            StartExecutionError,
-        },
-        model::ExecutionStatus,
-        output::{
-            DescribeExecutionOutput, ListExecutionsOutput, ListStateMachinesOutput,
-            StartExecutionOutput,
+        config::Builder as ConfigBuilder,
+        error::SdkError,
+        operation::{
+            describe_execution::{DescribeExecutionError, DescribeExecutionOutput},
+            describe_state_machine::{DescribeStateMachineError, DescribeStateMachineOutput},
+            describe_state_machine_for_execution::{
+                DescribeStateMachineForExecutionError, DescribeStateMachineForExecutionOutput,
+            },
+            get_execution_history::{GetExecutionHistoryError, GetExecutionHistoryOutput},
+            list_executions::{ListExecutionsError, ListExecutionsOutput},
+            list_state_machines::{ListStateMachinesError, ListStateMachinesOutput},
+            redrive_execution::{RedriveExecutionError, RedriveExecutionOutput},
+            start_execution::{StartExecutionError, StartExecutionOutput},
+            stop_execution::{StopExecutionError, StopExecutionOutput},
         },
+        types::ExecutionStatus as SdkExecutionStatus,
         Client,
     };
-    use aws_smithy_http::result::SdkError;
 
     pub struct StepFunctionsClient {
         pub client: Client,
@@ -28,36 +51,90 @@ mod external_client {
 
     #[cfg_attr(test, mockall::automock)]
     impl StepFunctionsClient {
-        pub async fn new() -> Self {
-            let config = from_env().load().await;
+        pub async fn new(options: ClientOptions) -> Self {
+            let mut region_chain = DefaultRegionChain::builder();
+            let mut credentials_chain = DefaultCredentialsChain::builder();
+            if let Some(profile) = &options.profile {
+                region_chain = region_chain.profile_name(profile);
+                credentials_chain = credentials_chain.profile_name(profile);
+            }
+
+            // The credentials chain looks the region up on its own, through IMDS off EC2,
+            // unless it is given the one already resolved.
+            let region = RegionProviderChain::first_try(options.region.map(Region::new))
+                .or_else(region_chain.build())
+                .region()
+                .await;
+            if let Some(region) = &region {
+                credentials_chain = credentials_chain.region(region.clone());
+            }
+            let config = aws_config::defaults(BehaviorVersion::latest())
+                .region(region)
+                .credentials_provider(credentials_chain.build().await)
+                .load()
+                .await;
+
+            let mut builder = ConfigBuilder::from(&config);
+            if let Some(endpoint_url) = options.endpoint_url {
+                builder = builder.endpoint_url(endpoint_url.to_string());
+            }
+
             StepFunctionsClient {
-                client: Client::new(&config),
+                client: Client::from_conf(builder.build()),
             }
         }
 
         pub async fn list_state_machines(
             &self,
+            next_token: Option<String>,
         ) -> Result<ListStateMachinesOutput, SdkError<ListStateMachinesError>> {
-            self.client.list_state_machines().send().await
+            self.client
+                .list_state_machines()
+                .max_results(1000)
+                .set_next_token(next_token)
+                .send()
+                .await
+        }
+
+        pub async fn describe_state_machine(
+            &self,
+            state_machine_arn: String,
+        ) -> Result<DescribeStateMachineOutput, SdkError<DescribeStateMachineError>> {
+            self.client
+                .describe_state_machine()
+                .state_machine_arn(state_machine_arn)
+                .send()
+                .await
+        }
+
+        pub async fn describe_state_machine_for_execution(
+            &self,
+            execution_arn: String,
+        ) -> Result<
+            DescribeStateMachineForExecutionOutput,
+            SdkError<DescribeStateMachineForExecutionError>,
+        > {
+            self.client
+                .describe_state_machine_for_execution()
+                .execution_arn(execution_arn)
+                .send()
+                .await
         }
 
-        pub async fn list_failed_executions(
+        pub async fn list_executions(
             &self,
             state_machine_arn: String,
+            status: Option<ExecutionStatus>,
             next_token: Option<String>,
         ) -> Result<ListExecutionsOutput, SdkError<ListExecutionsError>> {
-            let mut req = self
-                .client
+            self.client
                 .list_executions()
                 .state_machine_arn(state_machine_arn)
                 .max_results(1000)
-                .status_filter(ExecutionStatus::Failed);
-
-            if next_token.is_some() {
-                req = req.next_token(next_token.unwrap());
-            }
-
-            req.send().await
+                .set_status_filter(status.map(|status| SdkExecutionStatus::from(status.as_str())))
+                .set_next_token(next_token)
+                .send()
+                .await
         }
 
         pub async fn describe_execution(
@@ -71,98 +148,289 @@ mod external_client {
                 .await
         }
 
+        pub async fn get_execution_history(
+            &self,
+            execution_arn: String,
+            next_token: Option<String>,
+        ) -> Result<GetExecutionHistoryOutput, SdkError<GetExecutionHistoryError>> {
+            self.client
+                .get_execution_history()
+                .execution_arn(execution_arn)
+                .max_results(1000)
+                .include_execution_data(true)
+                .set_next_token(next_token)
+                .send()
+                .await
+        }
+
+        /// Last events of the history, newest first.
+        pub async fn get_latest_execution_history(
+            &self,
+            execution_arn: String,
+            max_results: i32,
+        ) -> Result<GetExecutionHistoryOutput, SdkError<GetExecutionHistoryError>> {
+            self.client
+                .get_execution_history()
+                .execution_arn(execution_arn)
+                .max_results(max_results)
+                .reverse_order(true)
+                .include_execution_data(false)
+                .send()
+                .await
+        }
+
         pub async fn start_execution(
             &self,
             state_machine_arn: String,
             input: String,
+            name: Option<String>,
         ) -> Result<StartExecutionOutput, SdkError<StartExecutionError>> {
             self.client
                 .start_execution()
                 .state_machine_arn(state_machine_arn)
                 .input(input)
+                .set_name(name)
+                .send()
+                .await
+        }
+
+        pub async fn redrive_execution(
+            &self,
+            execution_arn: String,
+        ) -> Result<RedriveExecutionOutput, SdkError<RedriveExecutionError>> {
+            self.client
+                .redrive_execution()
+                .execution_arn(execution_arn)
+                .send()
+                .await
+        }
+
+        pub async fn stop_execution(
+            &self,
+            execution_arn: String,
+            error: Option<String>,
+            cause: Option<String>,
+        ) -> Result<StopExecutionOutput, SdkError<StopExecutionError>> {
+            self.client
+                .stop_execution()
+                .execution_arn(execution_arn)
+                .set_error(error)
+                .set_cause(cause)
                 .send()
                 .await
         }
     }
 }
 
+/// Events read from the end of a history to find the root cause of a failure, enough to cover
+/// the retries of the state that failed.
+const ROOT_CAUSE_EVENTS: i32 = 100;
+
 pub struct StepFunctionsMachine {
     client: StepFunctionsClient,
 }
 
 impl StepFunctionsMachine {
-    pub async fn new() -> StepFunctionsMachine {
+    pub async fn new(options: ClientOptions) -> StepFunctionsMachine {
         StepFunctionsMachine {
-            client: StepFunctionsClient::new().await,
+            client: StepFunctionsClient::new(options).await,
+        }
+    }
+
+    /// Machine over a mocked client, for the tests of the actions.
+    #[cfg(test)]
+    pub fn with_client(client: StepFunctionsClient) -> StepFunctionsMachine {
+        StepFunctionsMachine { client }
+    }
+
+    pub async fn list_machines(
+        &self,
+        filter: Option<&NameFilter>,
+    ) -> Result<Vec<StateMachine>, PawError> {
+        let mut machines: Vec<StateMachine> = vec![];
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let raw_machines = self.client.list_state_machines(next_token).await?;
+
+            next_token = raw_machines.next_token;
+
+            let mut partial_machines = raw_machines
+                .state_machines
+                .into_iter()
+                .map(|machine| StateMachine {
+                    arn: machine.state_machine_arn,
+                    name: machine.name,
+                    details: None,
+                })
+                .filter(|machine| filter.is_none_or(|filter| filter.matches(&machine.name)))
+                .collect::<Vec<StateMachine>>();
+
+            machines.append(&mut partial_machines);
+
+            if next_token.is_none() {
+                break;
+            }
         }
+
+        Ok(machines)
     }
 
-    pub async fn list_machines(&self) -> Result<Vec<StateMachine>, Error> {
-        let machines = self.client.list_state_machines().await?;
-        let machine_names = machines
-            .state_machines
-            .unwrap_or_default()
+    /// Describes the machine of an ARN, and looks a name up in the machines of the account.
+    pub async fn find_machine(&self, name_or_arn: &str) -> Result<Option<StateMachine>, PawError> {
+        if name_or_arn.starts_with("arn:") {
+            return match self.describe_state_machine(name_or_arn.to_string()).await {
+                Ok(machine) => Ok(Some(machine)),
+                Err(PawError::Sdk(error))
+                    if matches!(*error, aws_sdk_sfn::Error::StateMachineDoesNotExist(_)) =>
+                {
+                    Ok(None)
+                }
+                Err(error) => Err(error),
+            };
+        }
+
+        let machines = self.list_machines(None).await?;
+
+        Ok(machines
             .into_iter()
-            .map(|machine| StateMachine {
-                arn: machine.state_machine_arn.unwrap(),
-                name: machine.name.unwrap(),
-            })
-            .collect::<Vec<StateMachine>>();
+            .find(|machine| machine.name == name_or_arn))
+    }
 
-        Ok(machine_names)
+    pub async fn describe_state_machine(
+        &self,
+        state_machine_arn: String,
+    ) -> Result<StateMachine, PawError> {
+        let raw_machine = self
+            .client
+            .describe_state_machine(state_machine_arn)
+            .await?;
+
+        Ok(StateMachine {
+            arn: raw_machine.state_machine_arn,
+            name: raw_machine.name,
+            details: Some(StateMachineDetails {
+                machine_type: required(
+                    MachineType::ALL
+                        .into_iter()
+                        .find(|known_type| known_type.as_str() == raw_machine.r#type.as_str()),
+                    "type",
+                )?,
+                status: raw_machine.status.and_then(|status| {
+                    MachineStatus::ALL
+                        .into_iter()
+                        .find(|known_status| known_status.as_str() == status.as_str())
+                }),
+                creation_date: required(
+                    StepFunctionsMachine::convert_date_time(raw_machine.creation_date),
+                    "creationDate",
+                )?,
+                role_arn: raw_machine.role_arn,
+                logging: raw_machine
+                    .logging_configuration
+                    .map(StepFunctionsMachine::convert_logging),
+                tracing_enabled: raw_machine
+                    .tracing_configuration
+                    .is_some_and(|tracing| tracing.enabled),
+                definition: raw_machine.definition,
+            }),
+        })
+    }
+
+    /// Definition the execution ran, which may be older than the current definition of its
+    /// machine.
+    pub async fn execution_definition(&self, execution_arn: String) -> Result<String, PawError> {
+        let raw_machine = self
+            .client
+            .describe_state_machine_for_execution(execution_arn)
+            .await?;
+
+        Ok(raw_machine.definition)
+    }
+
+    /// Lists the executions of the machine started inside `range`, newest first. An empty
+    /// list of statuses lists the executions in any status.
+    pub async fn list_executions(
+        &self,
+        machine: &StateMachine,
+        statuses: &[ExecutionStatus],
+        range: &DateRange,
+    ) -> Result<Vec<StateMachineExecution>, PawError> {
+        if statuses.is_empty() {
+            return self.list_executions_by_status(machine, None, range).await;
+        }
+
+        let mut executions: Vec<StateMachineExecution> = vec![];
+        for status in ExecutionStatus::ALL
+            .into_iter()
+            .filter(|status| statuses.contains(status))
+        {
+            let mut status_executions = self
+                .list_executions_by_status(machine, Some(status), range)
+                .await?;
+            executions.append(&mut status_executions);
+        }
+        executions.sort_by_key(|execution| std::cmp::Reverse(execution.start_date));
+
+        Ok(executions)
     }
 
-    pub async fn list_failed_executions(
+    async fn list_executions_by_status(
         &self,
         machine: &StateMachine,
-        start_date: Option<DateTime<Utc>>,
-        end_date: Option<DateTime<Utc>>,
-    ) -> Result<Vec<StateMachineExecution>, Error> {
+        status: Option<ExecutionStatus>,
+        range: &DateRange,
+    ) -> Result<Vec<StateMachineExecution>, PawError> {
         let mut executions: Vec<StateMachineExecution> = vec![];
         let mut next_token: Option<String> = None;
 
         loop {
             let raw_executions = self
                 .client
-                .list_failed_executions(machine.arn.clone(), next_token)
+                .list_executions(machine.arn.clone(), status, next_token)
                 .await?;
 
             next_token = raw_executions.next_token;
 
-            let mut partial_executions = raw_executions
+            let page_executions = raw_executions
                 .executions
-                .unwrap_or_default()
                 .into_iter()
-                .map(|execution| StateMachineExecution {
-                    arn: execution.execution_arn.unwrap(),
-                    machine_arn: execution.state_machine_arn.unwrap(),
-                    name: execution.name.unwrap(),
-                    start_date: StepFunctionsMachine::convert_date_time(
-                        execution.start_date.unwrap(),
-                    ),
-                    input: Option::None,
-                    output: Option::None,
-                })
-                .filter(|execution| {
-                    let start = start_date.unwrap_or(Utc::now());
-                    let end = end_date.unwrap_or(Utc::now());
-                    let execution_time = execution.start_date;
-
-                    if start_date.is_some() && end_date.is_some() {
-                        return start <= execution_time && end >= execution_time;
-                    } else if start_date.is_some() {
-                        return start <= execution_time;
-                    } else if end_date.is_some() {
-                        return end >= execution_time;
-                    }
-
-                    true
+                .map(|execution| {
+                    Ok(StateMachineExecution {
+                        arn: execution.execution_arn,
+                        machine_arn: execution.state_machine_arn,
+                        name: execution.name,
+                        status: required(
+                            StepFunctionsMachine::convert_status(execution.status),
+                            "status",
+                        )?,
+                        start_date: required(
+                            StepFunctionsMachine::convert_date_time(execution.start_date),
+                            "startDate",
+                        )?,
+                        input: Option::None,
+                        output: Option::None,
+                        redrive_count: execution.redrive_count,
+                        redrive_status: Option::None,
+                        root_cause: Option::None,
+                    })
                 })
+                .collect::<Result<Vec<StateMachineExecution>, PawError>>()?;
+
+            // Executions come newest first, so once a page reaches the start of the range
+            // every following page is older and can be skipped.
+            let reached_start = page_executions
+                .iter()
+                .any(|execution| range.is_before_start(execution.start_date));
+
+            let mut partial_executions = page_executions
+                .into_iter()
+                .filter(|execution| range.contains(execution.start_date))
                 .collect::<Vec<StateMachineExecution>>();
 
             executions.append(&mut partial_executions);
 
-            if next_token.is_none() {
+            if next_token.is_none() || reached_start {
                 break;
             }
         }
@@ -173,77 +441,401 @@ impl StepFunctionsMachine {
     pub async fn describe_execution(
         &self,
         execution_arn: String,
-    ) -> Result<StateMachineExecution, Error> {
+    ) -> Result<StateMachineExecution, PawError> {
         let raw_execution = self.client.describe_execution(execution_arn).await?;
 
         let execution = StateMachineExecution {
-            arn: raw_execution.execution_arn.unwrap(),
-            machine_arn: raw_execution.state_machine_arn.unwrap(),
-            name: raw_execution.name.unwrap(),
-            start_date: StepFunctionsMachine::convert_date_time(raw_execution.start_date.unwrap()),
+            arn: raw_execution.execution_arn,
+            machine_arn: raw_execution.state_machine_arn,
+            name: required(raw_execution.name, "name")?,
+            status: required(
+                StepFunctionsMachine::convert_status(raw_execution.status),
+                "status",
+            )?,
+            start_date: required(
+                StepFunctionsMachine::convert_date_time(raw_execution.start_date),
+                "startDate",
+            )?,
             input: raw_execution.input,
             output: raw_execution.output,
+            redrive_count: raw_execution.redrive_count,
+            redrive_status: raw_execution.redrive_status.map(|status| match status {
+                SdkRedriveStatus::Redrivable => RedriveStatus::Redrivable,
+                SdkRedriveStatus::RedrivableByMapRun => RedriveStatus::RedrivableByMapRun,
+                _ => RedriveStatus::NotRedrivable {
+                    reason: raw_execution.redrive_status_reason,
+                },
+            }),
+            root_cause: Option::None,
         };
 
         Ok(execution)
     }
 
-    pub async fn start_execution(&self, input: ExecutionInput) -> Result<(), Error> {
+    /// Returns every event of the execution, oldest first.
+    pub async fn get_execution_history(
+        &self,
+        execution_arn: String,
+    ) -> Result<Vec<HistoryEvent>, PawError> {
+        let mut events: Vec<HistoryEvent> = vec![];
+        let mut next_token: Option<String> = None;
+
+        loop {
+            let raw_history = self
+                .client
+                .get_execution_history(execution_arn.clone(), next_token)
+                .await?;
+
+            next_token = raw_history.next_token;
+
+            let mut partial_events = raw_history
+                .events
+                .into_iter()
+                .map(StepFunctionsMachine::convert_event)
+                .collect::<Result<Vec<HistoryEvent>, PawError>>()?;
+
+            events.append(&mut partial_events);
+
+            if next_token.is_none() {
+                break;
+            }
+        }
+
+        StepFunctionsMachine::assign_state_names(&mut events);
+
+        Ok(events)
+    }
+
+    /// Reads the last events of a failed execution to find why it failed, a single request
+    /// whatever the length of its history.
+    pub async fn find_root_cause(
+        &self,
+        execution_arn: String,
+    ) -> Result<Option<RootCause>, PawError> {
+        let raw_history = self
+            .client
+            .get_latest_execution_history(execution_arn, ROOT_CAUSE_EVENTS)
+            .await?;
+
+        let mut events = raw_history
+            .events
+            .into_iter()
+            .rev()
+            .map(StepFunctionsMachine::convert_event)
+            .collect::<Result<Vec<HistoryEvent>, PawError>>()?;
+        StepFunctionsMachine::assign_state_names(&mut events);
+
+        Ok(RootCause::from_history(&events))
+    }
+
+    pub async fn start_execution(&self, input: ExecutionInput) -> Result<StartOutcome, PawError> {
+        let started_execution = self
+            .client
+            .start_execution(input.machine_arn, input.input, input.name)
+            .await;
+
+        match started_execution {
+            Ok(started_execution) => Ok(StartOutcome::Started {
+                execution_arn: started_execution.execution_arn,
+            }),
+            Err(error) => match PawError::from(error) {
+                PawError::Sdk(error)
+                    if matches!(*error, aws_sdk_sfn::Error::ExecutionAlreadyExists(_)) =>
+                {
+                    Ok(StartOutcome::AlreadyExists)
+                }
+                error => Err(error),
+            },
+        }
+    }
+
+    /// Restarts a failed execution from the state that failed, keeping its ARN.
+    pub async fn redrive_execution(
+        &self,
+        execution_arn: String,
+    ) -> Result<RedriveOutcome, PawError> {
+        match self.client.redrive_execution(execution_arn).await {
+            Ok(_) => Ok(RedriveOutcome::Redriven),
+            Err(error) => match PawError::from(error) {
+                PawError::Sdk(error)
+                    if matches!(*error, aws_sdk_sfn::Error::ExecutionNotRedrivable(_)) =>
+                {
+                    Ok(RedriveOutcome::NotRedrivable)
+                }
+                error => Err(error),
+            },
+        }
+    }
+
+    pub async fn stop_execution(
+        &self,
+        execution_arn: String,
+        error: Option<String>,
+        cause: Option<String>,
+    ) -> Result<(), PawError> {
         self.client
-            .start_execution(input.machine_arn, input.input)
+            .stop_execution(execution_arn, error, cause)
             .await?;
 
         Ok(())
     }
 
-    fn convert_date_time(date: aws_smithy_types::DateTime) -> DateTime<Utc> {
-        Utc.from_utc_datetime(
-            &DateTime::from_timestamp(date.secs(), date.subsec_nanos())
-                .unwrap()
-                .naive_utc(),
-        )
+    fn convert_event(event: SdkHistoryEvent) -> Result<HistoryEvent, PawError> {
+        let state_name = [
+            event
+                .state_entered_event_details
+                .as_ref()
+                .map(|details| details.name.clone()),
+            event
+                .state_exited_event_details
+                .as_ref()
+                .map(|details| details.name.clone()),
+            event
+                .evaluation_failed_event_details
+                .as_ref()
+                .map(|details| details.state.clone()),
+        ]
+        .into_iter()
+        .flatten()
+        .next();
+
+        let input = [
+            event
+                .execution_started_event_details
+                .and_then(|details| details.input),
+            event
+                .state_entered_event_details
+                .and_then(|details| details.input),
+            event
+                .task_scheduled_event_details
+                .map(|details| details.parameters),
+            event
+                .lambda_function_scheduled_event_details
+                .and_then(|details| details.input),
+            event
+                .activity_scheduled_event_details
+                .and_then(|details| details.input),
+        ]
+        .into_iter()
+        .flatten()
+        .next();
+
+        let output = [
+            event
+                .execution_succeeded_event_details
+                .and_then(|details| details.output),
+            event
+                .state_exited_event_details
+                .and_then(|details| details.output),
+            event
+                .task_succeeded_event_details
+                .and_then(|details| details.output),
+            event
+                .lambda_function_succeeded_event_details
+                .and_then(|details| details.output),
+            event
+                .activity_succeeded_event_details
+                .and_then(|details| details.output),
+        ]
+        .into_iter()
+        .flatten()
+        .next();
+
+        let (error, cause) = [
+            event
+                .execution_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .execution_aborted_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .execution_timed_out_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .task_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .task_timed_out_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .task_start_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .task_submit_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .lambda_function_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .lambda_function_timed_out_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .lambda_function_start_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .lambda_function_schedule_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .activity_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .activity_timed_out_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .activity_schedule_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .map_run_failed_event_details
+                .map(|details| (details.error, details.cause)),
+            event
+                .evaluation_failed_event_details
+                .map(|details| (details.error, details.cause)),
+        ]
+        .into_iter()
+        .flatten()
+        .next()
+        .unwrap_or_default();
+
+        Ok(HistoryEvent {
+            id: event.id,
+            previous_event_id: event.previous_event_id,
+            event_type: event.r#type.as_str().to_string(),
+            timestamp: required(
+                StepFunctionsMachine::convert_date_time(event.timestamp),
+                "timestamp",
+            )?,
+            state_name,
+            input,
+            output,
+            error,
+            cause,
+        })
+    }
+
+    /// Only the state entered and exited events name their state, the events in between
+    /// (ex. TaskScheduled or TaskFailed) take it from the event they follow.
+    fn assign_state_names(events: &mut [HistoryEvent]) {
+        let mut open_states: HashMap<i64, String> = HashMap::new();
+
+        for event in events.iter_mut() {
+            if event.event_type.starts_with("Execution") {
+                continue;
+            }
+
+            if event.state_name.is_none() {
+                event.state_name = open_states.get(&event.previous_event_id).cloned();
+            }
+
+            if let Some(state_name) = &event.state_name {
+                if !event.event_type.ends_with("StateExited") {
+                    open_states.insert(event.id, state_name.clone());
+                }
+            }
+        }
+    }
+
+    fn convert_logging(logging: SdkLoggingConfiguration) -> LoggingConfiguration {
+        LoggingConfiguration {
+            level: logging
+                .level
+                .map(|level| level.as_str().to_string())
+                .unwrap_or_else(|| "OFF".to_string()),
+            include_execution_data: logging.include_execution_data,
+            log_groups: logging
+                .destinations
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|destination| destination.cloud_watch_logs_log_group?.log_group_arn)
+                .collect(),
+        }
+    }
+
+    fn convert_status(status: SdkExecutionStatus) -> Option<ExecutionStatus> {
+        ExecutionStatus::ALL
+            .into_iter()
+            .find(|known_status| known_status.as_str() == status.as_str())
+    }
+
+    fn convert_date_time(date: aws_smithy_types::DateTime) -> Option<DateTime<Utc>> {
+        DateTime::from_timestamp(date.secs(), date.subsec_nanos())
+            .map(|date| Utc.from_utc_datetime(&date.naive_utc()))
     }
 }
 
+fn required<T>(value: Option<T>, field: &'static str) -> Result<T, PawError> {
+    value.ok_or(PawError::MissingField(field))
+}
+
 #[cfg(test)]
+// The mocked `StepFunctionsClient` returns the errors of the SDK as they are.
+#[allow(clippy::result_large_err)]
 mod tests {
     use super::*;
-    use aws_sdk_sfn::model::execution_list_item::Builder as ExecutionListItemBuilder;
-    use aws_sdk_sfn::model::state_machine_list_item::Builder as StateMachineListItemBuilder;
-    use aws_sdk_sfn::output::describe_execution_output::Builder as DescribeExecutionBuilder;
-    use aws_sdk_sfn::output::list_executions_output::Builder as ListExecutionsBuilder;
-    use aws_sdk_sfn::output::list_state_machines_output::Builder as ListStateMachinesBuilder;
-    use aws_sdk_sfn::output::start_execution_output::Builder as StartExecutioBuilder;
+    use aws_sdk_sfn::error::SdkError;
+    use aws_sdk_sfn::operation::describe_execution::builders::DescribeExecutionOutputBuilder as DescribeExecutionBuilder;
+    use aws_sdk_sfn::operation::describe_state_machine::builders::DescribeStateMachineOutputBuilder as DescribeStateMachineBuilder;
+    use aws_sdk_sfn::operation::describe_state_machine::DescribeStateMachineError;
+    use aws_sdk_sfn::operation::describe_state_machine_for_execution::builders::DescribeStateMachineForExecutionOutputBuilder as DescribeStateMachineForExecutionBuilder;
+    use aws_sdk_sfn::operation::get_execution_history::builders::GetExecutionHistoryOutputBuilder as GetExecutionHistoryBuilder;
+    use aws_sdk_sfn::operation::list_executions::builders::ListExecutionsOutputBuilder as ListExecutionsBuilder;
+    use aws_sdk_sfn::operation::list_state_machines::builders::ListStateMachinesOutputBuilder as ListStateMachinesBuilder;
+    use aws_sdk_sfn::operation::redrive_execution::builders::RedriveExecutionOutputBuilder as RedriveExecutionBuilder;
+    use aws_sdk_sfn::operation::redrive_execution::RedriveExecutionError;
+    use aws_sdk_sfn::operation::start_execution::builders::StartExecutionOutputBuilder as StartExecutioBuilder;
+    use aws_sdk_sfn::operation::start_execution::StartExecutionError;
+    use aws_sdk_sfn::operation::stop_execution::builders::StopExecutionOutputBuilder as StopExecutionBuilder;
+    use aws_sdk_sfn::types::builders::ExecutionListItemBuilder;
+    use aws_sdk_sfn::types::error::{
+        ExecutionAlreadyExists, ExecutionNotRedrivable, StateMachineDoesNotExist,
+    };
+    use aws_sdk_sfn::types::{
+        CloudWatchLogsLogGroup, ExecutionFailedEventDetails, ExecutionStartedEventDetails,
+        HistoryEventType, LogDestination, LogLevel, StateEnteredEventDetails, StateMachineListItem,
+        StateMachineStatus, StateMachineType, TaskFailedEventDetails, TaskScheduledEventDetails,
+        TracingConfiguration,
+    };
+    use aws_smithy_runtime_api::http::{Response, StatusCode};
+    use aws_smithy_types::body::SdkBody;
     use aws_smithy_types::DateTime;
 
     use mockall::predicate::eq;
 
+    fn state_machine_item(arn: &str, name: &str) -> StateMachineListItem {
+        StateMachineListItem::builder()
+            .state_machine_arn(arn)
+            .name(name)
+            .r#type(StateMachineType::Standard)
+            .creation_date(DateTime::from_secs(0))
+            .build()
+            .unwrap()
+    }
+
     #[tokio::test]
     async fn should_return_state_machines() {
         let mut result = Some(Ok(ListStateMachinesBuilder::default()
-            .state_machines(
-                StateMachineListItemBuilder::default()
-                    .state_machine_arn("dinosaur_machine::arn")
-                    .name("dinosaur_machine")
-                    .build(),
-            )
-            .build()));
+            .state_machines(state_machine_item(
+                "dinosaur_machine::arn",
+                "dinosaur_machine",
+            ))
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
             .expect_list_state_machines()
-            .with()
+            .with(eq(None))
             .times(1)
-            .returning(move || result.take().unwrap());
+            .returning(move |_next_token| result.take().unwrap());
 
         let machine = StepFunctionsMachine {
             client: mock_client,
         };
 
         assert_eq!(
-            machine.list_machines().await.unwrap(),
+            machine.list_machines(None).await.unwrap(),
             vec![StateMachine {
                 arn: "dinosaur_machine::arn".to_string(),
-                name: "dinosaur_machine".to_string()
+                name: "dinosaur_machine".to_string(),
+                details: None
             }]
         );
     }
@@ -251,111 +843,585 @@ mod tests {
     #[tokio::test]
     async fn should_return_empty_state_machines() {
         let mut result = Some(Ok(ListStateMachinesBuilder::default()
-            .set_state_machines(None)
-            .build()));
+            .set_state_machines(Some(vec![]))
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
             .expect_list_state_machines()
-            .with()
+            .with(eq(None))
             .times(1)
-            .returning(move || result.take().unwrap());
+            .returning(move |_next_token| result.take().unwrap());
 
         let machine = StepFunctionsMachine {
             client: mock_client,
         };
 
-        assert_eq!(machine.list_machines().await.unwrap(), vec![]);
+        assert_eq!(machine.list_machines(None).await.unwrap(), vec![]);
     }
 
     #[tokio::test]
-    async fn should_return_failed_executions_inside_date_range() {
-        let utc_now = DateTime::from_secs(Utc::now().timestamp());
-        let mut result = Some(Ok(ListExecutionsBuilder::default()
-            .executions(
-                ExecutionListItemBuilder::default()
-                    .execution_arn("dinosaur::arn::exec")
-                    .state_machine_arn("dinosaur::arn")
-                    .name("Execution")
-                    .start_date(utc_now)
-                    .build(),
-            )
-            .build()));
+    async fn should_return_state_machines_from_every_page() {
+        let mut first_page = Some(Ok(ListStateMachinesBuilder::default()
+            .state_machines(state_machine_item(
+                "dinosaur_machine::arn",
+                "dinosaur_machine",
+            ))
+            .next_token("page-2")
+            .build()
+            .unwrap()));
+        let mut second_page = Some(Ok(ListStateMachinesBuilder::default()
+            .state_machines(state_machine_item("t_rex_machine::arn", "t_rex_machine"))
+            .state_machines(state_machine_item(
+                "dinosaur_egg_machine::arn",
+                "dinosaur_egg_machine",
+            ))
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
-            .expect_list_failed_executions()
-            .with(eq("dinosaur::arn".to_string()), eq(None))
+            .expect_list_state_machines()
+            .with(eq(None))
+            .times(1)
+            .returning(move |_next_token| first_page.take().unwrap());
+        mock_client
+            .expect_list_state_machines()
+            .with(eq(Some("page-2".to_string())))
             .times(1)
-            .returning(move |_state_machine_arn, _next_token| result.take().unwrap());
+            .returning(move |_next_token| second_page.take().unwrap());
 
         let machine = StepFunctionsMachine {
             client: mock_client,
         };
 
-        let state_machine = StateMachine {
-            arn: "dinosaur::arn".to_string(),
-            name: "dinosaur".to_string(),
-        };
-        let failed_executions = machine
-            .list_failed_executions(&state_machine, None, None)
-            .await
-            .unwrap();
-
         assert_eq!(
-            failed_executions,
-            vec![StateMachineExecution {
-                arn: "dinosaur::arn::exec".to_string(),
-                machine_arn: "dinosaur::arn".to_string(),
-                name: "Execution".to_string(),
-                start_date: StepFunctionsMachine::convert_date_time(utc_now),
-                input: None,
-                output: None
-            }]
-        )
+            machine
+                .list_machines(Some(&NameFilter::Prefix("dinosaur".to_string())))
+                .await
+                .unwrap(),
+            vec![
+                StateMachine {
+                    arn: "dinosaur_machine::arn".to_string(),
+                    name: "dinosaur_machine".to_string(),
+                    details: None
+                },
+                StateMachine {
+                    arn: "dinosaur_egg_machine::arn".to_string(),
+                    name: "dinosaur_egg_machine".to_string(),
+                    details: None
+                }
+            ]
+        );
     }
 
     #[tokio::test]
-    async fn should_return_empty_failed_executions() {
-        let mut result = Some(Ok(ListExecutionsBuilder::default()
-            .set_executions(None)
-            .build()));
+    async fn should_describe_state_machine() {
+        let mut result = Some(Ok(DescribeStateMachineBuilder::default()
+            .state_machine_arn("dinosaur_machine::arn")
+            .name("dinosaur_machine")
+            .status(StateMachineStatus::Active)
+            .definition("{\"StartAt\":\"Roar\"}")
+            .role_arn("dinosaur::role")
+            .r#type(StateMachineType::Express)
+            .creation_date(DateTime::from_secs(0))
+            .logging_configuration(
+                aws_sdk_sfn::types::LoggingConfiguration::builder()
+                    .level(LogLevel::Error)
+                    .destinations(
+                        LogDestination::builder()
+                            .cloud_watch_logs_log_group(
+                                CloudWatchLogsLogGroup::builder()
+                                    .log_group_arn("dinosaur::logs")
+                                    .build(),
+                            )
+                            .build(),
+                    )
+                    .build(),
+            )
+            .tracing_configuration(TracingConfiguration::builder().enabled(true).build())
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
-            .expect_list_failed_executions()
-            .with(eq("dinosaur::arn".to_string()), eq(None))
+            .expect_describe_state_machine()
+            .with(eq("dinosaur_machine::arn".to_string()))
             .times(1)
-            .returning(move |_state_machine_arn, _next_token| result.take().unwrap());
+            .returning(move |_state_machine_arn| result.take().unwrap());
 
         let machine = StepFunctionsMachine {
             client: mock_client,
         };
 
-        let state_machine = StateMachine {
-            arn: "dinosaur::arn".to_string(),
-            name: "dinosaur".to_string(),
-        };
-        let failed_executions = machine
-            .list_failed_executions(&state_machine, None, None)
-            .await
-            .unwrap();
-
-        assert_eq!(failed_executions, vec![])
+        assert_eq!(
+            machine
+                .describe_state_machine("dinosaur_machine::arn".to_string())
+                .await
+                .unwrap(),
+            StateMachine {
+                arn: "dinosaur_machine::arn".to_string(),
+                name: "dinosaur_machine".to_string(),
+                details: Some(StateMachineDetails {
+                    machine_type: MachineType::Express,
+                    status: Some(MachineStatus::Active),
+                    creation_date: StepFunctionsMachine::convert_date_time(DateTime::from_secs(0))
+                        .unwrap(),
+                    role_arn: "dinosaur::role".to_string(),
+                    logging: Some(LoggingConfiguration {
+                        level: "ERROR".to_string(),
+                        include_execution_data: false,
+                        log_groups: vec!["dinosaur::logs".to_string()],
+                    }),
+                    tracing_enabled: true,
+                    definition: "{\"StartAt\":\"Roar\"}".to_string(),
+                }),
+            }
+        );
     }
 
     #[tokio::test]
-    async fn should_return_execution() {
-        let utc = DateTime::from_secs(Utc::now().timestamp());
-        let mut result = Some(Ok(DescribeExecutionBuilder::default()
-            .execution_arn("dinosaur::arn")
-            .state_machine_arn("dinousar::machine")
-            .name("dinosaur")
-            .start_date(utc)
-            .input("{'batata': 'frita'}")
-            .output("{'body': 'delicia'}")
-            .build()));
-
+    async fn should_return_the_definition_an_execution_ran() {
+        let mut result = Some(Ok(DescribeStateMachineForExecutionBuilder::default()
+            .state_machine_arn("dinosaur_machine::arn")
+            .name("dinosaur_machine")
+            .definition("{\"StartAt\":\"Roar\"}")
+            .role_arn("dinosaur::role")
+            .update_date(DateTime::from_secs(0))
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
-            .expect_describe_execution()
+            .expect_describe_state_machine_for_execution()
+            .with(eq("dinosaur_execution::arn".to_string()))
+            .times(1)
+            .returning(move |_execution_arn| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        assert_eq!(
+            machine
+                .execution_definition("dinosaur_execution::arn".to_string())
+                .await
+                .unwrap(),
+            "{\"StartAt\":\"Roar\"}".to_string()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_find_machine_by_name() {
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_state_machines()
+            .with(eq(None))
+            .times(2)
+            .returning(|_next_token| {
+                Ok(ListStateMachinesBuilder::default()
+                    .state_machines(state_machine_item(
+                        "dinosaur_machine::arn",
+                        "dinosaur_machine",
+                    ))
+                    .build()
+                    .unwrap())
+            });
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+        let expected = Some(StateMachine {
+            arn: "dinosaur_machine::arn".to_string(),
+            name: "dinosaur_machine".to_string(),
+            details: None,
+        });
+
+        assert_eq!(
+            machine.find_machine("dinosaur_machine").await.unwrap(),
+            expected
+        );
+        assert_eq!(machine.find_machine("t-rex").await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn should_describe_machine_found_by_arn() {
+        let arn = "arn:aws:states:us-east-1:123456789012:stateMachine:dinosaur_machine";
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client.expect_list_state_machines().never();
+        mock_client
+            .expect_describe_state_machine()
+            .with(eq(arn.to_string()))
+            .times(1)
+            .returning(|state_machine_arn| {
+                Ok(DescribeStateMachineBuilder::default()
+                    .state_machine_arn(state_machine_arn)
+                    .name("dinosaur_machine")
+                    .definition("{}")
+                    .role_arn("dinosaur::role")
+                    .r#type(StateMachineType::Standard)
+                    .creation_date(DateTime::from_secs(0))
+                    .build()
+                    .unwrap())
+            });
+        mock_client
+            .expect_describe_state_machine()
+            .with(eq(format!("{}-extinct", arn)))
+            .times(1)
+            .returning(|_state_machine_arn| {
+                Err(SdkError::service_error(
+                    DescribeStateMachineError::StateMachineDoesNotExist(
+                        StateMachineDoesNotExist::builder().build(),
+                    ),
+                    Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty()),
+                ))
+            });
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let found = machine.find_machine(arn).await.unwrap().unwrap();
+        assert_eq!(found.arn, arn);
+        assert_eq!(found.name, "dinosaur_machine");
+        assert_eq!(
+            machine
+                .find_machine(&format!("{}-extinct", arn))
+                .await
+                .unwrap(),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn should_return_executions_with_status() {
+        let utc_now = DateTime::from_secs(Utc::now().timestamp());
+        let mut result = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::exec")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("Execution")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(utc_now)
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_executions()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some(ExecutionStatus::Failed)),
+                eq(None),
+            )
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+        let failed_executions = machine
+            .list_executions(
+                &state_machine,
+                &[ExecutionStatus::Failed],
+                &DateRange::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            failed_executions,
+            vec![StateMachineExecution {
+                arn: "dinosaur::arn::exec".to_string(),
+                machine_arn: "dinosaur::arn".to_string(),
+                name: "Execution".to_string(),
+                status: ExecutionStatus::Failed,
+                start_date: StepFunctionsMachine::convert_date_time(utc_now).unwrap(),
+                input: None,
+                output: None,
+                redrive_count: None,
+                redrive_status: None,
+                root_cause: None
+            }]
+        )
+    }
+
+    #[tokio::test]
+    async fn should_return_empty_executions() {
+        let mut result = Some(Ok(ListExecutionsBuilder::default()
+            .set_executions(Some(vec![]))
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_executions()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some(ExecutionStatus::Failed)),
+                eq(None),
+            )
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+        let failed_executions = machine
+            .list_executions(
+                &state_machine,
+                &[ExecutionStatus::Failed],
+                &DateRange::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(failed_executions, vec![])
+    }
+
+    #[tokio::test]
+    async fn should_merge_executions_of_every_status_newest_first() {
+        let mut failed = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::failed")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("Failed")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(DateTime::from_secs(100))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()));
+        let mut timed_out = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::timed_out")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("TimedOut")
+                    .status(SdkExecutionStatus::TimedOut)
+                    .start_date(DateTime::from_secs(200))
+                    .build()
+                    .unwrap(),
+            )
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::too_old")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("TooOld")
+                    .status(SdkExecutionStatus::TimedOut)
+                    .start_date(DateTime::from_secs(10))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_executions()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some(ExecutionStatus::Failed)),
+                eq(None),
+            )
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| failed.take().unwrap());
+        mock_client
+            .expect_list_executions()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some(ExecutionStatus::TimedOut)),
+                eq(None),
+            )
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| timed_out.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+        let range = DateRange::new(
+            StepFunctionsMachine::convert_date_time(DateTime::from_secs(50)),
+            None,
+        );
+        let executions = machine
+            .list_executions(
+                &state_machine,
+                &[ExecutionStatus::TimedOut, ExecutionStatus::Failed],
+                &range,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(
+            executions
+                .iter()
+                .map(|execution| (execution.name.as_str(), execution.status))
+                .collect::<Vec<(&str, ExecutionStatus)>>(),
+            vec![
+                ("TimedOut", ExecutionStatus::TimedOut),
+                ("Failed", ExecutionStatus::Failed)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_stop_paginating_once_executions_are_before_start_date() {
+        let mut first_page = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::too_new")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("TooNew")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(DateTime::from_secs(300))
+                    .build()
+                    .unwrap(),
+            )
+            .next_token("page-2")
+            .build()
+            .unwrap()));
+        let mut second_page = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::inside")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("Inside")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(DateTime::from_secs(150))
+                    .build()
+                    .unwrap(),
+            )
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::too_old")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("TooOld")
+                    .status(SdkExecutionStatus::Failed)
+                    .start_date(DateTime::from_secs(50))
+                    .build()
+                    .unwrap(),
+            )
+            .next_token("page-3")
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_executions()
+            .with(eq("dinosaur::arn".to_string()), eq(None), eq(None))
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| first_page.take().unwrap());
+        mock_client
+            .expect_list_executions()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(None),
+                eq(Some("page-2".to_string())),
+            )
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| second_page.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+        let range = DateRange::new(
+            StepFunctionsMachine::convert_date_time(DateTime::from_secs(100)),
+            StepFunctionsMachine::convert_date_time(DateTime::from_secs(200)),
+        );
+        let executions = machine
+            .list_executions(&state_machine, &[], &range)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            executions
+                .iter()
+                .map(|execution| execution.name.as_str())
+                .collect::<Vec<&str>>(),
+            vec!["Inside"]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_list_executions_in_any_status_without_statuses() {
+        let mut result = Some(Ok(ListExecutionsBuilder::default()
+            .executions(
+                ExecutionListItemBuilder::default()
+                    .execution_arn("dinosaur::arn::running")
+                    .state_machine_arn("dinosaur::arn")
+                    .name("Running")
+                    .status(SdkExecutionStatus::Running)
+                    .start_date(DateTime::from_secs(100))
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_list_executions()
+            .with(eq("dinosaur::arn".to_string()), eq(None), eq(None))
+            .times(1)
+            .returning(move |_state_machine_arn, _status, _next_token| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let state_machine = StateMachine {
+            arn: "dinosaur::arn".to_string(),
+            name: "dinosaur".to_string(),
+            details: None,
+        };
+        let executions = machine
+            .list_executions(&state_machine, &[], &DateRange::default())
+            .await
+            .unwrap();
+
+        assert_eq!(executions.len(), 1);
+        assert_eq!(executions[0].status, ExecutionStatus::Running);
+    }
+
+    #[tokio::test]
+    async fn should_return_execution() {
+        let utc = DateTime::from_secs(Utc::now().timestamp());
+        let mut result = Some(Ok(DescribeExecutionBuilder::default()
+            .execution_arn("dinosaur::arn")
+            .state_machine_arn("dinousar::machine")
+            .name("dinosaur")
+            .status(SdkExecutionStatus::Succeeded)
+            .start_date(utc)
+            .input("{'batata': 'frita'}")
+            .output("{'body': 'delicia'}")
+            .build()
+            .unwrap()));
+
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
             .with(eq("dinousar::arn".to_string()))
             .times(1)
             .returning(move |_machine_arn| result.take().unwrap());
@@ -375,39 +1441,436 @@ mod tests {
                 arn: "dinosaur::arn".to_string(),
                 machine_arn: "dinousar::machine".to_string(),
                 name: "dinosaur".to_string(),
-                start_date: StepFunctionsMachine::convert_date_time(utc),
+                status: ExecutionStatus::Succeeded,
+                start_date: StepFunctionsMachine::convert_date_time(utc).unwrap(),
                 input: Some("{'batata': 'frita'}".to_string()),
-                output: Some("{'body': 'delicia'}".to_string())
+                output: Some("{'body': 'delicia'}".to_string()),
+                redrive_count: None,
+                redrive_status: None,
+                root_cause: None
             }
         )
     }
 
+    #[tokio::test]
+    async fn should_fail_when_execution_has_no_name() {
+        let mut result = Some(Ok(DescribeExecutionBuilder::default()
+            .execution_arn("dinosaur::arn")
+            .state_machine_arn("dinousar::machine")
+            .status(SdkExecutionStatus::Failed)
+            .start_date(DateTime::from_secs(0))
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .with(eq("dinosaur::arn".to_string()))
+            .times(1)
+            .returning(move |_execution_arn| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        assert!(matches!(
+            machine
+                .describe_execution("dinosaur::arn".to_string())
+                .await,
+            Err(PawError::MissingField("name"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn should_return_redrive_status_of_execution() {
+        let mut result = Some(Ok(DescribeExecutionBuilder::default()
+            .execution_arn("dinosaur::arn")
+            .state_machine_arn("dinousar::machine")
+            .name("dinosaur")
+            .status(SdkExecutionStatus::Failed)
+            .start_date(DateTime::from_secs(0))
+            .redrive_count(1)
+            .redrive_status(SdkRedriveStatus::NotRedrivable)
+            .redrive_status_reason("Execution is older than 14 days")
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_describe_execution()
+            .with(eq("dinosaur::arn".to_string()))
+            .times(1)
+            .returning(move |_execution_arn| result.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let execution = machine
+            .describe_execution("dinosaur::arn".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(execution.redrive_count, Some(1));
+        assert_eq!(
+            execution.redrive_status,
+            Some(RedriveStatus::NotRedrivable {
+                reason: Some("Execution is older than 14 days".to_string())
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn should_return_execution_history_from_every_page() {
+        let event = |id: i64, event_type: HistoryEventType| {
+            SdkHistoryEvent::builder()
+                .id(id)
+                .previous_event_id(id - 1)
+                .r#type(event_type)
+                .timestamp(DateTime::from_secs(id))
+        };
+        let mut first_page = Some(Ok(GetExecutionHistoryBuilder::default()
+            .events(
+                event(1, HistoryEventType::ExecutionStarted)
+                    .execution_started_event_details(
+                        ExecutionStartedEventDetails::builder()
+                            .input("{\"card\":\"visa\"}")
+                            .build(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .events(
+                event(2, HistoryEventType::TaskStateEntered)
+                    .state_entered_event_details(
+                        StateEnteredEventDetails::builder()
+                            .name("Charge")
+                            .input("{\"card\":\"visa\"}")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .next_token("page-2")
+            .build()
+            .unwrap()));
+        let mut second_page = Some(Ok(GetExecutionHistoryBuilder::default()
+            .events(
+                event(3, HistoryEventType::TaskScheduled)
+                    .task_scheduled_event_details(
+                        TaskScheduledEventDetails::builder()
+                            .resource_type("lambda")
+                            .resource("invoke")
+                            .region("us-east-1")
+                            .parameters("{\"FunctionName\":\"charge\"}")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .events(
+                event(4, HistoryEventType::TaskFailed)
+                    .task_failed_event_details(
+                        TaskFailedEventDetails::builder()
+                            .resource_type("lambda")
+                            .resource("invoke")
+                            .error("States.Timeout")
+                            .cause("Card issuer took too long")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .events(
+                event(5, HistoryEventType::ExecutionFailed)
+                    .execution_failed_event_details(
+                        ExecutionFailedEventDetails::builder()
+                            .error("States.Timeout")
+                            .build(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_get_execution_history()
+            .with(eq("dinosaur::arn".to_string()), eq(None))
+            .times(1)
+            .returning(move |_execution_arn, _next_token| first_page.take().unwrap());
+        mock_client
+            .expect_get_execution_history()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some("page-2".to_string())),
+            )
+            .times(1)
+            .returning(move |_execution_arn, _next_token| second_page.take().unwrap());
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let events = machine
+            .get_execution_history("dinosaur::arn".to_string())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            events
+                .iter()
+                .map(|event| (event.event_type.as_str(), event.state_name.as_deref()))
+                .collect::<Vec<(&str, Option<&str>)>>(),
+            vec![
+                ("ExecutionStarted", None),
+                ("TaskStateEntered", Some("Charge")),
+                ("TaskScheduled", Some("Charge")),
+                ("TaskFailed", Some("Charge")),
+                ("ExecutionFailed", None),
+            ]
+        );
+        assert_eq!(events[0].input, Some("{\"card\":\"visa\"}".to_string()));
+        assert_eq!(
+            events[2].input,
+            Some("{\"FunctionName\":\"charge\"}".to_string())
+        );
+        assert_eq!(events[3].error, Some("States.Timeout".to_string()));
+        assert_eq!(
+            events[3].cause,
+            Some("Card issuer took too long".to_string())
+        );
+        assert_eq!(
+            events[4].timestamp,
+            StepFunctionsMachine::convert_date_time(DateTime::from_secs(5)).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn should_find_root_cause_from_latest_events() {
+        let event = |id: i64, event_type: HistoryEventType| {
+            SdkHistoryEvent::builder()
+                .id(id)
+                .previous_event_id(id - 1)
+                .r#type(event_type)
+                .timestamp(DateTime::from_secs(id))
+        };
+        let mut result = Some(Ok(GetExecutionHistoryBuilder::default()
+            .events(
+                event(4, HistoryEventType::ExecutionFailed)
+                    .execution_failed_event_details(
+                        ExecutionFailedEventDetails::builder()
+                            .error("States.Timeout")
+                            .build(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .events(
+                event(3, HistoryEventType::TaskFailed)
+                    .task_failed_event_details(
+                        TaskFailedEventDetails::builder()
+                            .resource_type("lambda")
+                            .resource("invoke")
+                            .error("States.Timeout")
+                            .cause("Card issuer took too long")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .events(
+                event(2, HistoryEventType::TaskStateEntered)
+                    .state_entered_event_details(
+                        StateEnteredEventDetails::builder()
+                            .name("Charge")
+                            .build()
+                            .unwrap(),
+                    )
+                    .build()
+                    .unwrap(),
+            )
+            .next_token("older-events")
+            .build()
+            .unwrap()));
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_get_latest_execution_history()
+            .with(eq("dinosaur::arn".to_string()), eq(ROOT_CAUSE_EVENTS))
+            .times(1)
+            .returning(move |_execution_arn, _max_results| result.take().unwrap());
+        mock_client.expect_get_execution_history().never();
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        assert_eq!(
+            machine
+                .find_root_cause("dinosaur::arn".to_string())
+                .await
+                .unwrap(),
+            Some(RootCause {
+                failed_state: Some("Charge".to_string()),
+                error: Some("States.Timeout".to_string()),
+                cause: Some("Card issuer took too long".to_string()),
+                retries_used: 0,
+            })
+        );
+    }
+
     #[tokio::test]
     async fn should_start_execution() {
         let mut result = Some(Ok(StartExecutioBuilder::default()
             .execution_arn("dinousar::arn")
             .start_date(DateTime::from_secs(Utc::now().timestamp()))
-            .build()));
+            .build()
+            .unwrap()));
         let mut mock_client = StepFunctionsClient::default();
         mock_client
             .expect_start_execution()
             .with(
                 eq("dinosaur::arn".to_string()),
                 eq("{'batata': 'frita'}".to_string()),
+                eq(None),
             )
             .times(1)
-            .returning(move |_machine_arn, _input| result.take().unwrap());
+            .returning(move |_machine_arn, _input, _name| result.take().unwrap());
 
         let machine = StepFunctionsMachine {
             client: mock_client,
         };
 
-        machine
+        let outcome = machine
             .start_execution(ExecutionInput {
                 machine_arn: "dinosaur::arn".to_string(),
                 input: "{'batata': 'frita'}".to_string(),
+                name: None,
             })
             .await
             .unwrap();
+
+        assert_eq!(
+            outcome,
+            StartOutcome::Started {
+                execution_arn: "dinousar::arn".to_string()
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_report_execution_already_started_with_name() {
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_start_execution()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq("{'batata': 'frita'}".to_string()),
+                eq(Some("dinosaur-retry-1".to_string())),
+            )
+            .times(1)
+            .returning(|_machine_arn, _input, _name| {
+                Err(SdkError::service_error(
+                    StartExecutionError::ExecutionAlreadyExists(
+                        ExecutionAlreadyExists::builder().build(),
+                    ),
+                    Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty()),
+                ))
+            });
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        let outcome = machine
+            .start_execution(ExecutionInput {
+                machine_arn: "dinosaur::arn".to_string(),
+                input: "{'batata': 'frita'}".to_string(),
+                name: Some("dinosaur-retry-1".to_string()),
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(outcome, StartOutcome::AlreadyExists);
+    }
+
+    #[tokio::test]
+    async fn should_stop_execution() {
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_stop_execution()
+            .with(
+                eq("dinosaur::arn".to_string()),
+                eq(Some("BadDeploy".to_string())),
+                eq(None),
+            )
+            .times(1)
+            .returning(|_execution_arn, _error, _cause| {
+                Ok(StopExecutionBuilder::default()
+                    .stop_date(DateTime::from_secs(623066400))
+                    .build()
+                    .unwrap())
+            });
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        assert!(machine
+            .stop_execution(
+                "dinosaur::arn".to_string(),
+                Some("BadDeploy".to_string()),
+                None
+            )
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_redrive_execution() {
+        let mut mock_client = StepFunctionsClient::default();
+        mock_client
+            .expect_redrive_execution()
+            .with(eq("dinosaur::arn".to_string()))
+            .times(1)
+            .returning(|_execution_arn| {
+                Ok(RedriveExecutionBuilder::default()
+                    .redrive_date(DateTime::from_secs(623066400))
+                    .build()
+                    .unwrap())
+            });
+        mock_client
+            .expect_redrive_execution()
+            .with(eq("t-rex::arn".to_string()))
+            .times(1)
+            .returning(|_execution_arn| {
+                Err(SdkError::service_error(
+                    RedriveExecutionError::ExecutionNotRedrivable(
+                        ExecutionNotRedrivable::builder().build(),
+                    ),
+                    Response::new(StatusCode::try_from(400).unwrap(), SdkBody::empty()),
+                ))
+            });
+
+        let machine = StepFunctionsMachine {
+            client: mock_client,
+        };
+
+        assert_eq!(
+            machine
+                .redrive_execution("dinosaur::arn".to_string())
+                .await
+                .unwrap(),
+            RedriveOutcome::Redriven
+        );
+        assert_eq!(
+            machine
+                .redrive_execution("t-rex::arn".to_string())
+                .await
+                .unwrap(),
+            RedriveOutcome::NotRedrivable
+        );
     }
 }
@@ -0,0 +1,1446 @@
+use crate::actions::describe_machine::{DescribeMachine, DescribeMachineOptions};
+use crate::actions::draw_diagram::{DiagramOptions, DrawDiagram};
+use crate::actions::draw_execution::{DrawExecutionDiagram, ExecutionDiagramOptions};
+use crate::actions::failed_executions::{
+    RetryFailedExecution, RetryFailedOptions, DEFAULT_CONCURRENCY,
+};
+use crate::actions::inspect_execution::{InspectExecution, InspectExecutionOptions};
+use crate::actions::lint_definition::{LintDefinition, LintOptions};
+use crate::actions::list_executions::{ListExecutions, ListExecutionsOptions};
+use crate::actions::list_machines::ListMachines;
+use crate::actions::resume_retry::{ResumeRetry, ResumeRetryOptions};
+use crate::actions::stop_executions::{StopExecutionsOptions, StopRunningExecutions};
+use crate::actions::{
+    parse_relative_duration, parse_utc_date_time, DefinitionSource, StepFunctionsAction,
+};
+use crate::asl::diagram::DiagramFormat;
+use crate::aws::model::{ClientOptions, DateRange, ExecutionStatus, NameFilter};
+use crate::config::settings::{parse_endpoint_url, Settings, SettingsError};
+use crate::error::PawError;
+use crate::output::OutputFormat;
+use crate::transform::InputTransform;
+use chrono::{DateTime, Duration, Utc};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use http::Uri;
+use regex::Regex;
+use std::path::PathBuf;
+
+#[derive(Parser, Debug)]
+#[command(name = "sfn-paw", version, about = "Step Functions CLI Tool")]
+pub struct Cli {
+    /// Configuration file [default: ~/.config/paw/config.toml]
+    #[arg(long, global = true, env = "PAW_CONFIG")]
+    pub config: Option<PathBuf>,
+
+    #[command(flatten)]
+    pub client: ClientArgs,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Args, Debug)]
+pub struct ClientArgs {
+    /// AWS profile to load the credentials and region from
+    #[arg(long, global = true)]
+    pub profile: Option<String>,
+
+    /// AWS region, overrides the region of the profile
+    #[arg(long, global = true)]
+    pub region: Option<String>,
+
+    /// Custom Step Functions endpoint (ex. http://localhost:8083 for Step Functions Local)
+    #[arg(long, global = true, value_parser = parse_endpoint_url)]
+    pub endpoint_url: Option<Uri>,
+
+    /// Environment of the configuration file to take the profile, region and endpoint from
+    #[arg(long, global = true)]
+    pub env: Option<String>,
+}
+
+impl ClientArgs {
+    /// Client options from the command line, falling back to the configuration file.
+    pub fn client_options(&self, settings: &Settings) -> Result<ClientOptions, SettingsError> {
+        let defaults = settings.client_options(self.env.as_deref())?;
+
+        Ok(ClientOptions {
+            profile: self.profile.clone().or(defaults.profile),
+            region: self.region.clone().or(defaults.region),
+            endpoint_url: self.endpoint_url.clone().or(defaults.endpoint_url),
+        })
+    }
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Retry the failed, timed out or aborted executions of a state machine
+    #[command(name = "retry-failed", alias = "retry")]
+    RetryFailed(RetryFailedArgs),
+
+    /// Redrive the failed, timed out or aborted executions of a state machine from the state
+    /// that failed, restarting the ones Step Functions cannot redrive
+    #[command(name = "redrive-failed", alias = "redrive")]
+    RedriveFailed(RedriveFailedArgs),
+
+    /// List the state machines of the account
+    #[command(name = "list-machines")]
+    ListMachines(ListMachinesArgs),
+
+    /// Show the configuration and the definition of a state machine
+    #[command(name = "describe-machine")]
+    DescribeMachine(DescribeMachineArgs),
+
+    /// Check a state machine definition for mistakes and risky patterns
+    #[command(name = "lint")]
+    Lint(LintArgs),
+
+    /// Draw a state machine definition, or the path an execution took through it, as a Mermaid
+    /// or Graphviz DOT flowchart
+    #[command(name = "diagram")]
+    Diagram(DiagramArgs),
+
+    /// List the executions of a state machine
+    #[command(name = "list-executions")]
+    ListExecutions(ListExecutionsArgs),
+
+    /// List the failed executions of a state machine
+    #[command(name = "list-failed")]
+    ListFailed(ListFailedArgs),
+
+    /// Show the event history of an execution
+    #[command(name = "inspect-execution")]
+    InspectExecution(InspectExecutionArgs),
+
+    /// Stop the running executions of a state machine
+    #[command(name = "stop-executions")]
+    StopExecutions(StopExecutionsArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct DateRangeArgs {
+    /// Only include executions started at or after this date (ex. 1989-09-30T22:10:32-03:00 or 24h)
+    #[arg(long, value_parser = parse_date_time)]
+    pub since: Option<DateTime<Utc>>,
+
+    /// Only include executions started at or before this date (ex. 1989-09-30T23:15:00-03:00 or 1h)
+    #[arg(long, value_parser = parse_date_time)]
+    pub until: Option<DateTime<Utc>>,
+}
+
+#[derive(Args, Debug)]
+pub struct OutputArgs {
+    /// Output format [default: table]
+    #[arg(long, short, value_enum)]
+    pub output: Option<OutputFormat>,
+}
+
+#[derive(Args, Debug)]
+pub struct ListMachinesArgs {
+    /// Only list machines whose name starts with this prefix
+    #[arg(long, conflicts_with = "name_regex")]
+    pub name_prefix: Option<String>,
+
+    /// Only list machines whose name matches this regex
+    #[arg(long)]
+    pub name_regex: Option<Regex>,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+impl ListMachinesArgs {
+    fn machine_filter(self) -> Option<NameFilter> {
+        name_filter(self.name_prefix, self.name_regex)
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct DescribeMachineArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long)]
+    pub machine: String,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct LintArgs {
+    /// Definition file to check
+    #[arg(required_unless_present = "machine", conflicts_with = "machine")]
+    pub definition: Option<PathBuf>,
+
+    /// Name, ARN or configured alias of a deployed state machine to check instead of a file
+    #[arg(long)]
+    pub machine: Option<String>,
+
+    /// Check the definition file against the limits of Express workflows
+    #[arg(long, conflicts_with = "machine")]
+    pub express: bool,
+
+    /// Fail on warnings as well as errors
+    #[arg(long)]
+    pub deny_warnings: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+impl LintArgs {
+    fn definition_source(self, settings: &Settings) -> DefinitionSource {
+        definition_source(self.definition, self.machine, settings)
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct DiagramArgs {
+    /// Definition file to draw
+    #[arg(
+        required_unless_present_any = ["machine", "execution"],
+        conflicts_with_all = ["machine", "execution"]
+    )]
+    pub definition: Option<PathBuf>,
+
+    /// Name, ARN or configured alias of a deployed state machine to draw instead of a file
+    #[arg(long, conflicts_with = "execution")]
+    pub machine: Option<String>,
+
+    /// ARN of an execution to draw, with the states it visited colored by how they ended
+    #[arg(long)]
+    pub execution: Option<String>,
+
+    /// Diagram format
+    #[arg(long, short, value_enum, default_value = "mermaid")]
+    pub format: DiagramFormat,
+}
+
+impl DiagramArgs {
+    fn definition_source(self, settings: &Settings) -> DefinitionSource {
+        definition_source(self.definition, self.machine, settings)
+    }
+}
+
+#[derive(Args, Debug)]
+pub struct RetryFailedArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long, required_unless_present = "resume")]
+    pub machine: Option<String>,
+
+    /// Resume the interrupted batch recorded in this journal
+    #[arg(long, value_name = "JOURNAL", conflicts_with_all = ["machine", "statuses", "since", "until", "journal", "idempotent_names", "transform", "redrive", "dry_run"])]
+    pub resume: Option<PathBuf>,
+
+    /// Record the batch in this journal [default: a new file under the local data directory]
+    #[arg(long, conflicts_with = "dry_run")]
+    pub journal: Option<PathBuf>,
+
+    /// Status of the executions to retry, repeat it or separate by commas for several
+    #[arg(
+        long = "status",
+        value_delimiter = ',',
+        value_parser = parse_retryable_status,
+        default_value = "failed"
+    )]
+    pub statuses: Vec<ExecutionStatus>,
+
+    #[command(flatten)]
+    pub range: DateRangeArgs,
+
+    /// Number of executions to retry at the same time [default: 1]
+    #[arg(long, value_parser = parse_concurrency)]
+    pub concurrency: Option<usize>,
+
+    /// Write the outcome of every retried execution to this file as JSON
+    #[arg(long)]
+    pub summary_file: Option<PathBuf>,
+
+    /// Name each retry after the original execution (ex. <name>-retry-1), so executions that
+    /// were already retried are skipped instead of started twice
+    #[arg(long)]
+    pub idempotent_names: bool,
+
+    /// Transform every input before the retry with a JSON merge patch, a JSON patch or an
+    /// expression (ex. `.retries = 1 | del(.token)`) read from this file
+    #[arg(long, value_name = "FILE")]
+    pub transform: Option<PathBuf>,
+
+    /// Redrive the executions that allow it from the state that failed instead of starting
+    /// them over, the others are restarted
+    #[arg(long)]
+    pub redrive: bool,
+
+    /// Fetch the inputs and print the executions that would be started, without starting them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Retry every execution found instead of only listing them
+    #[arg(long, short)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RedriveFailedArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long)]
+    pub machine: String,
+
+    /// Record the batch in this journal [default: a new file under the local data directory]
+    #[arg(long, conflicts_with = "dry_run")]
+    pub journal: Option<PathBuf>,
+
+    /// Status of the executions to redrive, repeat it or separate by commas for several
+    #[arg(
+        long = "status",
+        value_delimiter = ',',
+        value_parser = parse_retryable_status,
+        default_value = "failed"
+    )]
+    pub statuses: Vec<ExecutionStatus>,
+
+    #[command(flatten)]
+    pub range: DateRangeArgs,
+
+    /// Number of executions to redrive at the same time [default: 1]
+    #[arg(long, value_parser = parse_concurrency)]
+    pub concurrency: Option<usize>,
+
+    /// Write the outcome of every redriven execution to this file as JSON
+    #[arg(long)]
+    pub summary_file: Option<PathBuf>,
+
+    /// Name the restart of the executions that cannot be redriven after the original execution
+    /// (ex. <name>-retry-1), so they are not started twice
+    #[arg(long)]
+    pub idempotent_names: bool,
+
+    /// Describe the executions and print how each would be redriven or restarted, without
+    /// changing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Redrive every execution found instead of only listing them
+    #[arg(long, short)]
+    pub yes: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ListExecutionsArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long)]
+    pub machine: String,
+
+    /// Only list executions in this status, repeat it or separate by commas for several [default: every status]
+    #[arg(long = "status", value_enum, value_delimiter = ',')]
+    pub statuses: Vec<ExecutionStatus>,
+
+    #[command(flatten)]
+    pub range: DateRangeArgs,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct ListFailedArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long)]
+    pub machine: String,
+
+    #[command(flatten)]
+    pub range: DateRangeArgs,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct InspectExecutionArgs {
+    /// ARN of the execution
+    #[arg(long)]
+    pub execution: String,
+
+    /// Add the input, output, error and cause of every event to the table and csv output
+    #[arg(long)]
+    pub include_data: bool,
+
+    #[command(flatten)]
+    pub output: OutputArgs,
+}
+
+#[derive(Args, Debug)]
+pub struct StopExecutionsArgs {
+    /// Name, ARN or configured alias of the state machine
+    #[arg(long)]
+    pub machine: String,
+
+    #[command(flatten)]
+    pub range: DateRangeArgs,
+
+    /// Only stop executions whose name starts with this prefix
+    #[arg(long, conflicts_with = "name_regex")]
+    pub name_prefix: Option<String>,
+
+    /// Only stop executions whose name matches this regex
+    #[arg(long)]
+    pub name_regex: Option<Regex>,
+
+    /// Only stop executions running for longer than this (ex. 30m or 2h)
+    #[arg(long, value_parser = parse_duration)]
+    pub older_than: Option<Duration>,
+
+    /// Error code recorded on the stopped executions
+    #[arg(long)]
+    pub error: Option<String>,
+
+    /// Cause recorded on the stopped executions
+    #[arg(long)]
+    pub cause: Option<String>,
+
+    /// Number of executions to stop at the same time [default: 1]
+    #[arg(long, value_parser = parse_concurrency)]
+    pub concurrency: Option<usize>,
+
+    /// Write the outcome of every stopped execution to this file as JSON
+    #[arg(long)]
+    pub summary_file: Option<PathBuf>,
+
+    /// Print the executions that would be stopped, without stopping them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Stop every execution found instead of only listing them
+    #[arg(long, short)]
+    pub yes: bool,
+}
+
+impl Command {
+    pub fn into_action(
+        self,
+        client_options: ClientOptions,
+        settings: &Settings,
+    ) -> Result<Box<dyn StepFunctionsAction>, PawError> {
+        match self {
+            Command::RetryFailed(args) => {
+                let concurrency = args
+                    .concurrency
+                    .or(settings.retry.concurrency)
+                    .unwrap_or(DEFAULT_CONCURRENCY);
+                let idempotent_names = args.idempotent_names || settings.retry.idempotent_names;
+                let transform = args
+                    .transform
+                    .as_deref()
+                    .map(InputTransform::load)
+                    .transpose()?;
+
+                match (args.resume, args.machine) {
+                    (Some(journal), _) => Ok(Box::new(ResumeRetry::with_options(
+                        client_options,
+                        ResumeRetryOptions {
+                            journal,
+                            concurrency,
+                            summary_file: args.summary_file,
+                        },
+                    ))),
+                    (None, Some(machine)) => Ok(Box::new(RetryFailedExecution::with_options(
+                        client_options,
+                        RetryFailedOptions {
+                            machine: settings.resolve_machine(&machine).to_string(),
+                            statuses: args.statuses,
+                            range: args.range.date_range(settings)?,
+                            concurrency,
+                            summary_file: args.summary_file,
+                            journal: args.journal,
+                            idempotent_names,
+                            transform,
+                            redrive: args.redrive,
+                            dry_run: args.dry_run,
+                            yes: args.yes,
+                        },
+                    ))),
+                    (None, None) => unreachable!("clap requires --machine without --resume"),
+                }
+            }
+            Command::RedriveFailed(args) => Ok(Box::new(RetryFailedExecution::with_options(
+                client_options,
+                RetryFailedOptions {
+                    machine: settings.resolve_machine(&args.machine).to_string(),
+                    statuses: args.statuses,
+                    range: args.range.date_range(settings)?,
+                    concurrency: args
+                        .concurrency
+                        .or(settings.retry.concurrency)
+                        .unwrap_or(DEFAULT_CONCURRENCY),
+                    summary_file: args.summary_file,
+                    journal: args.journal,
+                    idempotent_names: args.idempotent_names || settings.retry.idempotent_names,
+                    transform: None,
+                    redrive: true,
+                    dry_run: args.dry_run,
+                    yes: args.yes,
+                },
+            ))),
+            Command::ListMachines(args) => {
+                let output = args.output.output_format(settings);
+                Ok(Box::new(ListMachines::new(
+                    client_options,
+                    output,
+                    args.machine_filter(),
+                )))
+            }
+            Command::DescribeMachine(args) => Ok(Box::new(DescribeMachine::with_options(
+                client_options,
+                DescribeMachineOptions {
+                    machine: settings.resolve_machine(&args.machine).to_string(),
+                    output: args.output.output_format(settings),
+                },
+            ))),
+            Command::Lint(args) => {
+                let output = args.output.output_format(settings);
+                let express = args.express;
+                let deny_warnings = args.deny_warnings;
+                Ok(Box::new(LintDefinition::with_options(
+                    client_options,
+                    LintOptions {
+                        definition: args.definition_source(settings),
+                        express,
+                        deny_warnings,
+                        output,
+                    },
+                )))
+            }
+            Command::Diagram(args) => {
+                let format = args.format;
+                match args.execution.clone() {
+                    Some(execution) => Ok(Box::new(DrawExecutionDiagram::with_options(
+                        client_options,
+                        ExecutionDiagramOptions { execution, format },
+                    ))),
+                    None => Ok(Box::new(DrawDiagram::with_options(
+                        client_options,
+                        DiagramOptions {
+                            definition: args.definition_source(settings),
+                            format,
+                        },
+                    ))),
+                }
+            }
+            Command::ListExecutions(args) => Ok(Box::new(ListExecutions::with_options(
+                client_options,
+                ListExecutionsOptions {
+                    machine: settings.resolve_machine(&args.machine).to_string(),
+                    statuses: args.statuses,
+                    range: args.range.date_range(settings)?,
+                    output: args.output.output_format(settings),
+                },
+            ))),
+            Command::ListFailed(args) => Ok(Box::new(ListExecutions::with_options(
+                client_options,
+                ListExecutionsOptions {
+                    machine: settings.resolve_machine(&args.machine).to_string(),
+                    statuses: vec![ExecutionStatus::Failed],
+                    range: args.range.date_range(settings)?,
+                    output: args.output.output_format(settings),
+                },
+            ))),
+            Command::InspectExecution(args) => Ok(Box::new(InspectExecution::with_options(
+                client_options,
+                InspectExecutionOptions {
+                    execution: args.execution,
+                    include_data: args.include_data,
+                    output: args.output.output_format(settings),
+                },
+            ))),
+            Command::StopExecutions(args) => Ok(Box::new(StopRunningExecutions::with_options(
+                client_options,
+                StopExecutionsOptions {
+                    machine: settings.resolve_machine(&args.machine).to_string(),
+                    range: args.range.date_range(settings)?,
+                    name_filter: name_filter(args.name_prefix, args.name_regex),
+                    older_than: args.older_than,
+                    error: args.error,
+                    cause: args.cause,
+                    concurrency: args
+                        .concurrency
+                        .or(settings.retry.concurrency)
+                        .unwrap_or(DEFAULT_CONCURRENCY),
+                    summary_file: args.summary_file,
+                    dry_run: args.dry_run,
+                    yes: args.yes,
+                },
+            ))),
+        }
+    }
+}
+
+impl DateRangeArgs {
+    /// Range from the command line, with the missing dates taken from the configuration file.
+    fn date_range(&self, settings: &Settings) -> Result<DateRange, SettingsError> {
+        Ok(DateRange::new(
+            or_default_date(self.since, &settings.range.since)?,
+            or_default_date(self.until, &settings.range.until)?,
+        ))
+    }
+}
+
+impl OutputArgs {
+    fn output_format(&self, settings: &Settings) -> OutputFormat {
+        self.output.or(settings.output).unwrap_or_default()
+    }
+}
+
+fn definition_source(
+    definition: Option<PathBuf>,
+    machine: Option<String>,
+    settings: &Settings,
+) -> DefinitionSource {
+    match (definition, machine) {
+        (Some(path), _) => DefinitionSource::File(path),
+        (None, Some(machine)) => {
+            DefinitionSource::Machine(settings.resolve_machine(&machine).to_string())
+        }
+        (None, None) => unreachable!("clap requires a definition file without --machine"),
+    }
+}
+
+fn name_filter(prefix: Option<String>, regex: Option<Regex>) -> Option<NameFilter> {
+    match (prefix, regex) {
+        (Some(prefix), _) => Some(NameFilter::Prefix(prefix)),
+        (None, Some(regex)) => Some(NameFilter::Regex(regex)),
+        (None, None) => None,
+    }
+}
+
+fn or_default_date(
+    date: Option<DateTime<Utc>>,
+    default_date: &Option<String>,
+) -> Result<Option<DateTime<Utc>>, SettingsError> {
+    match (date, default_date) {
+        (Some(date), _) => Ok(Some(date)),
+        (None, Some(default_date)) => parse_date_time(default_date)
+            .map(Some)
+            .map_err(|error| SettingsError::InvalidDate(default_date.clone(), error)),
+        (None, None) => Ok(None),
+    }
+}
+
+fn parse_retryable_status(raw_status: &str) -> Result<ExecutionStatus, String> {
+    let status = ExecutionStatus::from_str(raw_status, true)?;
+
+    if !ExecutionStatus::RETRYABLE.contains(&status) {
+        return Err(format!(
+            "only failed, timed-out and aborted executions can be retried, not {}",
+            raw_status
+        ));
+    }
+
+    Ok(status)
+}
+
+fn parse_concurrency(raw_concurrency: &str) -> Result<usize, String> {
+    match raw_concurrency.parse::<usize>() {
+        Ok(0) => Err("concurrency must be at least 1".to_string()),
+        Ok(concurrency) => Ok(concurrency),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+fn parse_duration(raw_duration: &str) -> Result<Duration, String> {
+    parse_relative_duration(raw_duration)
+        .ok_or_else(|| "expected an amount and a unit, m, h, d or w (ex. 30m)".to_string())
+}
+
+fn parse_date_time(raw_date_time: &str) -> Result<DateTime<Utc>, String> {
+    match parse_utc_date_time(raw_date_time.to_string()) {
+        Ok(Some(date)) => Ok(date),
+        Ok(None) => Err("date must not be empty".to_string()),
+        Err(PawError::InvalidInput(error)) => Err(error),
+        Err(error) => Err(error.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_parse_without_subcommand() {
+        let cli = Cli::try_parse_from(["sfn-paw"]).unwrap();
+
+        assert!(cli.command.is_none());
+        assert_eq!(
+            cli.client.client_options(&Settings::default()).unwrap(),
+            ClientOptions::default()
+        );
+    }
+
+    #[test]
+    fn should_parse_client_options_before_and_after_subcommand() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "--profile",
+            "staging",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--region",
+            "sa-east-1",
+            "--endpoint-url",
+            "http://localhost:8083",
+        ])
+        .unwrap();
+
+        assert_eq!(
+            cli.client.client_options(&Settings::default()).unwrap(),
+            ClientOptions {
+                profile: Some("staging".to_string()),
+                region: Some("sa-east-1".to_string()),
+                endpoint_url: Some(Uri::from_static("http://localhost:8083")),
+            }
+        );
+    }
+
+    #[test]
+    fn should_prefer_command_line_over_settings() {
+        let settings = Settings::parse(
+            r#"
+profile = "dinosaur"
+region = "us-east-1"
+
+[environments.local]
+endpoint_url = "http://localhost:8083"
+"#,
+        )
+        .unwrap();
+        let cli =
+            Cli::try_parse_from(["sfn-paw", "--env", "local", "--region", "sa-east-1"]).unwrap();
+
+        assert_eq!(
+            cli.client.client_options(&settings).unwrap(),
+            ClientOptions {
+                profile: Some("dinosaur".to_string()),
+                region: Some("sa-east-1".to_string()),
+                endpoint_url: Some(Uri::from_static("http://localhost:8083")),
+            }
+        );
+    }
+
+    #[test]
+    fn should_reject_relative_endpoint_url() {
+        let result = Cli::try_parse_from(["sfn-paw", "--endpoint-url", "localhost"]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_parse_retry_failed_subcommand() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--since",
+            "1989-09-30T22:10:32-03:00",
+            "--summary-file",
+            "summary.json",
+            "--yes",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RetryFailed(args)) => {
+                assert_eq!(args.machine, Some("PawMachine".to_string()));
+                assert_eq!(
+                    args.range.since,
+                    Some(
+                        DateTime::parse_from_rfc3339("1989-10-01T01:10:32Z")
+                            .unwrap()
+                            .with_timezone(&Utc)
+                    )
+                );
+                assert_eq!(args.range.until, None);
+                assert_eq!(args.statuses, vec![ExecutionStatus::Failed]);
+                assert_eq!(args.summary_file, Some(PathBuf::from("summary.json")));
+                assert!(!args.dry_run);
+                assert!(args.yes);
+            }
+            _ => panic!("expected retry-failed command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_retry_statuses() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--status",
+            "timed-out,aborted",
+            "--status",
+            "FAILED",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RetryFailed(args)) => assert_eq!(
+                args.statuses,
+                vec![
+                    ExecutionStatus::TimedOut,
+                    ExecutionStatus::Aborted,
+                    ExecutionStatus::Failed
+                ]
+            ),
+            _ => panic!("expected retry-failed command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--status",
+            "succeeded"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn should_parse_retry_concurrency() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--concurrency",
+            "16",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RetryFailed(args)) => assert_eq!(args.concurrency, Some(16)),
+            _ => panic!("expected retry-failed command"),
+        }
+
+        for concurrency in ["0", "-1", "many"] {
+            assert!(Cli::try_parse_from([
+                "sfn-paw",
+                "retry-failed",
+                "--machine",
+                "PawMachine",
+                "--concurrency",
+                concurrency
+            ])
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn should_parse_retry_dry_run() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--dry-run",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RetryFailed(args)) => assert!(args.dry_run && !args.yes && !args.redrive),
+            _ => panic!("expected retry-failed command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_retry_redrive() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--redrive",
+            "--yes",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RetryFailed(args)) => assert!(args.redrive && args.yes),
+            _ => panic!("expected retry-failed command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_redrive_failed() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "redrive-failed",
+            "--machine",
+            "PawMachine",
+            "--status",
+            "failed,timed-out",
+            "--since",
+            "24h",
+            "--yes",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::RedriveFailed(args)) => {
+                assert_eq!(args.machine, "PawMachine");
+                assert_eq!(
+                    args.statuses,
+                    vec![ExecutionStatus::Failed, ExecutionStatus::TimedOut]
+                );
+                assert!(args.range.since.is_some());
+                assert!(args.yes && !args.dry_run);
+            }
+            _ => panic!("expected redrive-failed command"),
+        }
+
+        let cli = Cli::try_parse_from(["sfn-paw", "redrive", "--machine", "PawMachine"]).unwrap();
+        assert!(matches!(cli.command, Some(Command::RedriveFailed(_))));
+        assert!(Cli::try_parse_from(["sfn-paw", "redrive-failed"]).is_err());
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "redrive-failed",
+            "--machine",
+            "PawMachine",
+            "--transform",
+            "fix.json"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn should_fail_on_missing_transform_file() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--transform",
+            "missing-transform.json",
+        ])
+        .unwrap();
+
+        let result = cli
+            .command
+            .unwrap()
+            .into_action(ClientOptions::default(), &Settings::default());
+
+        assert!(matches!(result, Err(PawError::Io(_))));
+    }
+
+    #[test]
+    fn should_parse_retry_resume() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "retry",
+            "--resume",
+            "batch.jsonl",
+            "--concurrency",
+            "4",
+        ])
+        .unwrap();
+
+        let action = match cli.command {
+            Some(Command::RetryFailed(args)) => {
+                assert_eq!(args.resume, Some(PathBuf::from("batch.jsonl")));
+                assert_eq!(args.concurrency, Some(4));
+                Command::RetryFailed(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap()
+            }
+            _ => panic!("expected retry-failed command"),
+        };
+        assert_eq!(action.name(), "Resume Interrupted Retry".to_string());
+
+        assert!(Cli::try_parse_from(["sfn-paw", "retry"]).is_err());
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "retry",
+            "--resume",
+            "batch.jsonl",
+            "--idempotent-names"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["sfn-paw", "retry", "--resume", "batch.jsonl", "--redrive"])
+                .is_err()
+        );
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "retry",
+            "--resume",
+            "batch.jsonl",
+            "--machine",
+            "PawMachine"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn should_accept_retry_alias() {
+        let cli = Cli::try_parse_from(["sfn-paw", "retry", "--machine", "PawMachine"]).unwrap();
+
+        assert!(matches!(cli.command, Some(Command::RetryFailed(_))));
+    }
+
+    #[test]
+    fn should_reject_invalid_dates() {
+        let result = Cli::try_parse_from([
+            "sfn-paw",
+            "retry-failed",
+            "--machine",
+            "PawMachine",
+            "--until",
+            "yesterday",
+        ]);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn should_map_command_into_action() {
+        let action = Command::RetryFailed(RetryFailedArgs {
+            machine: Some("PawMachine".to_string()),
+            resume: None,
+            journal: None,
+            idempotent_names: false,
+            transform: None,
+            redrive: false,
+            statuses: vec![ExecutionStatus::Failed],
+            range: DateRangeArgs {
+                since: None,
+                until: None,
+            },
+            concurrency: None,
+            summary_file: None,
+            dry_run: false,
+            yes: false,
+        })
+        .into_action(ClientOptions::default(), &Settings::default())
+        .unwrap();
+
+        assert_eq!(action.name(), "Retry Failed Executions".to_string());
+    }
+
+    #[test]
+    fn should_parse_list_commands() {
+        let cli = Cli::try_parse_from(["sfn-paw", "list-machines", "--output", "ndjson"]).unwrap();
+
+        match cli.command {
+            Some(Command::ListMachines(args)) => {
+                assert_eq!(args.output.output, Some(OutputFormat::Ndjson));
+                assert_eq!(args.machine_filter(), None);
+            }
+            _ => panic!("expected list-machines command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "list-failed",
+            "--machine",
+            "PawMachine",
+            "--until",
+            "1h",
+            "-o",
+            "csv",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::ListFailed(args)) => {
+                assert_eq!(args.machine, "PawMachine");
+                assert!(args.range.until.is_some());
+                assert_eq!(args.output.output, Some(OutputFormat::Csv));
+            }
+            _ => panic!("expected list-failed command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "list-executions",
+            "--machine",
+            "PawMachine",
+            "--status",
+            "running,succeeded",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::ListExecutions(args)) => assert_eq!(
+                args.statuses,
+                vec![ExecutionStatus::Running, ExecutionStatus::Succeeded]
+            ),
+            _ => panic!("expected list-executions command"),
+        }
+
+        let cli =
+            Cli::try_parse_from(["sfn-paw", "list-executions", "--machine", "PawMachine"]).unwrap();
+
+        match cli.command {
+            Some(Command::ListExecutions(args)) => assert!(args.statuses.is_empty()),
+            _ => panic!("expected list-executions command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_list_machines_filters() {
+        let cli =
+            Cli::try_parse_from(["sfn-paw", "list-machines", "--name-prefix", "orders"]).unwrap();
+
+        match cli.command {
+            Some(Command::ListMachines(args)) => assert_eq!(
+                args.machine_filter(),
+                Some(NameFilter::Prefix("orders".to_string()))
+            ),
+            _ => panic!("expected list-machines command"),
+        }
+
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "list-machines",
+            "--name-regex",
+            "^orders-v[0-9]+$",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::ListMachines(args)) => assert_eq!(
+                args.machine_filter(),
+                Some(NameFilter::Regex(Regex::new("^orders-v[0-9]+$").unwrap()))
+            ),
+            _ => panic!("expected list-machines command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_describe_machine() {
+        let cli =
+            Cli::try_parse_from(["sfn-paw", "describe-machine", "--machine", "orders"]).unwrap();
+
+        match cli.command {
+            Some(Command::DescribeMachine(args)) => {
+                assert_eq!(args.machine, "orders");
+                assert_eq!(args.output.output, None);
+
+                let action = Command::DescribeMachine(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Describe Machine".to_string());
+            }
+            _ => panic!("expected describe-machine command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_lint_of_a_definition_file() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "lint",
+            "dinosaur.json",
+            "--express",
+            "--deny-warnings",
+            "-o",
+            "json",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::Lint(args)) => {
+                assert_eq!(args.definition, Some(PathBuf::from("dinosaur.json")));
+                assert!(args.express);
+                assert!(args.deny_warnings);
+                assert_eq!(args.output.output, Some(OutputFormat::Json));
+
+                let action = Command::Lint(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Lint Machine Definition".to_string());
+            }
+            _ => panic!("expected lint command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_lint_of_a_deployed_machine() {
+        let settings = Settings::parse(
+            r#"
+[machines]
+orders = "arn:aws:states:us-east-1:123456789012:stateMachine:orders"
+"#,
+        )
+        .unwrap();
+        let cli = Cli::try_parse_from(["sfn-paw", "lint", "--machine", "orders"]).unwrap();
+
+        match cli.command {
+            Some(Command::Lint(args)) => assert_eq!(
+                args.definition_source(&settings),
+                DefinitionSource::Machine(
+                    "arn:aws:states:us-east-1:123456789012:stateMachine:orders".to_string()
+                )
+            ),
+            _ => panic!("expected lint command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_diagram_of_a_definition_file() {
+        let cli = Cli::try_parse_from(["sfn-paw", "diagram", "dinosaur.json", "--format", "dot"])
+            .unwrap();
+
+        match cli.command {
+            Some(Command::Diagram(args)) => {
+                assert_eq!(args.definition, Some(PathBuf::from("dinosaur.json")));
+                assert_eq!(args.format, DiagramFormat::Dot);
+
+                let action = Command::Diagram(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Draw Machine Diagram".to_string());
+            }
+            _ => panic!("expected diagram command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_diagram_of_a_deployed_machine_as_mermaid() {
+        let cli = Cli::try_parse_from(["sfn-paw", "diagram", "--machine", "orders"]).unwrap();
+
+        match cli.command {
+            Some(Command::Diagram(args)) => {
+                assert_eq!(args.format, DiagramFormat::Mermaid);
+                assert_eq!(
+                    args.definition_source(&Settings::default()),
+                    DefinitionSource::Machine("orders".to_string())
+                );
+            }
+            _ => panic!("expected diagram command"),
+        }
+    }
+
+    #[test]
+    fn should_parse_diagram_of_an_execution() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "diagram",
+            "--execution",
+            "dinosaur::arn",
+            "-f",
+            "dot",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::Diagram(args)) => {
+                assert_eq!(args.execution, Some("dinosaur::arn".to_string()));
+                assert_eq!(args.format, DiagramFormat::Dot);
+
+                let action = Command::Diagram(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Draw Execution Diagram".to_string());
+            }
+            _ => panic!("expected diagram command"),
+        }
+
+        assert!(Cli::try_parse_from(["sfn-paw", "diagram"]).is_err());
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "diagram",
+            "--machine",
+            "orders",
+            "--execution",
+            "dinosaur::arn"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn should_require_a_definition_file_or_a_machine_to_lint() {
+        assert!(Cli::try_parse_from(["sfn-paw", "lint"]).is_err());
+        assert!(
+            Cli::try_parse_from(["sfn-paw", "lint", "dinosaur.json", "--machine", "orders"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn should_parse_inspect_execution() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "inspect-execution",
+            "--execution",
+            "dinosaur::arn",
+            "--include-data",
+            "-o",
+            "json",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::InspectExecution(args)) => {
+                assert_eq!(args.execution, "dinosaur::arn");
+                assert!(args.include_data);
+                assert_eq!(args.output.output, Some(OutputFormat::Json));
+
+                let action = Command::InspectExecution(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Inspect Execution".to_string());
+            }
+            _ => panic!("expected inspect-execution command"),
+        }
+
+        assert!(Cli::try_parse_from(["sfn-paw", "inspect-execution"]).is_err());
+    }
+
+    #[test]
+    fn should_parse_stop_executions() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "stop-executions",
+            "--machine",
+            "PawMachine",
+            "--name-prefix",
+            "deploy-",
+            "--older-than",
+            "30m",
+            "--error",
+            "BadDeploy",
+            "--yes",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::StopExecutions(args)) => {
+                assert_eq!(args.name_prefix, Some("deploy-".to_string()));
+                assert_eq!(args.older_than, Duration::try_minutes(30));
+                assert_eq!(args.error, Some("BadDeploy".to_string()));
+                assert_eq!(args.cause, None);
+                assert!(args.yes);
+
+                let action = Command::StopExecutions(args)
+                    .into_action(ClientOptions::default(), &Settings::default())
+                    .unwrap();
+                assert_eq!(action.name(), "Stop Running Executions".to_string());
+            }
+            _ => panic!("expected stop-executions command"),
+        }
+
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "stop-executions",
+            "--machine",
+            "PawMachine",
+            "--older-than",
+            "soon",
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn should_parse_stop_executions_dry_run() {
+        let cli = Cli::try_parse_from([
+            "sfn-paw",
+            "stop-executions",
+            "--machine",
+            "PawMachine",
+            "--dry-run",
+            "--summary-file",
+            "stopped.json",
+        ])
+        .unwrap();
+
+        match cli.command {
+            Some(Command::StopExecutions(args)) => {
+                assert!(args.dry_run && !args.yes);
+                assert_eq!(args.summary_file, Some(PathBuf::from("stopped.json")));
+            }
+            _ => panic!("expected stop-executions command"),
+        }
+    }
+
+    #[test]
+    fn should_reject_conflicting_or_invalid_machine_filters() {
+        assert!(Cli::try_parse_from([
+            "sfn-paw",
+            "list-machines",
+            "--name-prefix",
+            "orders",
+            "--name-regex",
+            "orders"
+        ])
+        .is_err());
+        assert!(
+            Cli::try_parse_from(["sfn-paw", "list-machines", "--name-regex", "orders-("]).is_err()
+        );
+    }
+
+    #[test]
+    fn should_reject_journal_with_dry_run() {
+        for command in ["retry-failed", "redrive-failed"] {
+            assert!(Cli::try_parse_from([
+                "sfn-paw",
+                command,
+                "--machine",
+                "PawMachine",
+                "--journal",
+                "batch.jsonl",
+                "--dry-run"
+            ])
+            .is_err());
+        }
+    }
+
+    #[test]
+    fn should_reject_unknown_output_format() {
+        assert!(Cli::try_parse_from(["sfn-paw", "list-machines", "--output", "xml"]).is_err());
+    }
+
+    #[test]
+    fn should_prefer_output_argument_over_settings() {
+        let settings = Settings::parse("output = \"json\"").unwrap();
+
+        assert_eq!(
+            OutputArgs { output: None }.output_format(&settings),
+            OutputFormat::Json
+        );
+        assert_eq!(
+            OutputArgs {
+                output: Some(OutputFormat::Csv)
+            }
+            .output_format(&settings),
+            OutputFormat::Csv
+        );
+        assert_eq!(
+            OutputArgs { output: None }.output_format(&Settings::default()),
+            OutputFormat::Table
+        );
+    }
+
+    #[test]
+    fn should_fill_missing_dates_from_settings() {
+        let until = DateTime::parse_from_rfc3339("1989-10-01T01:10:32Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let settings = Settings::parse("[range]\nsince = \"24h\"").unwrap();
+
+        let start_date = or_default_date(None, &settings.range.since).unwrap();
+        let end_date = or_default_date(Some(until), &settings.range.until).unwrap();
+
+        assert!(start_date.unwrap() < Utc::now());
+        assert_eq!(end_date, Some(until));
+    }
+
+    #[test]
+    fn should_fail_on_invalid_default_dates() {
+        let settings = Settings::parse("[range]\nsince = \"yesterday\"").unwrap();
+
+        assert!(matches!(
+            or_default_date(None, &settings.range.since),
+            Err(SettingsError::InvalidDate(_, _))
+        ));
+    }
+}
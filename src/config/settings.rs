@@ -0,0 +1,299 @@
+use crate::aws::model::ClientOptions;
+use crate::output::OutputFormat;
+use http::Uri;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+#[derive(Clone, Default, PartialEq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Settings {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub output: Option<OutputFormat>,
+    pub range: RangeSettings,
+    pub retry: RetrySettings,
+    pub environments: HashMap<String, EnvironmentSettings>,
+    pub machines: HashMap<String, String>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RangeSettings {
+    pub since: Option<String>,
+    pub until: Option<String>,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RetrySettings {
+    pub concurrency: Option<usize>,
+    /// Executions described or read at the same time before a batch, which changes nothing.
+    pub read_concurrency: Option<usize>,
+    pub idempotent_names: bool,
+}
+
+#[derive(Clone, Default, PartialEq, Debug, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnvironmentSettings {
+    pub profile: Option<String>,
+    pub region: Option<String>,
+    pub endpoint_url: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum SettingsError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+    UnknownEnvironment(String),
+    InvalidEndpoint(String, String),
+    InvalidDate(String, String),
+}
+
+impl Display for SettingsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            SettingsError::Io(path, error) => {
+                write!(f, "could not read {}: {}", path.display(), error)
+            }
+            SettingsError::Parse(path, error) => {
+                write!(f, "invalid configuration in {}: {}", path.display(), error)
+            }
+            SettingsError::UnknownEnvironment(name) => {
+                write!(f, "environment '{}' is not configured", name)
+            }
+            SettingsError::InvalidEndpoint(endpoint_url, error) => {
+                write!(f, "invalid endpoint_url '{}': {}", endpoint_url, error)
+            }
+            SettingsError::InvalidDate(date, error) => {
+                write!(f, "invalid date '{}': {}", date, error)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SettingsError {}
+
+impl Settings {
+    pub fn default_path() -> Option<PathBuf> {
+        env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| dirs::home_dir().map(|home| home.join(".config")))
+            .map(|config_dir| config_dir.join("paw").join("config.toml"))
+    }
+
+    /// Loads the settings from `path`, or from the default location when no path is given.
+    /// A missing file at the default location is not an error, paw runs with empty settings.
+    pub fn load(path: Option<&Path>) -> Result<Settings, SettingsError> {
+        let (path, required) = match path {
+            Some(path) => (path.to_path_buf(), true),
+            None => match Settings::default_path() {
+                Some(path) => (path, false),
+                None => return Ok(Settings::default()),
+            },
+        };
+
+        match fs::read_to_string(&path) {
+            Ok(raw_settings) => Settings::parse(&raw_settings)
+                .map_err(|error| SettingsError::Parse(path.clone(), error)),
+            Err(error) if error.kind() == io::ErrorKind::NotFound && !required => {
+                Ok(Settings::default())
+            }
+            Err(error) => Err(SettingsError::Io(path, error)),
+        }
+    }
+
+    pub fn parse(raw_settings: &str) -> Result<Settings, toml::de::Error> {
+        toml::from_str(raw_settings)
+    }
+
+    /// Default client options, with the values of `environment` taking precedence over the
+    /// top level profile and region.
+    pub fn client_options(
+        &self,
+        environment: Option<&str>,
+    ) -> Result<ClientOptions, SettingsError> {
+        let environment = match environment {
+            Some(name) => Some(
+                self.environments
+                    .get(name)
+                    .ok_or_else(|| SettingsError::UnknownEnvironment(name.to_string()))?,
+            ),
+            None => None,
+        };
+
+        let endpoint_url = match environment.and_then(|env| env.endpoint_url.as_ref()) {
+            Some(endpoint_url) => Some(
+                parse_endpoint_url(endpoint_url)
+                    .map_err(|error| SettingsError::InvalidEndpoint(endpoint_url.clone(), error))?,
+            ),
+            None => None,
+        };
+
+        Ok(ClientOptions {
+            profile: environment
+                .and_then(|env| env.profile.clone())
+                .or_else(|| self.profile.clone()),
+            region: environment
+                .and_then(|env| env.region.clone())
+                .or_else(|| self.region.clone()),
+            endpoint_url,
+        })
+    }
+
+    /// Resolves a machine alias into its ARN, anything else is returned unchanged.
+    pub fn resolve_machine<'a>(&'a self, machine: &'a str) -> &'a str {
+        self.machines
+            .get(machine)
+            .map(|arn| arn.as_str())
+            .unwrap_or(machine)
+    }
+}
+
+/// Endpoint given on the command line or in the settings, which must be an absolute url.
+pub fn parse_endpoint_url(raw_endpoint_url: &str) -> Result<Uri, String> {
+    let endpoint_url = raw_endpoint_url
+        .parse::<Uri>()
+        .map_err(|error| error.to_string())?;
+
+    if endpoint_url.scheme().is_none() || endpoint_url.host().is_none() {
+        return Err("endpoint must be an absolute url (ex. http://localhost:8083)".to_string());
+    }
+
+    Ok(endpoint_url)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SETTINGS: &str = r#"
+profile = "dinosaur"
+region = "sa-east-1"
+output = "json"
+
+[range]
+since = "24h"
+
+[retry]
+concurrency = 8
+read_concurrency = 20
+idempotent_names = true
+
+[environments.local]
+endpoint_url = "http://localhost:8083"
+region = "us-east-1"
+
+[environments.staging]
+profile = "dinosaur-staging"
+
+[machines]
+orders = "arn:aws:states:sa-east-1:123456789012:stateMachine:orders"
+"#;
+
+    #[test]
+    fn should_parse_settings() {
+        let settings = Settings::parse(SETTINGS).unwrap();
+
+        assert_eq!(settings.profile, Some("dinosaur".to_string()));
+        assert_eq!(settings.region, Some("sa-east-1".to_string()));
+        assert_eq!(settings.output, Some(OutputFormat::Json));
+        assert_eq!(settings.range.since, Some("24h".to_string()));
+        assert_eq!(settings.range.until, None);
+        assert_eq!(settings.retry.concurrency, Some(8));
+        assert_eq!(settings.retry.read_concurrency, Some(20));
+        assert!(settings.retry.idempotent_names);
+        assert_eq!(settings.environments.len(), 2);
+    }
+
+    #[test]
+    fn should_parse_empty_settings() {
+        assert_eq!(Settings::parse("").unwrap(), Settings::default());
+    }
+
+    #[test]
+    fn should_reject_unknown_keys() {
+        assert!(Settings::parse("regoin = \"sa-east-1\"").is_err());
+    }
+
+    #[test]
+    fn should_return_top_level_client_options() {
+        let settings = Settings::parse(SETTINGS).unwrap();
+
+        assert_eq!(
+            settings.client_options(None).unwrap(),
+            ClientOptions {
+                profile: Some("dinosaur".to_string()),
+                region: Some("sa-east-1".to_string()),
+                endpoint_url: None,
+            }
+        );
+    }
+
+    #[test]
+    fn should_override_client_options_with_environment() {
+        let settings = Settings::parse(SETTINGS).unwrap();
+
+        assert_eq!(
+            settings.client_options(Some("local")).unwrap(),
+            ClientOptions {
+                profile: Some("dinosaur".to_string()),
+                region: Some("us-east-1".to_string()),
+                endpoint_url: Some(Uri::from_static("http://localhost:8083")),
+            }
+        );
+        assert_eq!(
+            settings.client_options(Some("staging")).unwrap(),
+            ClientOptions {
+                profile: Some("dinosaur-staging".to_string()),
+                region: Some("sa-east-1".to_string()),
+                endpoint_url: None,
+            }
+        );
+    }
+
+    #[test]
+    fn should_fail_on_unknown_environment() {
+        let settings = Settings::parse(SETTINGS).unwrap();
+
+        assert!(matches!(
+            settings.client_options(Some("production")),
+            Err(SettingsError::UnknownEnvironment(_))
+        ));
+    }
+
+    #[test]
+    fn should_fail_on_relative_environment_endpoint() {
+        let settings =
+            Settings::parse("[environments.local]\nendpoint_url = \"localhost:8083\"").unwrap();
+
+        assert!(matches!(
+            settings.client_options(Some("local")),
+            Err(SettingsError::InvalidEndpoint(endpoint_url, _)) if endpoint_url == "localhost:8083"
+        ));
+    }
+
+    #[test]
+    fn should_resolve_machine_alias() {
+        let settings = Settings::parse(SETTINGS).unwrap();
+
+        assert_eq!(
+            settings.resolve_machine("orders"),
+            "arn:aws:states:sa-east-1:123456789012:stateMachine:orders"
+        );
+        assert_eq!(settings.resolve_machine("PawMachine"), "PawMachine");
+    }
+
+    #[test]
+    fn should_fail_when_given_file_is_missing() {
+        let missing = env::temp_dir().join("paw-missing-settings.toml");
+
+        assert!(matches!(
+            Settings::load(Some(&missing)),
+            Err(SettingsError::Io(_, _))
+        ));
+    }
+}
@@ -0,0 +1,306 @@
+use crate::config::settings::SettingsError;
+use crate::output::count;
+use aws_sdk_sfn::error::{ProvideErrorMetadata, SdkError};
+use aws_smithy_runtime_api::http::Response;
+use aws_smithy_types::retry::ProvideErrorKind;
+use std::error::Error as StdError;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::io;
+
+/// SDK errors are boxed, they are several times larger than the other variants and would make
+/// every `Result` returned by paw as large.
+#[derive(Debug)]
+pub enum PawError {
+    Sdk(Box<aws_sdk_sfn::Error>),
+    Transient(Box<aws_sdk_sfn::Error>),
+    Cancelled,
+    InvalidInput(String),
+    MissingField(&'static str),
+    Io(io::Error),
+    Config(SettingsError),
+    PartialFailure(usize),
+    LintFailed(usize),
+}
+
+impl PawError {
+    /// Process exit code for the error, distinct for each kind of failure so scripts can react
+    /// to them.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            PawError::InvalidInput(_) => 2,
+            PawError::Sdk(_) => 3,
+            PawError::MissingField(_) => 4,
+            PawError::Io(_) => 5,
+            PawError::Config(_) => 6,
+            PawError::PartialFailure(_) => 7,
+            PawError::LintFailed(_) => 8,
+            PawError::Transient(_) => 75,
+            PawError::Cancelled => 130,
+        }
+    }
+
+    /// Whether the same request may succeed when sent again, as for timeouts and throttling.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, PawError::Transient(_))
+    }
+
+    /// Whether Step Functions turned the request down to slow the caller down, so it surely
+    /// did not act on it, unlike a request that timed out.
+    pub fn is_throttling(&self) -> bool {
+        match self {
+            PawError::Transient(error) => error
+                .code()
+                .is_some_and(|code| THROTTLING_CODES.contains(&code)),
+            _ => false,
+        }
+    }
+}
+
+impl Display for PawError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match self {
+            PawError::Sdk(error) => {
+                write!(f, "Step Functions request failed: {}", sdk_message(error))
+            }
+            PawError::Transient(error) => write!(
+                f,
+                "Step Functions request failed temporarily: {}",
+                sdk_message(error)
+            ),
+            PawError::Cancelled => write!(f, "Cancelled by the user"),
+            PawError::InvalidInput(message) => write!(f, "Invalid input: {}", message),
+            PawError::MissingField(field) => {
+                write!(f, "Step Functions response is missing the {} field", field)
+            }
+            PawError::Io(error) => write!(f, "I/O error: {}", error),
+            PawError::Config(error) => write!(f, "Configuration error: {}", error),
+            PawError::PartialFailure(failed) => {
+                write!(f, "{} of the batch failed", count(*failed, "execution"))
+            }
+            PawError::LintFailed(problems) => {
+                write!(f, "{} found in the definition", count(*problems, "problem"))
+            }
+        }
+    }
+}
+
+impl std::error::Error for PawError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PawError::Sdk(error) | PawError::Transient(error) => Some(error.as_ref()),
+            PawError::Io(error) => Some(error),
+            PawError::Config(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// Message of an SDK error. Errors the SDK does not model only print their code, so their
+/// message, or the chain of causes when the request never reached the service, is added to it.
+fn sdk_message(error: &aws_sdk_sfn::Error) -> String {
+    let mut message = error.to_string();
+
+    match error.message() {
+        Some(detail) if !message.contains(detail) => message = format!("{}: {}", message, detail),
+        Some(_) => {}
+        None => {
+            let mut source = error.source();
+            while let Some(cause) = source {
+                message = format!("{}: {}", message, cause);
+                source = cause.source();
+            }
+        }
+    }
+
+    message
+}
+
+impl From<aws_sdk_sfn::Error> for PawError {
+    fn from(error: aws_sdk_sfn::Error) -> Self {
+        PawError::Sdk(Box::new(error))
+    }
+}
+
+/// Error codes Step Functions answers with when the caller should slow down.
+const THROTTLING_CODES: [&str; 4] = [
+    "ThrottlingException",
+    "Throttling",
+    "TooManyRequestsException",
+    "RequestLimitExceeded",
+];
+
+impl<E> From<SdkError<E, Response>> for PawError
+where
+    aws_sdk_sfn::Error: From<SdkError<E, Response>>,
+    E: ProvideErrorKind,
+{
+    fn from(error: SdkError<E, Response>) -> Self {
+        if is_transient(&error) {
+            PawError::Transient(Box::new(error.into()))
+        } else {
+            PawError::Sdk(Box::new(error.into()))
+        }
+    }
+}
+
+fn is_transient<E: ProvideErrorKind>(error: &SdkError<E, Response>) -> bool {
+    match error {
+        SdkError::TimeoutError(_) | SdkError::DispatchFailure(_) => true,
+        SdkError::ResponseError(context) => context.raw().status().is_server_error(),
+        SdkError::ServiceError(context) => {
+            let status = context.raw().status();
+            context.err().retryable_error_kind().is_some()
+                || context
+                    .err()
+                    .code()
+                    .is_some_and(|code| THROTTLING_CODES.contains(&code))
+                || status.is_server_error()
+                || status.as_u16() == 429
+        }
+        _ => false,
+    }
+}
+
+impl From<io::Error> for PawError {
+    fn from(error: io::Error) -> Self {
+        PawError::Io(error)
+    }
+}
+
+impl From<SettingsError> for PawError {
+    fn from(error: SettingsError) -> Self {
+        PawError::Config(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aws_sdk_sfn::error::ErrorMetadata;
+    use aws_sdk_sfn::operation::start_execution::StartExecutionError;
+    use aws_sdk_sfn::types::error::{ExecutionLimitExceeded, InvalidArn};
+    use aws_smithy_runtime_api::http::StatusCode;
+    use aws_smithy_types::body::SdkBody;
+    use std::collections::HashSet;
+
+    fn all_errors() -> Vec<PawError> {
+        vec![
+            PawError::Sdk(Box::new(aws_sdk_sfn::Error::InvalidArn(
+                InvalidArn::builder().message("boom").build(),
+            ))),
+            PawError::Transient(Box::new(aws_sdk_sfn::Error::ExecutionLimitExceeded(
+                ExecutionLimitExceeded::builder()
+                    .message("slow down")
+                    .build(),
+            ))),
+            PawError::Cancelled,
+            PawError::InvalidInput("machine not found: dinosaur".to_string()),
+            PawError::MissingField("input"),
+            PawError::Io(io::Error::new(io::ErrorKind::BrokenPipe, "broken pipe")),
+            PawError::Config(SettingsError::UnknownEnvironment("local".to_string())),
+            PawError::PartialFailure(2),
+            PawError::LintFailed(3),
+        ]
+    }
+
+    #[test]
+    fn should_have_distinct_exit_codes() {
+        let exit_codes: HashSet<i32> = all_errors().iter().map(|error| error.exit_code()).collect();
+
+        assert_eq!(exit_codes.len(), all_errors().len());
+        assert!(!exit_codes.contains(&0));
+        assert!(!exit_codes.contains(&1));
+    }
+
+    #[test]
+    fn should_print_readable_messages() {
+        let messages: Vec<String> = all_errors().iter().map(|error| error.to_string()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "Step Functions request failed: InvalidArn: boom",
+                "Step Functions request failed temporarily: ExecutionLimitExceeded: slow down",
+                "Cancelled by the user",
+                "Invalid input: machine not found: dinosaur",
+                "Step Functions response is missing the input field",
+                "I/O error: broken pipe",
+                "Configuration error: environment 'local' is not configured",
+                "2 executions of the batch failed",
+                "3 problems found in the definition",
+            ]
+        );
+    }
+
+    #[test]
+    fn should_print_counts_of_one_in_the_singular() {
+        assert_eq!(
+            PawError::PartialFailure(1).to_string(),
+            "1 execution of the batch failed"
+        );
+        assert_eq!(
+            PawError::LintFailed(1).to_string(),
+            "1 problem found in the definition"
+        );
+    }
+
+    #[test]
+    fn should_convert_from_io_and_settings_errors() {
+        let io_error: PawError = io::Error::new(io::ErrorKind::NotFound, "gone").into();
+        let settings_error: PawError =
+            SettingsError::UnknownEnvironment("local".to_string()).into();
+
+        assert!(matches!(io_error, PawError::Io(_)));
+        assert!(matches!(settings_error, PawError::Config(_)));
+    }
+
+    fn service_error(code: &str, status: u16) -> SdkError<StartExecutionError, Response> {
+        SdkError::service_error(
+            StartExecutionError::generic(
+                ErrorMetadata::builder()
+                    .code(code)
+                    .message("request failed")
+                    .build(),
+            ),
+            Response::new(StatusCode::try_from(status).unwrap(), SdkBody::empty()),
+        )
+    }
+
+    #[test]
+    fn should_flag_transient_sdk_errors_as_retryable() {
+        let timeout: PawError =
+            SdkError::<StartExecutionError, Response>::timeout_error("timeout").into();
+        let throttled: PawError = service_error("ThrottlingException", 400).into();
+        let unavailable: PawError = service_error("ServiceUnavailable", 503).into();
+        let invalid: PawError = service_error("InvalidExecutionInput", 400).into();
+
+        assert!(timeout.is_retryable());
+        assert!(throttled.is_retryable());
+        assert!(unavailable.is_retryable());
+        assert!(!invalid.is_retryable());
+        assert!(matches!(invalid, PawError::Sdk(_)));
+        assert!(!PawError::MissingField("input").is_retryable());
+    }
+
+    #[test]
+    fn should_tell_throttling_from_other_transient_errors() {
+        let timeout: PawError =
+            SdkError::<StartExecutionError, Response>::timeout_error("timeout").into();
+        let throttled: PawError = service_error("ThrottlingException", 400).into();
+        let unavailable: PawError = service_error("ServiceUnavailable", 503).into();
+
+        assert!(throttled.is_throttling());
+        assert!(!timeout.is_throttling());
+        assert!(!unavailable.is_throttling());
+    }
+
+    #[test]
+    fn should_print_message_of_unmodeled_errors() {
+        let invalid: PawError = service_error("InvalidExecutionInput", 400).into();
+
+        assert_eq!(
+            invalid.to_string(),
+            "Step Functions request failed: unhandled error (InvalidExecutionInput): request failed"
+        );
+    }
+}
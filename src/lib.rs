@@ -1,6 +1,11 @@
 mod actions;
+pub mod asl;
 mod aws;
+mod output;
 
+pub mod cli;
 pub mod config;
+pub mod error;
+mod transform;
 
-pub use actions::get_actions;
+pub use actions::{get_actions, StepFunctionsAction};
@@ -1,25 +1,42 @@
-mod actions;
-mod aws;
-
-use actions::get_actions;
-use aws_sdk_sfn::Error;
+use clap::Parser;
 use console::Term;
 use dialoguer::{theme::ColorfulTheme, Select};
+use sfn_paw::cli::Cli;
+use sfn_paw::config::settings::Settings;
 use sfn_paw::config::telemetry::{get_subscriber, init_subscriber};
+use sfn_paw::error::PawError;
+use sfn_paw::get_actions;
+use std::process;
 
 #[tokio::main]
-async fn main() -> Result<(), Error> {
-    let subscriber = get_subscriber("paw".into(), "info".into(), std::io::stdout);
+async fn main() {
+    let subscriber = get_subscriber("paw".into(), "info".into(), std::io::stderr);
     init_subscriber(subscriber);
 
-    let actions = get_actions();
+    if let Err(error) = run(Cli::parse()).await {
+        eprintln!("{}", error);
+        process::exit(error.exit_code());
+    }
+}
+
+async fn run(cli: Cli) -> Result<(), PawError> {
+    let settings = Settings::load(cli.config.as_deref())?;
+    let client_options = cli.client.client_options(&settings)?;
+    if let Some(command) = cli.command {
+        return command
+            .into_action(client_options, &settings)?
+            .execute()
+            .await;
+    }
+
+    let actions = get_actions(client_options, settings);
 
     let selected_action = Select::with_theme(&ColorfulTheme::default())
         .with_prompt("Select the Action:")
         .items(&actions)
         .default(0)
-        .interact_on(&Term::buffered_stderr())
-        .unwrap();
+        .interact_on_opt(&Term::buffered_stderr())?
+        .ok_or(PawError::Cancelled)?;
 
     actions[selected_action].execute().await
 }
@@ -0,0 +1,198 @@
+use chrono::Duration;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::io::{Result as IoResult, Write};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Debug, Deserialize, ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+    Ndjson,
+    Csv,
+}
+
+/// Row based view of a model, used by the table and csv formats.
+pub trait Tabular {
+    fn headers() -> Vec<&'static str>;
+
+    fn row(&self) -> Vec<String>;
+}
+
+pub fn render<T, W>(items: &[T], format: OutputFormat, writer: &mut W) -> IoResult<()>
+where
+    T: Serialize + Tabular,
+    W: Write,
+{
+    match format {
+        OutputFormat::Table => render_table(items, writer),
+        OutputFormat::Json => {
+            serde_json::to_writer_pretty(&mut *writer, items)?;
+            writeln!(writer)
+        }
+        OutputFormat::Ndjson => {
+            for item in items {
+                serde_json::to_writer(&mut *writer, item)?;
+                writeln!(writer)?;
+            }
+            Ok(())
+        }
+        OutputFormat::Csv => {
+            let mut csv_writer = csv::Writer::from_writer(writer);
+            csv_writer.write_record(T::headers())?;
+            for item in items {
+                csv_writer.write_record(item.row())?;
+            }
+            csv_writer.flush()
+        }
+    }
+}
+
+/// Count followed by its noun, in the plural unless the count is 1 (ex. 1 error, 2 errors).
+pub fn count(count: usize, noun: &str) -> String {
+    match count {
+        1 => format!("1 {}", noun),
+        count => format!("{} {}s", count, noun),
+    }
+}
+
+/// Time since the execution started, ex. +0.250s, +2m05.000s or +1h02m05s.
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let millis = elapsed.num_milliseconds().max(0);
+    let (hours, minutes, seconds) = (millis / 3_600_000, millis / 60_000 % 60, millis / 1000 % 60);
+
+    if hours > 0 {
+        format!("+{}h{:02}m{:02}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("+{}m{:02}.{:03}s", minutes, seconds, millis % 1000)
+    } else {
+        format!("+{}.{:03}s", seconds, millis % 1000)
+    }
+}
+
+fn render_table<T: Tabular, W: Write>(items: &[T], writer: &mut W) -> IoResult<()> {
+    let headers: Vec<String> = T::headers()
+        .into_iter()
+        .map(|header| header.to_uppercase())
+        .collect();
+    let rows: Vec<Vec<String>> = items.iter().map(|item| item.row()).collect();
+
+    let mut widths: Vec<usize> = headers
+        .iter()
+        .map(|header| header.chars().count())
+        .collect();
+    for row in rows.iter() {
+        for (width, cell) in widths.iter_mut().zip(row.iter()) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    for row in std::iter::once(&headers).chain(rows.iter()) {
+        let line = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+            .collect::<Vec<String>>()
+            .join("  ");
+        writeln!(writer, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Dinosaur {
+        name: String,
+        era: String,
+    }
+
+    impl Tabular for Dinosaur {
+        fn headers() -> Vec<&'static str> {
+            vec!["name", "era"]
+        }
+
+        fn row(&self) -> Vec<String> {
+            vec![self.name.clone(), self.era.clone()]
+        }
+    }
+
+    fn dinosaurs() -> Vec<Dinosaur> {
+        vec![
+            Dinosaur {
+                name: "t-rex".to_string(),
+                era: "cretaceous".to_string(),
+            },
+            Dinosaur {
+                name: "stegosaurus".to_string(),
+                era: "jurassic, late".to_string(),
+            },
+        ]
+    }
+
+    fn render_to_string(format: OutputFormat) -> String {
+        let mut output = vec![];
+        render(&dinosaurs(), format, &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn should_render_table() {
+        assert_eq!(
+            render_to_string(OutputFormat::Table),
+            "NAME         ERA\nt-rex        cretaceous\nstegosaurus  jurassic, late\n"
+        );
+    }
+
+    #[test]
+    fn should_render_json() {
+        let rendered: serde_json::Value =
+            serde_json::from_str(&render_to_string(OutputFormat::Json)).unwrap();
+
+        assert_eq!(
+            rendered,
+            serde_json::json!([
+                {"name": "t-rex", "era": "cretaceous"},
+                {"name": "stegosaurus", "era": "jurassic, late"}
+            ])
+        );
+    }
+
+    #[test]
+    fn should_render_ndjson() {
+        assert_eq!(
+            render_to_string(OutputFormat::Ndjson),
+            "{\"name\":\"t-rex\",\"era\":\"cretaceous\"}\n{\"name\":\"stegosaurus\",\"era\":\"jurassic, late\"}\n"
+        );
+    }
+
+    #[test]
+    fn should_render_csv() {
+        assert_eq!(
+            render_to_string(OutputFormat::Csv),
+            "name,era\nt-rex,cretaceous\nstegosaurus,\"jurassic, late\"\n"
+        );
+    }
+
+    #[test]
+    fn should_format_elapsed_time() {
+        assert_eq!(format_elapsed(Duration::milliseconds(250)), "+0.250s");
+        assert_eq!(
+            format_elapsed(Duration::milliseconds(125_000)),
+            "+2m05.000s"
+        );
+        assert_eq!(format_elapsed(Duration::seconds(3725)), "+1h02m05s");
+    }
+
+    #[test]
+    fn should_render_empty_table() {
+        let mut output = vec![];
+        render::<Dinosaur, _>(&[], OutputFormat::Table, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "NAME  ERA\n");
+    }
+}
@@ -0,0 +1,629 @@
+use crate::error::PawError;
+use serde::{Deserialize, Serialize};
+use serde_json::{Deserializer, Value};
+use similar::TextDiff;
+use std::fmt::{Display, Formatter, Result as FmtResult};
+use std::fs;
+use std::path::Path;
+
+/// Transformation applied to the execution inputs before they are retried.
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InputTransform {
+    /// RFC 7396 JSON Merge Patch
+    MergePatch(Value),
+    /// RFC 6902 JSON Patch
+    JsonPatch(json_patch::Patch),
+    /// jq like assignments and deletions, ex. `.order.retry = true | del(.debug)`
+    Expression(Vec<Statement>),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Statement {
+    Set(Vec<Segment>, Operand),
+    Delete(Vec<Segment>),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Operand {
+    Literal(Value),
+    Path(Vec<Segment>),
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TransformError(String);
+
+impl Display for TransformError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for TransformError {}
+
+impl InputTransform {
+    /// Loads a transformation from `path`. A JSON array is read as a JSON Patch, any other
+    /// JSON value as a Merge Patch and everything else as an expression.
+    pub fn load(path: &Path) -> Result<InputTransform, PawError> {
+        let raw_transform = fs::read_to_string(path)?;
+
+        InputTransform::parse(&raw_transform).map_err(|error| {
+            PawError::InvalidInput(format!(
+                "invalid transformation {}: {}",
+                path.display(),
+                error
+            ))
+        })
+    }
+
+    pub fn parse(raw_transform: &str) -> Result<InputTransform, TransformError> {
+        match serde_json::from_str::<Value>(raw_transform) {
+            Ok(patch @ Value::Array(_)) => serde_json::from_value(patch)
+                .map(InputTransform::JsonPatch)
+                .map_err(|error| TransformError(format!("invalid JSON Patch: {}", error))),
+            Ok(patch) => Ok(InputTransform::MergePatch(patch)),
+            Err(_) => ExpressionParser::new(raw_transform)
+                .parse()
+                .map(InputTransform::Expression),
+        }
+    }
+
+    /// Applies the transformation to a JSON input, returning the transformed input as compact
+    /// JSON. Keys keep their order and numbers their digits.
+    pub fn apply(&self, input: &str) -> Result<String, TransformError> {
+        let mut document: Value = serde_json::from_str(input)
+            .map_err(|error| TransformError(format!("input is not valid JSON: {}", error)))?;
+
+        match self {
+            InputTransform::MergePatch(patch) => json_patch::merge(&mut document, patch),
+            InputTransform::JsonPatch(patch) => json_patch::patch(&mut document, patch)
+                .map_err(|error| TransformError(error.to_string()))?,
+            InputTransform::Expression(statements) => {
+                for statement in statements {
+                    statement.apply(&mut document)?;
+                }
+            }
+        }
+
+        Ok(document.to_string())
+    }
+}
+
+impl Statement {
+    fn apply(&self, document: &mut Value) -> Result<(), TransformError> {
+        match self {
+            Statement::Set(path, operand) => {
+                let value = match operand {
+                    Operand::Literal(value) => value.clone(),
+                    Operand::Path(source) => get(document, source).cloned().unwrap_or(Value::Null),
+                };
+                *get_or_create(document, path)? = value;
+                Ok(())
+            }
+            Statement::Delete(path) => {
+                delete(document, path);
+                Ok(())
+            }
+        }
+    }
+}
+
+fn get<'a>(document: &'a Value, path: &[Segment]) -> Option<&'a Value> {
+    path.iter()
+        .try_fold(document, |value, segment| match segment {
+            Segment::Key(key) => value.get(key),
+            Segment::Index(index) => value.get(index),
+        })
+}
+
+fn get_or_create<'a>(
+    document: &'a mut Value,
+    path: &[Segment],
+) -> Result<&'a mut Value, TransformError> {
+    let mut value = document;
+
+    for segment in path {
+        value = match segment {
+            Segment::Key(key) => {
+                if value.is_null() {
+                    *value = Value::Object(Default::default());
+                }
+                value
+                    .as_object_mut()
+                    .ok_or_else(|| TransformError(format!("cannot set .{} on a non object", key)))?
+                    .entry(key.clone())
+                    .or_insert(Value::Null)
+            }
+            Segment::Index(index) => {
+                let array = value.as_array_mut().ok_or_else(|| {
+                    TransformError(format!("cannot set [{}] on a non array", index))
+                })?;
+                if *index == array.len() {
+                    array.push(Value::Null);
+                }
+                let length = array.len();
+                array.get_mut(*index).ok_or_else(|| {
+                    TransformError(format!(
+                        "index [{}] is out of bounds for an array of {} items",
+                        index, length
+                    ))
+                })?
+            }
+        };
+    }
+
+    Ok(value)
+}
+
+fn delete(document: &mut Value, path: &[Segment]) {
+    let Some((last, parent_path)) = path.split_last() else {
+        *document = Value::Null;
+        return;
+    };
+
+    let parent = parent_path
+        .iter()
+        .try_fold(document, |value, segment| match segment {
+            Segment::Key(key) => value.get_mut(key),
+            Segment::Index(index) => value.get_mut(index),
+        });
+
+    match (parent, last) {
+        (Some(Value::Object(object)), Segment::Key(key)) => {
+            object.remove(key);
+        }
+        (Some(Value::Array(array)), Segment::Index(index)) if *index < array.len() => {
+            array.remove(*index);
+        }
+        _ => {}
+    }
+}
+
+struct ExpressionParser<'a> {
+    source: &'a str,
+    position: usize,
+}
+
+impl<'a> ExpressionParser<'a> {
+    fn new(source: &'a str) -> Self {
+        ExpressionParser {
+            source,
+            position: 0,
+        }
+    }
+
+    fn parse(mut self) -> Result<Vec<Statement>, TransformError> {
+        let mut statements = vec![];
+
+        loop {
+            self.skip_whitespace(true);
+            if self.rest().is_empty() {
+                break;
+            }
+
+            statements.push(self.parse_statement()?);
+
+            self.skip_whitespace(false);
+            match self.peek() {
+                None => break,
+                Some('\n') | Some('|') => self.position += 1,
+                Some(other) => return Err(self.error(&format!("unexpected '{}'", other))),
+            }
+        }
+
+        if statements.is_empty() {
+            return Err(TransformError("expression is empty".to_string()));
+        }
+
+        Ok(statements)
+    }
+
+    fn parse_statement(&mut self) -> Result<Statement, TransformError> {
+        if self.rest().starts_with("del(") {
+            self.position += "del(".len();
+            self.skip_whitespace(false);
+            let path = self.parse_path()?;
+            self.skip_whitespace(false);
+            self.expect(')')?;
+            return Ok(Statement::Delete(path));
+        }
+
+        let path = self.parse_path()?;
+        self.skip_whitespace(false);
+        self.expect('=')?;
+        self.skip_whitespace(false);
+
+        let operand = match self.peek() {
+            Some('.') => Operand::Path(self.parse_path()?),
+            _ => Operand::Literal(self.parse_json()?),
+        };
+
+        Ok(Statement::Set(path, operand))
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<Segment>, TransformError> {
+        if self.peek() != Some('.') {
+            return Err(self.error("expected a path starting with '.'"));
+        }
+
+        let mut path = vec![];
+        loop {
+            match self.peek() {
+                Some('.') => {
+                    self.position += 1;
+                    match self.peek() {
+                        Some('"') => match self.parse_json()? {
+                            Value::String(key) => path.push(Segment::Key(key)),
+                            _ => return Err(self.error("expected a quoted key")),
+                        },
+                        Some(character) if is_key_character(character) => {
+                            let key: String = self
+                                .rest()
+                                .chars()
+                                .take_while(|c| is_key_character(*c))
+                                .collect();
+                            self.position += key.len();
+                            path.push(Segment::Key(key));
+                        }
+                        _ if path.is_empty() => return Ok(path),
+                        _ => return Err(self.error("expected a key after '.'")),
+                    }
+                }
+                Some('[') => {
+                    self.position += 1;
+                    let digits: String = self
+                        .rest()
+                        .chars()
+                        .take_while(|c| c.is_ascii_digit())
+                        .collect();
+                    let index = digits
+                        .parse::<usize>()
+                        .map_err(|_| self.error("expected an array index"))?;
+                    self.position += digits.len();
+                    self.expect(']')?;
+                    path.push(Segment::Index(index));
+                }
+                _ => return Ok(path),
+            }
+        }
+    }
+
+    fn parse_json(&mut self) -> Result<Value, TransformError> {
+        let mut values = Deserializer::from_str(self.rest()).into_iter::<Value>();
+
+        match values.next() {
+            Some(Ok(value)) => {
+                self.position += values.byte_offset();
+                Ok(value)
+            }
+            Some(Err(error)) => Err(self.error(&format!("invalid JSON value ({})", error))),
+            None => Err(self.error("expected a JSON value")),
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TransformError> {
+        match self.peek() {
+            Some(character) if character == expected => {
+                self.position += expected.len_utf8();
+                Ok(())
+            }
+            _ => Err(self.error(&format!("expected '{}'", expected))),
+        }
+    }
+
+    /// Skips spaces and comments, and line breaks too when `newlines` is set.
+    fn skip_whitespace(&mut self, newlines: bool) {
+        loop {
+            match self.peek() {
+                Some('\n') if !newlines => return,
+                Some(character) if character.is_whitespace() => {
+                    self.position += character.len_utf8()
+                }
+                Some('#') => {
+                    self.position += self.rest().find('\n').unwrap_or(self.rest().len());
+                }
+                _ => return,
+            }
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.rest().chars().next()
+    }
+
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, message: &str) -> TransformError {
+        let consumed = &self.source[..self.position];
+        let line = consumed.matches('\n').count() + 1;
+        let column = consumed.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+
+        TransformError(format!("{} at line {}, column {}", message, line, column))
+    }
+}
+
+fn is_key_character(character: char) -> bool {
+    character.is_alphanumeric() || character == '_' || character == '-' || character == '$'
+}
+
+/// Largest execution input accepted by Step Functions, in bytes.
+pub const MAX_INPUT_BYTES: usize = 256 * 1024;
+
+/// Pretty printed JSON input, or the input as it is when it is not valid JSON. Only the
+/// whitespace between the tokens changes, keys and numbers are written as in the input.
+pub fn pretty_input(input: &str) -> String {
+    if serde_json::from_str::<Value>(input).is_err() {
+        return input.to_string();
+    }
+
+    let mut pretty = String::with_capacity(input.len() * 2);
+    let mut depth = 0;
+    let (mut in_string, mut escaped) = (false, false);
+    let mut characters = input.chars().peekable();
+    while let Some(character) = characters.next() {
+        if in_string {
+            pretty.push(character);
+            match character {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+
+        match character {
+            '"' => {
+                in_string = true;
+                pretty.push(character);
+            }
+            '{' | '[' => {
+                pretty.push(character);
+                while characters.next_if(|next| next.is_whitespace()).is_some() {}
+                match characters.next_if(|next| matches!(next, '}' | ']')) {
+                    Some(close) => pretty.push(close),
+                    None => {
+                        depth += 1;
+                        new_line(&mut pretty, depth);
+                    }
+                }
+            }
+            '}' | ']' => {
+                depth -= 1;
+                new_line(&mut pretty, depth);
+                pretty.push(character);
+            }
+            ',' => {
+                pretty.push(character);
+                new_line(&mut pretty, depth);
+            }
+            ':' => pretty.push_str(": "),
+            _ if character.is_whitespace() => {}
+            _ => pretty.push(character),
+        }
+    }
+
+    pretty + "\n"
+}
+
+fn new_line(pretty: &mut String, depth: usize) {
+    pretty.push('\n');
+    pretty.push_str(&"  ".repeat(depth));
+}
+
+/// Checks that an input edited by hand can start an execution, returning it as it was written.
+pub fn validate_input(input: &str) -> Result<String, TransformError> {
+    serde_json::from_str::<Value>(input)
+        .map_err(|error| TransformError(format!("input is not valid JSON: {}", error)))?;
+
+    if input.len() > MAX_INPUT_BYTES {
+        return Err(TransformError(format!(
+            "input is {} bytes, over the {} KB payload limit",
+            input.len(),
+            MAX_INPUT_BYTES / 1024
+        )));
+    }
+
+    Ok(input.to_string())
+}
+
+/// Unified diff between the original and transformed inputs, laid out one value per line but
+/// otherwise as they were given.
+pub fn input_diff(original: &str, transformed: &str) -> String {
+    let (original, transformed) = (pretty_input(original), pretty_input(transformed));
+
+    TextDiff::from_lines(&original, &transformed)
+        .unified_diff()
+        .context_radius(3)
+        .header("original", "transformed")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transform(raw_transform: &str, input: Value) -> Value {
+        let transformed = InputTransform::parse(raw_transform)
+            .unwrap()
+            .apply(&input.to_string())
+            .unwrap();
+        serde_json::from_str(&transformed).unwrap()
+    }
+
+    #[test]
+    fn should_apply_merge_patch() {
+        assert_eq!(
+            transform(
+                r#"{"order": {"retry": true, "debug": null}}"#,
+                json!({"order": {"id": 1, "debug": true}})
+            ),
+            json!({"order": {"id": 1, "retry": true}})
+        );
+    }
+
+    #[test]
+    fn should_apply_json_patch() {
+        assert_eq!(
+            transform(
+                r#"[
+                    {"op": "replace", "path": "/order/id", "value": 2},
+                    {"op": "add", "path": "/items/-", "value": "egg"}
+                ]"#,
+                json!({"order": {"id": 1}, "items": ["t-rex"]})
+            ),
+            json!({"order": {"id": 2}, "items": ["t-rex", "egg"]})
+        );
+    }
+
+    #[test]
+    fn should_fail_json_patch_on_missing_path() {
+        let patch =
+            InputTransform::parse(r#"[{"op": "replace", "path": "/order/id", "value": 2}]"#)
+                .unwrap();
+
+        assert!(patch.apply("{}").is_err());
+    }
+
+    #[test]
+    fn should_apply_expression() {
+        assert_eq!(
+            transform(
+                "# fix the bad orders\n\
+                 .order.retry = true | .order.\"customer id\" = \"dino|saur\"\n\
+                 .items[0] = {\"name\": \"egg\"}\n\
+                 .copy = .order.id\n\
+                 del(.debug)\n\
+                 del(.items[1])",
+                json!({"order": {"id": 1}, "items": ["t-rex", "raptor"], "debug": true})
+            ),
+            json!({
+                "order": {"id": 1, "retry": true, "customer id": "dino|saur"},
+                "items": [{"name": "egg"}],
+                "copy": 1
+            })
+        );
+    }
+
+    #[test]
+    fn should_replace_whole_input_with_identity_path() {
+        assert_eq!(
+            transform(". = {\"fresh\": true}", json!({"stale": true})),
+            json!({"fresh": true})
+        );
+    }
+
+    #[test]
+    fn should_report_expression_error_location() {
+        assert_eq!(
+            InputTransform::parse(".order.retry = true\n.order.id == 2").unwrap_err(),
+            TransformError(
+                "invalid JSON value (expected value at line 1 column 1) at line 2, column 12"
+                    .to_string()
+            )
+        );
+        assert!(InputTransform::parse("order = 1").is_err());
+        assert!(InputTransform::parse("del(.order").is_err());
+        assert!(InputTransform::parse("# nothing").is_err());
+    }
+
+    #[test]
+    fn should_fail_expression_on_non_object() {
+        let expression = InputTransform::parse(".order.id = 2").unwrap();
+
+        assert_eq!(
+            expression.apply(r#"{"order": "egg"}"#).unwrap_err(),
+            TransformError("cannot set .id on a non object".to_string())
+        );
+        assert!(expression.apply("not json").is_err());
+    }
+
+    #[test]
+    fn should_diff_pretty_printed_inputs() {
+        assert_eq!(
+            input_diff(r#"{"id":1,"retry":false}"#, r#"{"id":1,"retry":true}"#),
+            "--- original\n\
+             +++ transformed\n\
+             @@ -1,4 +1,4 @@\n \
+             {\n   \
+             \"id\": 1,\n\
+             -  \"retry\": false\n\
+             +  \"retry\": true\n \
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_key_order_and_numbers_of_transformed_input() {
+        let transformed = InputTransform::parse(r#"{"retry": true}"#)
+            .unwrap()
+            .apply(r#"{"zebra":1.10,"id":123456789012345678901234}"#)
+            .unwrap();
+
+        assert_eq!(
+            transformed,
+            r#"{"zebra":1.10,"id":123456789012345678901234,"retry":true}"#
+        );
+    }
+
+    #[test]
+    fn should_pretty_print_inputs_as_they_were_given() {
+        assert_eq!(
+            pretty_input(
+                r#"{"zebra":1.10,"id":[], "text":"a,\"b\": {c}","items":[1,{"egg":1E2}]}"#
+            ),
+            "{\n  \"zebra\": 1.10,\n  \"id\": [],\n  \"text\": \"a,\\\"b\\\": {c}\",\n  \
+             \"items\": [\n    1,\n    {\n      \"egg\": 1E2\n    }\n  ]\n}\n"
+        );
+        assert_eq!(pretty_input("not {json}"), "not {json}");
+    }
+
+    #[test]
+    fn should_diff_numbers_as_they_were_given() {
+        assert_eq!(
+            input_diff(r#"{"price":1.10}"#, r#"{"price":1.1}"#),
+            "--- original\n\
+             +++ transformed\n\
+             @@ -1,3 +1,3 @@\n \
+             {\n\
+             -  \"price\": 1.10\n\
+             +  \"price\": 1.1\n \
+             }\n"
+        );
+    }
+
+    #[test]
+    fn should_keep_valid_edited_input_as_written() {
+        let input = "{\n  \"zebra\": 1.10,\n  \"egg\": 123456789012345678901234\n}\n";
+
+        assert_eq!(validate_input(input), Ok(input.to_string()));
+    }
+
+    #[test]
+    fn should_reject_invalid_edited_input() {
+        let error = validate_input("{\"egg\": }").unwrap_err();
+
+        assert!(error.to_string().starts_with("input is not valid JSON"));
+    }
+
+    #[test]
+    fn should_reject_edited_input_over_payload_limit() {
+        let input = json!({ "egg": "x".repeat(MAX_INPUT_BYTES) }).to_string();
+
+        let error = validate_input(&input).unwrap_err();
+
+        assert!(error.to_string().ends_with("over the 256 KB payload limit"));
+    }
+}
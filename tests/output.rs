@@ -0,0 +1,112 @@
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::process::{Command, Output};
+use std::thread;
+
+#[test]
+fn should_keep_logs_out_of_the_rendered_output() {
+    let home = std::env::temp_dir().join("sfn-paw-output-test");
+    let output = Command::new(env!("CARGO_BIN_EXE_sfn-paw"))
+        .args([
+            "list-machines",
+            "--region",
+            "us-east-1",
+            "--endpoint-url",
+            "http://127.0.0.1:1",
+            "--output",
+            "json",
+        ])
+        .env("HOME", &home)
+        .env("AWS_ACCESS_KEY_ID", "AKIDPAW")
+        .env("AWS_SECRET_ACCESS_KEY", "paw")
+        .env("AWS_MAX_ATTEMPTS", "1")
+        .env("RUST_LOG", "debug")
+        .output()
+        .unwrap();
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("\"name\":\"paw\""));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+}
+
+/// Answers the Step Functions requests of one paw run with a machine and its `executions`.
+fn serve_machine(executions: &'static str) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let endpoint = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut reader = BufReader::new(stream.unwrap());
+            let (mut target, mut content_length) = (String::new(), 0);
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                    break;
+                }
+                let (name, value) = line.split_once(':').unwrap_or_default();
+                match name.to_ascii_lowercase().as_str() {
+                    "x-amz-target" => target = value.trim().to_string(),
+                    "content-length" => content_length = value.trim().parse().unwrap(),
+                    _ => {}
+                }
+            }
+            reader.read_exact(&mut vec![0; content_length]).unwrap();
+
+            let body = match target.as_str() {
+                "AWSStepFunctions.DescribeStateMachine" => r#"{"stateMachineArn":"arn:aws:states:us-east-1:123456789012:stateMachine:PawMachine","name":"PawMachine","definition":"{}","roleArn":"arn:aws:iam::123456789012:role/paw","type":"STANDARD","creationDate":0}"#.to_string(),
+                _ => format!(r#"{{"executions":{}}}"#, executions),
+            };
+            write!(
+                reader.get_mut(),
+                "HTTP/1.1 200 OK\r\nContent-Type: application/x-amz-json-1.0\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+
+    endpoint
+}
+
+fn retry_failed(endpoint: &str) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_sfn-paw"))
+        .args([
+            "retry-failed",
+            "--machine",
+            "arn:aws:states:us-east-1:123456789012:stateMachine:PawMachine",
+            "--region",
+            "us-east-1",
+            "--endpoint-url",
+            endpoint,
+        ])
+        .env("HOME", std::env::temp_dir().join("sfn-paw-output-test"))
+        .env("AWS_ACCESS_KEY_ID", "AKIDPAW")
+        .env("AWS_SECRET_ACCESS_KEY", "paw")
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn should_list_executions_to_retry_on_stderr() {
+    let output = retry_failed(&serve_machine(
+        r#"[{"executionArn":"arn:aws:states:us-east-1:123456789012:execution:PawMachine:dinosaur","stateMachineArn":"arn:aws:states:us-east-1:123456789012:stateMachine:PawMachine","name":"dinosaur","status":"FAILED","startDate":0}]"#,
+    ));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("dinosaur : 1970-01-01T00:00:00Z : FAILED"));
+    assert!(stderr.contains("run again with --yes"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+}
+
+#[test]
+fn should_tell_no_executions_were_found_on_stderr() {
+    let output = retry_failed(&serve_machine("[]"));
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(output.status.success(), "{}", stderr);
+    assert!(stderr.contains("No executions to retry found for PawMachine"));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "");
+}